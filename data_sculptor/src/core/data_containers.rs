@@ -16,7 +16,7 @@ pub struct DayDataUnparsed
 }
 
 /// Struct holding data for a single day parsed into proper data structures.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct DayDataParsed
 {
     pub date: DateKey,
@@ -99,6 +99,18 @@ pub fn parse(unparsed: DayDataUnparsed) -> Result<DayDataParsed, ParseError>
     })
 }
 
+/// Turns the given [`DayDataParsed`] back into its unparsed form.
+pub fn unparse(parsed: &DayDataParsed) -> DayDataUnparsed
+{
+    let mut entries: IndexMap<String, String> = IndexMap::new();
+    for (key, value) in &parsed.entries
+    {
+        entries.insert(key.title.clone(), value.string_value.clone());
+    }
+
+    DayDataUnparsed {date: parsed.date.date_string.clone(), entries}
+}
+
 pub fn parse_and_sort_by_date(unparsed_days: Vec<DayDataUnparsed>)
     -> Result<Vec<DayDataParsed>, ParseError>
 {
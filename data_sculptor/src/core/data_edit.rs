@@ -0,0 +1,74 @@
+//! Specifies the edits that can be applied to the data of the
+//! [`DataManager`](crate::core::data_manager::DataManager).
+
+use std::fmt::{Display, Formatter};
+use indexmap::IndexMap;
+use crate::core::data_containers::ParseError;
+
+/// A single change to the loaded data. Days are identified by their date string and
+/// entries by their key title so that edits stay valid when the order of the data changes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataEdit
+{
+    /// Sets the value of an existing key.
+    SetValue {date: String, key: String, value: String},
+
+    /// Inserts a new key-value pair at the given position of the day's entries.
+    InsertKey {date: String, key: String, value: String, index: usize},
+
+    /// Removes an existing key-value pair.
+    RemoveKey {date: String, key: String},
+
+    /// Inserts a new day with the given entries.
+    InsertDay {date: String, entries: IndexMap<String, String>},
+
+    /// Removes an existing day.
+    RemoveDay {date: String}
+}
+
+impl DataEdit
+{
+    /// The date string of the day the edit changes.
+    pub fn date(&self) -> &str
+    {
+        match self
+        {
+            DataEdit::SetValue {date, ..} | DataEdit::InsertKey {date, ..} | DataEdit::RemoveKey {date, ..}
+            | DataEdit::InsertDay {date, ..} | DataEdit::RemoveDay {date} => {date}
+        }
+    }
+}
+
+/// Errors that can occur while applying a [`DataEdit`].
+#[derive(Debug, PartialEq)]
+pub enum EditError
+{
+    UnknownDate(String),
+    UnknownKey(String),
+    DuplicateKey(String),
+    EmptyKey,
+    InvalidDay(ParseError)
+}
+
+impl Display for EditError
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            EditError::UnknownDate(date) =>
+                {write!(f, "There is no day with the date '{}'!", date)}
+
+            EditError::UnknownKey(key) =>
+                {write!(f, "There is no key '{}' on this day!", key)}
+
+            EditError::DuplicateKey(key) =>
+                {write!(f, "The key '{}' already exists on this day!", key)}
+
+            EditError::EmptyKey =>
+                {write!(f, "Keys can not be empty!")}
+
+            EditError::InvalidDay(e) => {e.fmt(f)}
+        }
+    }
+}
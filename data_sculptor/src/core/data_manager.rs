@@ -1,12 +1,48 @@
 //! Module for the [`DataManager`] that sits at the core of data_sculptor.
 
-use crate::core::data_containers::{DayDataParsed, DayDataUnparsed, parse_and_sort_by_date};
-use crate::file_io::data_loader;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use indexmap::{IndexMap, IndexSet};
+use crate::core::data_containers::{DayDataParsed, DayDataUnparsed, EntryKey, parse, parse_and_sort_by_date, ParseError, unparse};
+use crate::core::data_edit::{DataEdit, EditError};
+use crate::core::search_index::SearchIndex;
+use crate::file_io::{data_loader, data_writer};
 
 /// Struct holding and managing all parsed data for the runtime of the program.
+#[derive(Default)]
 pub struct DataManager
 {
-    pub data: Vec<DayDataParsed>
+    pub data: Vec<DayDataParsed>,
+
+    /// Incremented every time the data changes so that views can tell when to
+    /// rebuild anything they derived from it.
+    pub generation: u64,
+
+    /// Path of the file the data was loaded from.
+    pub source_path: Option<String>,
+
+    /// Whether the data has been edited since it was loaded or last saved.
+    pub dirty: bool
+}
+
+/// Events sent while data is being read in the background. See [`DataManager::read_data`].
+#[derive(Debug, Clone)]
+pub enum LoadingEvent
+{
+    /// Fraction (0.0 to 1.0) of the data that has been processed so far.
+    Progress(f32),
+
+    /// The parsed data or the failure message.
+    Finished(Result<LoadedData, String>)
+}
+
+/// Data read in the background together with the [`SearchIndex`] built from it,
+/// so that the index does not have to be built on the UI thread.
+#[derive(Debug, Clone)]
+pub struct LoadedData
+{
+    pub data: Vec<DayDataParsed>,
+    pub search_index: SearchIndex
 }
 
 impl DataManager
@@ -17,23 +53,237 @@ impl DataManager
     pub fn load_data(&mut self, file_path: &str) -> (bool, String)
     {
         self.data.clear();
+        self.generation += 1;
+        self.source_path = None;
+        self.dirty = false;
+
+        match DataManager::read_data(file_path, &mut |_| {}, &AtomicBool::new(false))
+        {
+            Ok(data) =>
+                {
+                    self.replace_data(data, file_path);
+                    (true, String::from("Loaded successfully"))
+                }
+            Err(e) => {(false, e)}
+        }
+    }
+
+    /// Reads and parses the data at the given file path without touching the currently loaded
+    /// data. This makes it safe to call from a background thread while the old data is still
+    /// being displayed. The result can then be swapped in with [`DataManager::replace_data`].
+    ///
+    /// # Arguments
+    /// * `file_path` - Path of the file to load.
+    /// * `on_progress` - Called with the fraction (0.0 to 1.0) of the file processed so far.
+    /// * `cancelled` - Stops loading with an error as soon as it is set.
+    pub fn read_data(file_path: &str, on_progress: &mut dyn FnMut(f32), cancelled: &AtomicBool)
+        -> Result<Vec<DayDataParsed>, String>
+    {
+        // Reading the bytes is what takes time. The rest is reported at once.
+        let mut on_read_progress = |progress: f32| on_progress(progress * 0.9);
+
+        let data_unparsed = data_loader::load_data_file_with_progress
+            (file_path, &mut on_read_progress, cancelled)
+            .map_err(|e| e.to_string())?;
+
+        let data_parsed = parse_and_sort_by_date(data_unparsed).map_err(|e| e.to_string())?;
+        on_progress(1.0);
 
-        let data_unparsed: Vec<DayDataUnparsed>;
-        match data_loader::load_data_file(file_path)
+        Ok(data_parsed)
+    }
+
+    /// Reads and parses the data of all given files like [`DataManager::read_data`] and merges
+    /// it into one list of days. The entries of days with the same date are combined. If several
+    /// files hold the same key for a day, the value of the file that comes last wins.
+    pub fn read_merged_data(file_paths: &[String], on_progress: &mut dyn FnMut(f32), cancelled: &AtomicBool)
+        -> Result<Vec<DayDataParsed>, String>
+    {
+        let file_count = file_paths.len().max(1) as f32;
+        let mut merged: IndexMap<String, IndexMap<String, String>> = IndexMap::new();
+
+        for (i, file_path) in file_paths.iter().enumerate()
         {
-            Ok(data) => {data_unparsed = data;}
-            Err(e) => {return (false, e.to_string());}
+            let mut on_read_progress = |progress: f32| on_progress((i as f32 + progress * 0.9) / file_count);
+
+            let days = data_loader::load_data_file_with_progress(file_path, &mut on_read_progress, cancelled)
+                .map_err(|e| format!("{}: {}", file_path, e))?;
+
+            for day in days
+            {
+                merged.entry(day.date).or_default().extend(day.entries);
+            }
         }
 
-        let mut data_parsed: Vec<DayDataParsed>;
-        match parse_and_sort_by_date(data_unparsed)
+        let data_unparsed = merged.into_iter()
+            .map(|(date, entries)| DayDataUnparsed {date, entries})
+            .collect();
+
+        let data_parsed = parse_and_sort_by_date(data_unparsed).map_err(|e| e.to_string())?;
+        on_progress(1.0);
+
+        Ok(data_parsed)
+    }
+
+    /// Replaces all currently loaded data with the given data loaded from the given file path.
+    pub fn replace_data(&mut self, data: Vec<DayDataParsed>, source_path: &str)
+    {
+        self.data = data;
+        self.generation += 1;
+        self.source_path = Some(String::from(source_path));
+        self.dirty = false;
+    }
+
+    /// Returns the number of days in the given data whose dates are not part of the
+    /// currently loaded data.
+    pub fn count_new_days(&self, data: &[DayDataParsed]) -> usize
+    {
+        let loaded_dates: HashSet<&str> = self.data.iter()
+            .map(|day| day.date.date_string.as_str())
+            .collect();
+
+        data.iter().filter(|day| !loaded_dates.contains(day.date.date_string.as_str())).count()
+    }
+
+    /// Replaces all currently loaded data with the given data merged from several files.
+    /// See [`DataManager::read_merged_data`]. The merged data does not belong to any file,
+    /// so it can only be saved through [`data_writer`].
+    pub fn replace_data_merged(&mut self, data: Vec<DayDataParsed>)
+    {
+        self.data = data;
+        self.generation += 1;
+        self.source_path = None;
+        self.dirty = false;
+    }
+
+    /// Writes all data back to the file it was loaded from.
+    pub fn save_data(&mut self) -> Result<(), String>
+    {
+        let Some(file_path) = &self.source_path else
+        {
+            return Err(String::from("There is no file to save to!"));
+        };
+
+        data_writer::write_data(file_path, &self.data).map_err(|e| e.to_string())?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Applies the given [`DataEdit`] to the data. Every changed day is validated by
+    /// parsing it again, so an invalid edit leaves the data untouched.
+    ///
+    /// # Returns
+    /// - `Ok`([`DataEdit`]) holding the edit that reverts the applied one
+    /// - `Err`([`EditError`]) if the edit could not be applied
+    pub fn apply_edit(&mut self, edit: DataEdit) -> Result<DataEdit, EditError>
+    {
+        let inverse = match edit
+        {
+            DataEdit::SetValue {date, key, value} =>
+                {
+                    let (day_index, mut entries) = self.unparsed_day(&date)?;
+                    let entry = entries.get_mut(&key).ok_or(EditError::UnknownKey(key.clone()))?;
+                    let old_value = std::mem::replace(entry, value);
+
+                    self.replace_day(day_index, date.clone(), entries)?;
+                    DataEdit::SetValue {date, key, value: old_value}
+                }
+
+            DataEdit::InsertKey {date, key, value, index} =>
+                {
+                    if key.is_empty()
+                    {
+                        return Err(EditError::EmptyKey);
+                    }
+
+                    let (day_index, mut entries) = self.unparsed_day(&date)?;
+                    if entries.contains_key(&key)
+                    {
+                        return Err(EditError::DuplicateKey(key));
+                    }
+                    entries.shift_insert(index.min(entries.len()), key.clone(), value);
+
+                    self.replace_day(day_index, date.clone(), entries)?;
+                    DataEdit::RemoveKey {date, key}
+                }
+
+            DataEdit::RemoveKey {date, key} =>
+                {
+                    let (day_index, mut entries) = self.unparsed_day(&date)?;
+                    let (index, _, value) = entries.shift_remove_full(&key)
+                        .ok_or(EditError::UnknownKey(key.clone()))?;
+
+                    self.replace_day(day_index, date.clone(), entries)?;
+                    DataEdit::InsertKey {date, key, value, index}
+                }
+
+            DataEdit::InsertDay {date, entries} =>
+                {
+                    if self.find_day(&date).is_some()
+                    {
+                        return Err(EditError::InvalidDay(ParseError::DuplicateDate(date)));
+                    }
+
+                    let day = parse(DayDataUnparsed {date: date.clone(), entries})
+                        .map_err(EditError::InvalidDay)?;
+
+                    // Keep the data sorted by date
+                    let position = self.data
+                        .partition_point(|other| other.date.naive_date < day.date.naive_date);
+                    self.data.insert(position, day);
+
+                    DataEdit::RemoveDay {date}
+                }
+
+            DataEdit::RemoveDay {date} =>
+                {
+                    let day_index = self.find_day(&date).ok_or(EditError::UnknownDate(date.clone()))?;
+                    let day = self.data.remove(day_index);
+
+                    DataEdit::InsertDay {date, entries: unparse(&day).entries}
+                }
+        };
+
+        self.generation += 1;
+        self.dirty = true;
+        Ok(inverse)
+    }
+
+    /// Returns the index of the day with the given date string.
+    pub fn find_day(&self, date: &str) -> Option<usize>
+    {
+        self.data.iter().position(|day| day.date.date_string == date)
+    }
+
+    /// Returns the value of the given key of the day with the given date string.
+    pub fn get_value(&self, date: &str, key: &str) -> Option<String>
+    {
+        let day = &self.data[self.find_day(date)?];
+        let value = day.entries.get(&EntryKey {title: String::from(key)})?;
+        Some(value.string_value.clone())
+    }
+
+    /// Returns the titles of all keys in the data in the order they first appear in.
+    pub fn all_keys(&self) -> Vec<String>
+    {
+        let mut keys: IndexSet<&str> = IndexSet::new();
+        for day in &self.data
         {
-            Ok(parsed) => {data_parsed = parsed;}
-            Err(e) => {return (false, e.to_string());}
+            keys.extend(day.entries.keys().map(|key| key.title.as_str()));
         }
+        keys.into_iter().map(String::from).collect()
+    }
 
-        self.data.append(&mut data_parsed);
+    fn unparsed_day(&self, date: &str) -> Result<(usize, IndexMap<String, String>), EditError>
+    {
+        let day_index = self.find_day(date).ok_or(EditError::UnknownDate(String::from(date)))?;
+        Ok((day_index, unparse(&self.data[day_index]).entries))
+    }
 
-        return (true, String::from("Loaded successfully"));
+    fn replace_day(&mut self, day_index: usize, date: String, entries: IndexMap<String, String>)
+        -> Result<(), EditError>
+    {
+        let day = parse(DayDataUnparsed {date, entries}).map_err(EditError::InvalidDay)?;
+        self.data[day_index] = day;
+        Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,66 @@
+//! Handles sorting [`DayDataParsed`] by the values of a specific [`EntryKey`].
+
+use std::cmp::Ordering;
+use crate::core::data_containers::{DayDataParsed, EntryKey, EntryValue};
+
+/// Sorts the given days by the value corresponding to the given key.
+/// Values that parse to a number come first and are compared numerically, all other values
+/// follow and are compared as strings. Days that do not contain the key are always put last,
+/// regardless of the sorting direction.
+pub fn sort_by_key(days: &mut [&DayDataParsed], key: &EntryKey, ascending: bool)
+{
+    days.sort_by(|a, b|
+        {
+            match (a.entries.get(key), b.entries.get(key))
+            {
+                (Some(a_value), Some(b_value)) =>
+                    {
+                        let ordering = compare_values(a_value, b_value);
+                        if ascending {ordering} else {ordering.reverse()}
+                    }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal
+            }
+        });
+}
+
+/// Compares the two given [`EntryValue`]s by their [`SortKey`]s.
+/// This is a total order, even when numbers and text are mixed.
+pub fn compare_values(a: &EntryValue, b: &EntryValue) -> Ordering
+{
+    SortKey::from(a).compare(&SortKey::from(b))
+}
+
+/// What an [`EntryValue`] is sorted by. Numbers are ordered before text.
+enum SortKey<'a>
+{
+    Number(f64),
+    Text(&'a str)
+}
+
+impl<'a> From<&'a EntryValue> for SortKey<'a>
+{
+    fn from(value: &'a EntryValue) -> Self
+    {
+        match value.string_value.trim().parse::<f64>()
+        {
+            Ok(number) => SortKey::Number(number),
+            Err(_) => SortKey::Text(&value.string_value)
+        }
+    }
+}
+
+impl SortKey<'_>
+{
+    fn compare(&self, other: &Self) -> Ordering
+    {
+        match (self, other)
+        {
+            (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+            (SortKey::Number(_), SortKey::Text(_)) => Ordering::Less,
+            (SortKey::Text(_), SortKey::Number(_)) => Ordering::Greater,
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b)
+        }
+    }
+}
@@ -9,6 +9,7 @@ use crate::core::filters::filter_commands::FilterCommand;
 pub enum Keywords
 {
     Contains,
+    Equals,
     KeyValueContains,
     NumOp,
     KeyValueNumOp,
@@ -17,12 +18,42 @@ pub enum Keywords
 
 impl Keywords
 {
+    /// All keywords in the order they are suggested in.
+    pub const ALL: [Keywords; 6] =
+        [
+            Keywords::Contains,
+            Keywords::Equals,
+            Keywords::KeyValueContains,
+            Keywords::NumOp,
+            Keywords::KeyValueNumOp,
+            Keywords::Date
+        ];
+
+    /// Whether the [`FilterCommand`] of the keyword can be used in filters of the given
+    /// [`FilterType`]. Mirrors the checks in [`parse`].
+    pub fn is_valid_for(&self, filter_type: &FilterType) -> bool
+    {
+        match self
+        {
+            Keywords::Contains | Keywords::Equals | Keywords::NumOp => {filter_type != &FilterType::Value}
+            Keywords::KeyValueContains | Keywords::KeyValueNumOp => {filter_type == &FilterType::Value}
+            Keywords::Date => {filter_type == &FilterType::Date}
+        }
+    }
+
+    /// Whether the first argument of the [`FilterCommand`] of the keyword is a key title.
+    pub fn takes_key(&self) -> bool
+    {
+        matches!(self, Keywords::KeyValueContains | Keywords::KeyValueNumOp)
+    }
+
     /// The &str representation of the [`FilterCommand`] Keyword
     pub fn cmd_str(&self) -> &'static str
     {
         match &self
         {
             Keywords::Contains => "contains",
+            Keywords::Equals => "equals",
             Keywords::KeyValueContains => "kv-contains",
             Keywords::NumOp => "numop",
             Keywords::KeyValueNumOp => "kv-numop",
@@ -44,6 +75,8 @@ impl Keywords
 /// - `None` if parsing was unsuccessful
 pub fn parse(filter_type: &FilterType, input: String,) -> Option<FilterCommand>
 {
+    let input = input.trim();
+
     if input.starts_with(Keywords::Contains.cmd_str())
     {
         if filter_type != &FilterType::Value
@@ -52,6 +85,11 @@ pub fn parse(filter_type: &FilterType, input: String,) -> Option<FilterCommand>
         }
     }
 
+    if input.starts_with(Keywords::Equals.cmd_str()) && filter_type != &FilterType::Value
+    {
+        return parse_equals(&input[Keywords::Equals.cmd_len()..]);
+    }
+
     if input.starts_with(Keywords::KeyValueContains.cmd_str())
     {
         if filter_type == &FilterType::Value
@@ -89,118 +127,132 @@ pub fn parse(filter_type: &FilterType, input: String,) -> Option<FilterCommand>
 
 fn parse_contains(input: &str) -> Option<FilterCommand>
 {
-    let contains_string = get_quotation_string_and_verify_is_last(input)?;
+    let [contains_string] = parse_quoted_arguments(input)?;
     Some(FilterCommand::Contains(contains_string))
 }
 
-/// Gets the string keyword inside quotation marks for 'contains' filters.
-/// Verifies that the keyword is the very last part of the input. Returns None if that
-/// is not the case.
-fn get_quotation_string_and_verify_is_last(input: &str) -> Option<String>
+fn parse_equals(input: &str) -> Option<FilterCommand>
 {
-    let start = input.find(" \"")?;
-
-    if start != 0
-    {
-        return None;
-    }
-
-    let end = input[start + 2..].find("\"")? + start + 2;
-    if end + 1 != input.len()
-    {
-        return None
-    }
-
-    return Some(String::from(&input[start + 2..end]))
+    let [equals_string] = parse_quoted_arguments(input)?;
+    Some(FilterCommand::Equals(equals_string))
 }
 
 fn parse_kv_contains(input: &str) -> Option<FilterCommand>
 {
-    // KEY
-    let start = input.find(" \"")?;
-
-    if start != 0
-    {
-        return None;
-    }
-
-    let end = input[start + 2..].find("\"")? + start + 2;
-    let key = String::from(&input[start + 2..end]);
-
-    // CONTAINS
-    let contains_string = get_quotation_string_and_verify_is_last(&input[end + 1..])?;
+    let [key, contains_string] = parse_quoted_arguments(input)?;
     Some(FilterCommand::KeyValueContains(key, contains_string))
 }
 
 fn parse_numop(input: &str) -> Option<FilterCommand>
 {
-    let (op, num) = get_numop_params(input)?;
-    return Some(FilterCommand::NumOp(op, num));
+    let [op, num] = parse_quoted_arguments(input)?;
+    Some(FilterCommand::NumOp(op, parse_number(&num)?))
 }
 
-fn get_numop_params(input: &str) -> Option<(String, f32)>
+fn parse_kv_numop(input: &str) -> Option<FilterCommand>
 {
-    let start = input.find(" \"")?;
-
-    if start != 0
-    {
-        return None;
-    }
-
-    let end = input[start + 2..].find("\"")? + start + 2;
-    let op = &input[start + 2..end];
+    let [key, op, num] = parse_quoted_arguments(input)?;
+    Some(FilterCommand::KeyValueNumOp(key, op, parse_number(&num)?))
+}
 
-    let num_string = get_quotation_string_and_verify_is_last(&input[end + 1..])?;
+fn parse_number(input: &str) -> Option<f32>
+{
+    input.parse::<f32>().ok()
+}
 
-    let num = match num_string.parse::<f32>()
-    {
-        Ok(num) => num,
-        Err(_) => return None
-    };
+fn parse_date_op(input: &str) -> Option<FilterCommand>
+{
+    let input = input.trim_start();
+    let op = ["before", "after"].into_iter().find(|op| input.starts_with(op))?;
 
-    return Some((String::from(op), num));
+    let [date_string] = parse_quoted_arguments(&input[op.len()..])?;
+    let date = NaiveDate::parse_from_str(date_string.as_str(), DATE_FORMAT).ok()?;
+    Some(FilterCommand::Date(String::from(op), date))
 }
 
-fn parse_kv_numop(input: &str) -> Option<FilterCommand>
+// ARGUMENTS
+/// Characters that have to be escaped with a backslash inside the quoted arguments of a command.
+const ESCAPED_CHARS: [char; 3] = ['\\', '"', '}'];
+
+/// Reads exactly N quoted arguments from the input. Any whitespace around the arguments
+/// is ignored, anything else makes the input invalid.
+fn parse_quoted_arguments<const N: usize>(input: &str) -> Option<[String; N]>
 {
-    // KEY
-    let start = input.find(" \"")?;
+    let mut arguments = Vec::new();
+    let mut chars = input.chars().peekable();
 
-    if start != 0
+    loop
     {
-        return None;
-    }
+        while chars.next_if(|ch| ch.is_whitespace()).is_some() {}
 
-    let end = input[start + 2..].find("\"")? + start + 2;
-    let key = String::from(&input[start + 2..end]);
+        match chars.next()
+        {
+            Some('"') => {arguments.push(parse_quoted_string(&mut chars)?);}
+            Some(_) => {return None;}
+            None => {break;}
+        }
+    }
 
-    let (op, num) = get_numop_params(&input[end + 1..])?;
-    return Some(FilterCommand::KeyValueNumOp(key, op, num));
+    arguments.try_into().ok()
 }
 
-fn parse_date_op(input: &str) -> Option<FilterCommand>
+/// Reads the rest of a quoted string whose opening quotation mark has already been consumed
+/// and resolves its escape sequences. A backslash in front of any other character than the
+/// [`ESCAPED_CHARS`] is kept as is, so that inputs like `"C:\path"` still work.
+/// Returns None if the string is never closed.
+fn parse_quoted_string(chars: &mut impl Iterator<Item = char>) -> Option<String>
 {
-    let start: usize;
-    let mut op = String::new();
-
-    if let Some(index) = input.find("before")
+    let mut string = String::new();
+    loop
     {
-        start = index + 6;
-        op += "before";
+        match chars.next()?
+        {
+            '"' => {return Some(string);}
+            '\\' =>
+                {
+                    let next = chars.next()?;
+                    if !ESCAPED_CHARS.contains(&next)
+                    {
+                        string.push('\\');
+                    }
+                    string.push(next);
+                }
+            ch => {string.push(ch);}
+        }
     }
+}
 
-    else if let Some(index) = input.find("after")
+/// Escapes all characters of the given string that can not appear in a quoted argument as is.
+pub fn escape(string: &str) -> String
+{
+    let mut escaped = String::with_capacity(string.len());
+    for ch in string.chars()
     {
-        start = index + 5;
-        op += "after";
+        if ESCAPED_CHARS.contains(&ch)
+        {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
     }
+    escaped
+}
 
-    else
+/// Returns the byte indices of all occurrences of the given character in the input that are
+/// not escaped by a backslash.
+pub fn unescaped_indices(input: &str, target: char) -> Vec<usize>
+{
+    let mut indices = Vec::new();
+    let mut chars = input.char_indices();
+    while let Some((index, ch)) = chars.next()
     {
-        return None;
+        if ch == '\\'
+        {
+            chars.next();
+        }
+        else if ch == target
+        {
+            indices.push(index);
+        }
     }
-
-    let date_string = get_quotation_string_and_verify_is_last(&input[start..])?;
-    let date = NaiveDate::parse_from_str(date_string.as_str(), DATE_FORMAT).ok()?;
-    return Some(FilterCommand::Date(op, date));
-}
\ No newline at end of file
+    indices
+}
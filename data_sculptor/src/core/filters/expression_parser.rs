@@ -1,7 +1,15 @@
 //! Module for parsing [`String`]s into [`FilterExpression`]s.
+//!
+//! The input is split into tokens which are then parsed by a Pratt parser:
+//! - `not` (or `!`) binds stronger than any binary operator and can only precede an expression
+//! - `and`, `nand` (`&&` for `and`) bind stronger than `or`, `nor`, `xor` and `xnor` (`||` for `or`)
+//! - Binary operators of the same precedence are evaluated from left to right
 
+use std::fmt::{Display, Formatter};
 use std::iter::Peekable;
-use std::str::Chars;
+use std::ops::Range;
+use std::str::CharIndices;
+use indexmap::IndexMap;
 use crate::core::filters::command_parser;
 use crate::core::filters::filter::{FilterType};
 use crate::core::filters::filter_expression::FilterExpression;
@@ -12,81 +20,236 @@ enum Token
 {
     Operator(String),
     FilterCommand(String),
+    Macro(String),
+    Literal(bool),
     LeftParenthesis,
     RightParenthesis,
     Invalid
 }
 
+/// A [`Token`] together with the byte range of the input it was read from.
+type SpannedToken = (Token, Range<usize>);
+
+/// The kinds of pieces of an expression [`String`] that are highlighted differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightKind
+{
+    /// A filter command inside {}.
+    Command,
+
+    /// A quoted string inside a filter command.
+    QuotedString,
+
+    /// A known logical operator.
+    Operator,
+
+    /// A reference to a filter macro.
+    Macro,
+
+    /// A 'true' or 'false' literal.
+    Literal,
+
+    /// A parenthesis with a matching partner.
+    Parenthesis,
+
+    /// A parenthesis without a matching partner.
+    MismatchedParenthesis,
+
+    /// Anything the tokenizer does not recognize.
+    Invalid
+}
+
+/// A highlighted piece of an expression [`String`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightSpan
+{
+    /// Byte range of the piece in the input.
+    pub range: Range<usize>,
+    pub kind: HighlightKind
+}
+
+/// Named filter expressions that can be referenced inside other expressions as `@name`.
+/// Maps the name to the expression string.
+pub type FilterMacros = IndexMap<String, String>;
+
+/// Errors that can occur while parsing an expression [`String`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionError
+{
+    InvalidSyntax,
+
+    /// The content of a {} is not a valid command for the filter type.
+    InvalidCommand(String),
+
+    UnknownOperator(String),
+
+    /// Two expressions follow each other without an operator between them.
+    MissingOperator,
+
+    /// An operator is missing one of its expressions.
+    MissingOperand,
+
+    /// A 'not' follows an expression instead of preceding one.
+    MisplacedNot,
+
+    UnmatchedParenthesis,
+    UnknownMacro(String),
+
+    /// Holds the chain of macro names that leads back to its first element.
+    MacroCycle(Vec<String>),
+
+    /// The macro with the given name is not a valid expression.
+    InvalidMacro(String)
+}
+
+impl Display for ExpressionError
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            ExpressionError::InvalidSyntax => {write!(f, "Invalid filter syntax")}
+
+            ExpressionError::InvalidCommand(command) =>
+                {write!(f, "'{{{}}}' is not a valid command for this filter type", command)}
+
+            ExpressionError::UnknownOperator(operator) => {write!(f, "Unknown operator '{}'", operator)}
+            ExpressionError::MissingOperator => {write!(f, "Expressions have to be connected by an operator")}
+            ExpressionError::MissingOperand => {write!(f, "An operator is missing an expression")}
+
+            ExpressionError::MisplacedNot =>
+                {write!(f, "'not' has to precede an expression. Use 'nand', 'nor' or 'xnor' to negate an operator")}
+
+            ExpressionError::UnmatchedParenthesis => {write!(f, "Parentheses do not match")}
+            ExpressionError::UnknownMacro(name) => {write!(f, "There is no macro named '@{}'", name)}
+
+            ExpressionError::MacroCycle(names) =>
+                {
+                    let chain: Vec<String> = names.iter().map(|name| format!("@{}", name)).collect();
+                    write!(f, "Macros reference each other in a cycle: {} -> @{}",
+                           chain.join(" -> "), names[0])
+                }
+
+            ExpressionError::InvalidMacro(name) => {write!(f, "The macro '@{}' is not a valid filter", name)}
+        }
+    }
+}
+
+impl ExpressionError
+{
+    /// Whether the error is caused by the syntax of the input itself rather than by its macros.
+    pub fn is_syntax_error(&self) -> bool
+    {
+        !matches!(self, ExpressionError::UnknownMacro(_) | ExpressionError::MacroCycle(_)
+            | ExpressionError::InvalidMacro(_))
+    }
+}
+
 /// Tries to parse the given input string to a [`FilterExpression`]
 ///
 /// # Returns
 /// - `Some`([`FilterExpression`]) if the command was successfully parsed
 /// - `None` if parsing was unsuccessful
 pub fn parse(filter_type: &FilterType, input: &str) -> Option<FilterExpression>
+{
+    parse_with_macros(filter_type, input, &FilterMacros::new()).ok()
+}
+
+/// Tries to parse the given input string to a [`FilterExpression`], resolving all references
+/// to the given [`FilterMacros`].
+///
+/// # Returns
+/// - `Ok`([`FilterExpression`]) if the command was successfully parsed
+/// - `Err`([`ExpressionError`]) if parsing was unsuccessful
+pub fn parse_with_macros(filter_type: &FilterType, input: &str, macros: &FilterMacros)
+    -> Result<FilterExpression, ExpressionError>
+{
+    parse_resolving(filter_type, input, macros, &mut Vec::new())
+}
+
+/// Returns whether the given name can be used for a macro, i.e. is not empty and only
+/// consists of alphanumeric characters and underscores.
+pub fn is_valid_macro_name(name: &str) -> bool
+{
+    !name.is_empty() && name.chars().all(is_macro_name_char)
+}
+
+fn is_macro_name_char(ch: char) -> bool
+{
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Parses the given input while the macros with the given names are being resolved.
+fn parse_resolving(filter_type: &FilterType, input: &str, macros: &FilterMacros,
+                   resolving: &mut Vec<String>) -> Result<FilterExpression, ExpressionError>
 {
     // First, attempt to parse normally
-    let mut result = parse_tokenized(filter_type, input);
+    let mut result = parse_tokenized(filter_type, input, macros, resolving);
 
     // If that fails, wrap in {} and try again -> shortcut so that you do not need to wrap single
     // filter command expressions in {} manually.
-    if result.is_none() && !input.contains("{") && !input.contains("}")
+    let is_syntax_error = matches!(&result, Err(e) if e.is_syntax_error());
+    if is_syntax_error && !input.contains("{") && command_parser::unescaped_indices(input, '}').is_empty()
     {
         let mut wrapped_input = String::from("{") + input;
         wrapped_input += "}";
 
-        result = parse_tokenized(filter_type, wrapped_input.as_str())
+        let wrapped_result = parse_tokenized(filter_type, wrapped_input.as_str(), macros, resolving);
+        if wrapped_result.is_ok()
+        {
+            result = wrapped_result;
+        }
     }
 
     result
 }
 
-/// Attempts to parse the given input to a [`FilterExpression`] using a tokenized postfix
-/// approach.
-fn parse_tokenized(filter_type: &FilterType, input: &str) -> Option<FilterExpression>
+/// Attempts to parse the given input to a [`FilterExpression`] by tokenizing it
+/// and running the tokens through the [`Parser`].
+fn parse_tokenized(filter_type: &FilterType, input: &str, macros: &FilterMacros,
+                   resolving: &mut Vec<String>) -> Result<FilterExpression, ExpressionError>
 {
     let tokens = tokenize(input);
-    let postfix = infix_to_postfix(tokens);
-    expression_from_tokenized_postfix(postfix, filter_type)
+    let mut parser = Parser {tokens: tokens.into_iter().peekable(), filter_type, macros, resolving};
+    parser.parse()
 }
 
 fn tokenize(input: &str) -> Vec<Token>
+{
+    tokenize_spanned(input).into_iter().map(|(token, _)| token).collect()
+}
+
+fn tokenize_spanned(input: &str) -> Vec<SpannedToken>
 {
     let mut tokens = Vec::new();
-    let mut chars = input.chars().peekable();
-    let mut parenthesis_count = 0;
+    let mut chars = input.char_indices().peekable();
 
-    while let Some(&ch) = chars.peek()
+    while let Some(&(index, ch)) = chars.peek()
     {
         match ch
         {
-            '{' =>{tokenize_command(&mut chars, &mut tokens);},
+            '{' =>{tokenize_command(input, &mut chars, &mut tokens);},
 
-            '(' =>
+            '@' =>{tokenize_macro(input, &mut chars, &mut tokens);},
+
+            '!' =>
             {
                 chars.next();
-                tokens.push(Token::LeftParenthesis);
-                parenthesis_count += 1;
+                tokens.push((Token::Operator(String::from("not")), index..index + 1));
+            },
 
-                // Disallow empty ()
-                if let Some(&')') = chars.peek()
-                {
-                   tokens.push(Token::Invalid)
-                }
+            '&' | '|' =>{tokenize_symbol_operator(&mut chars, &mut tokens);},
+
+            '(' =>
+            {
+                chars.next();
+                tokens.push((Token::LeftParenthesis, index..index + 1));
             },
 
             ')' =>
             {
                 chars.next();
-                if parenthesis_count == 0
-                {
-                    tokens.push(Token::Invalid);
-                }
-
-                else
-                {
-                    tokens.push(Token::RightParenthesis);
-                    parenthesis_count -= 1;
-                }
+                tokens.push((Token::RightParenthesis, index..index + 1));
             },
 
             ' ' | '\t' | '\n' | '\r' =>
@@ -96,184 +259,360 @@ fn tokenize(input: &str) -> Vec<Token>
 
             _ =>
             {
-                tokenize_logical_operator(&mut chars, &mut tokens);
+                tokenize_logical_operator(input, &mut chars, &mut tokens);
             }
         }
     }
 
-    if parenthesis_count != 0
-    {
-        tokens.push(Token::Invalid);
-    }
-
     tokens
 }
 
-fn tokenize_command(chars: &mut Peekable<Chars>, tokens: &mut Vec<Token>)
+fn tokenize_command(input: &str, chars: &mut Peekable<CharIndices>, tokens: &mut Vec<SpannedToken>)
 {
-    chars.next();
+    let (start, _) = chars.next().unwrap();
     let mut content = String::new();
-    while let Some(&ch) = chars.peek()
+    let mut end = input.len();
+    while let Some(&(index, ch)) = chars.peek()
     {
+        chars.next();
         if ch == '}'
         {
-            chars.next();
+            end = index + 1;
             break;
         }
 
-        else
+        content.push(ch);
+
+        // Escaped characters (like '\}') never end the command
+        if ch == '\\' && let Some((_, escaped)) = chars.next()
+        {
+            content.push(escaped);
+        }
+    }
+    tokens.push((Token::FilterCommand(content), start..end));
+}
+
+fn tokenize_macro(input: &str, chars: &mut Peekable<CharIndices>, tokens: &mut Vec<SpannedToken>)
+{
+    let (start, _) = chars.next().unwrap();
+    let mut name = String::new();
+    let mut end = input.len();
+    while let Some(&(index, ch)) = chars.peek()
+    {
+        if is_macro_name_char(ch)
         {
-            content.push(ch);
+            name.push(ch);
             chars.next();
         }
+        else {end = index; break;}
+    }
+
+    if name.is_empty()
+    {
+        tokens.push((Token::Invalid, start..end));
+    }
+    else
+    {
+        tokens.push((Token::Macro(name), start..end));
+    }
+}
+
+/// Tokenizes the '&&' and '||' aliases of 'and' and 'or'.
+fn tokenize_symbol_operator(chars: &mut Peekable<CharIndices>, tokens: &mut Vec<SpannedToken>)
+{
+    let (start, ch) = chars.next().unwrap();
+    match chars.peek()
+    {
+        Some(&(_, next)) if next == ch =>
+            {
+                chars.next();
+                let operator = if ch == '&' {"and"} else {"or"};
+                tokens.push((Token::Operator(String::from(operator)), start..start + 2));
+            }
+        _ => {tokens.push((Token::Invalid, start..start + 1));}
     }
-    tokens.push(Token::FilterCommand(content));
 }
 
-fn tokenize_logical_operator(chars: &mut Peekable<Chars>, tokens: &mut Vec<Token>)
+fn tokenize_logical_operator(input: &str, chars: &mut Peekable<CharIndices>,
+                             tokens: &mut Vec<SpannedToken>)
 {
-    if let Some(&ch) = chars.peek()
+    if let Some(&(start, ch)) = chars.peek()
     {
         if ch.is_alphanumeric()
         {
             let mut name = String::new();
-            while let Some(&c) = chars.peek()
+            let mut end = input.len();
+            while let Some(&(index, c)) = chars.peek()
             {
                 if c.is_alphanumeric()
                 {
                     name.push(c);
                     chars.next();
                 }
-                else {break;}
+                else {end = index; break;}
             }
 
-            tokens.push(Token::Operator(name));
+            let token = match name.as_str()
+            {
+                "true" => Token::Literal(true),
+                "false" => Token::Literal(false),
+                _ => Token::Operator(name)
+            };
+            tokens.push((token, start..end));
         }
 
         else
         {
-            tokens.push(Token::Invalid);
+            tokens.push((Token::Invalid, start..start + ch.len_utf8()));
             chars.next();
         }
     }
 }
 
+/// Splits the given input into the pieces the tokenizer recognizes and assigns each of them a
+/// [`HighlightKind`]. Whitespace is not part of any span. The spans are sorted by position.
+pub fn highlight(input: &str) -> Vec<HighlightSpan>
+{
+    let mut spans: Vec<HighlightSpan> = Vec::new();
+    let mut open_parentheses: Vec<usize> = Vec::new();
+
+    for (token, range) in tokenize_spanned(input)
+    {
+        match token
+        {
+            Token::FilterCommand(_) => {highlight_command(input, range, &mut spans);}
+            Token::Macro(_) => {spans.push(HighlightSpan {range, kind: HighlightKind::Macro});}
+            Token::Literal(_) => {spans.push(HighlightSpan {range, kind: HighlightKind::Literal});}
+
+            Token::Operator(operator) =>
+                {
+                    let kind = if logical_precedence(&operator) > 0 {HighlightKind::Operator}
+                        else {HighlightKind::Invalid};
+                    spans.push(HighlightSpan {range, kind});
+                }
+
+            Token::LeftParenthesis =>
+                {
+                    // Only marked as matched once its partner is found
+                    open_parentheses.push(spans.len());
+                    spans.push(HighlightSpan {range, kind: HighlightKind::MismatchedParenthesis});
+                }
+
+            Token::RightParenthesis =>
+                {
+                    let kind = match open_parentheses.pop()
+                    {
+                        Some(left) =>
+                            {
+                                spans[left].kind = HighlightKind::Parenthesis;
+                                HighlightKind::Parenthesis
+                            }
+                        None => HighlightKind::MismatchedParenthesis
+                    };
+                    spans.push(HighlightSpan {range, kind});
+                }
+
+            Token::Invalid => {spans.push(HighlightSpan {range, kind: HighlightKind::Invalid});}
+        }
+    }
+
+    spans
+}
+
+/// Splits the filter command in the given range into the quoted strings and the rest.
+fn highlight_command(input: &str, range: Range<usize>, spans: &mut Vec<HighlightSpan>)
+{
+    let mut piece_start = range.start;
+    let mut in_quotes = false;
+
+    for index in command_parser::unescaped_indices(&input[range.clone()], '"')
+    {
+        let index = range.start + index;
+        if in_quotes
+        {
+            spans.push(HighlightSpan {range: piece_start..index + 1, kind: HighlightKind::QuotedString});
+            piece_start = index + 1;
+        }
+        else
+        {
+            if piece_start < index
+            {
+                spans.push(HighlightSpan {range: piece_start..index, kind: HighlightKind::Command});
+            }
+            piece_start = index;
+        }
+        in_quotes = !in_quotes;
+    }
+
+    if piece_start < range.end
+    {
+        let kind = if in_quotes {HighlightKind::QuotedString} else {HighlightKind::Command};
+        spans.push(HighlightSpan {range: piece_start..range.end, kind});
+    }
+}
+
 /// Precedence declaration for all supported logical operators
 /// (Greater precedence -> greater number)
-fn logical_precedence(operator: &str) -> i32
+pub(crate) fn logical_precedence(operator: &str) -> i32
 {
     match operator
     {
-        "not" => 3,
+        "not" => NOT_PRECEDENCE,
         "and" | "nand" => 2,
         "or" | "nor" | "xor" | "xnor" => 1,
         _ => 0,
     }
 }
 
-/// Converts a vector of tokens from infix notation to postfix notation using the
-/// shunting yard algorithm.
-fn infix_to_postfix(tokens: Vec<Token>) -> Vec<Token>
+/// Precedence of 'not'. Binds stronger than any binary operator.
+const NOT_PRECEDENCE: i32 = 3;
+
+/// Pratt parser turning a sequence of [`Token`]s into a [`FilterExpression`].
+struct Parser<'a>
 {
-    let mut output: Vec<Token> = Vec::new();
-    let mut ops_stack: Vec<Token> = Vec::new();
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+    filter_type: &'a FilterType,
+    macros: &'a FilterMacros,
+
+    /// Names of the macros that are currently being resolved.
+    resolving: &'a mut Vec<String>
+}
 
-    for token in tokens
+impl Parser<'_>
+{
+    /// Parses all tokens into a single expression.
+    fn parse(&mut self) -> Result<FilterExpression, ExpressionError>
     {
-        match token
+        let expression = self.parse_expression(0)?;
+        match self.tokens.next()
         {
-            Token::FilterCommand(_) => output.push(token),
+            None => Ok(expression),
+            Some(token) => Err(unexpected_after_operand(token))
+        }
+    }
 
-            Token::LeftParenthesis => ops_stack.push(token),
+    /// Parses an expression whose binary operators all have at least the given precedence.
+    fn parse_expression(&mut self, min_precedence: i32) -> Result<FilterExpression, ExpressionError>
+    {
+        let mut left = self.parse_operand()?;
 
-            Token::RightParenthesis =>
+        while let Some(Token::Operator(operator)) = self.tokens.peek()
+        {
+            let precedence = logical_precedence(operator);
+            if precedence == 0 || precedence == NOT_PRECEDENCE || precedence < min_precedence
             {
-                while let Some(top) = ops_stack.pop()
+                break;
+            }
+
+            let Some(Token::Operator(operator)) = self.tokens.next() else {break};
+
+            // Operators of equal precedence are evaluated from left to right
+            let right = self.parse_expression(precedence + 1)?;
+            left = combine(&operator, left, right);
+        }
+
+        Ok(left)
+    }
+
+    /// Parses a single command, macro, literal, negated operand or expression in parentheses.
+    fn parse_operand(&mut self) -> Result<FilterExpression, ExpressionError>
+    {
+        let Some(token) = self.tokens.next() else {return Err(ExpressionError::MissingOperand)};
+
+        match token
+        {
+            Token::FilterCommand(command) =>
                 {
-                    if let Token::LeftParenthesis = top
-                    {
-                        break;
-                    }
-                    else {output.push(top);}
+                    let filter_command = command_parser::parse(self.filter_type, command.clone())
+                        .ok_or(ExpressionError::InvalidCommand(command))?;
+                    Ok(FilterExpression::SingleCommand(filter_command))
                 }
-            },
 
-            Token::Operator(op) =>
-            {
-                while let Some(Token::Operator(top_op)) = ops_stack.last()
+            Token::Macro(name) =>
                 {
-                    if logical_precedence(&op) <= logical_precedence(&top_op)
+                    let expression = resolve_macro(self.filter_type, name.clone(), self.macros, self.resolving)?;
+                    Ok(FilterExpression::Macro(name, Box::new(expression)))
+                }
+
+            Token::Literal(value) => Ok(FilterExpression::Constant(value)),
+
+            Token::Operator(operator) if operator == "not" =>
+                {
+                    let operand = self.parse_expression(NOT_PRECEDENCE)?;
+                    Ok(FilterExpression::Not(Box::new(operand)))
+                }
+
+            Token::Operator(operator) if logical_precedence(&operator) > 0 =>
+                {
+                    Err(ExpressionError::MissingOperand)
+                }
+
+            Token::Operator(operator) => Err(ExpressionError::UnknownOperator(operator)),
+
+            Token::LeftParenthesis =>
+                {
+                    let expression = self.parse_expression(0)?;
+                    match self.tokens.next()
                     {
-                        output.push(ops_stack.pop().unwrap());
+                        Some(Token::RightParenthesis) => Ok(expression),
+                        None => Err(ExpressionError::UnmatchedParenthesis),
+                        Some(token) => Err(unexpected_after_operand(token))
                     }
-                    else {break;}
                 }
-                ops_stack.push(Token::Operator(op));
-            },
 
-            Token::Invalid => output.push(token)
+            Token::RightParenthesis => Err(ExpressionError::MissingOperand),
+            Token::Invalid => Err(ExpressionError::InvalidSyntax)
         }
     }
+}
 
-    while let Some(t) = ops_stack.pop()
+/// The error for a token that directly follows a complete expression without being a binary
+/// operator or a closing parenthesis.
+fn unexpected_after_operand(token: Token) -> ExpressionError
+{
+    match token
     {
-        output.push(t);
-    }
+        Token::Operator(operator) if operator == "not" => ExpressionError::MisplacedNot,
+        Token::Operator(operator) => ExpressionError::UnknownOperator(operator),
+        Token::RightParenthesis => ExpressionError::UnmatchedParenthesis,
+        Token::Invalid => ExpressionError::InvalidSyntax,
 
-    output
+        Token::FilterCommand(_) | Token::Macro(_) | Token::Literal(_)
+        | Token::LeftParenthesis => ExpressionError::MissingOperator
+    }
 }
 
-fn expression_from_tokenized_postfix(tokens: Vec<Token>, filter_type: &FilterType)
-    -> Option<FilterExpression>
+/// Connects the given expressions with the given binary operator.
+fn combine(operator: &str, a: FilterExpression, b: FilterExpression) -> FilterExpression
 {
-    let mut stack: Vec<FilterExpression> = Vec::new();
-
-    for token in tokens
+    let (a, b) = (Box::new(a), Box::new(b));
+    match operator
     {
-        match token
-        {
-            Token::FilterCommand(expr) =>
-            {
-                let filter_command = command_parser::parse(filter_type, expr)?;
-                stack.push(FilterExpression::SingleCommand(filter_command));
-            },
-
-            Token::Operator(operator) =>
-            {
-                match operator.as_str()
-                {
-                    "not" =>
-                    {
-                        let a = stack.pop()?;
-                        stack.push(FilterExpression::Not(Box::new(a)));
-                    },
+        "and" => FilterExpression::And(a, b),
+        "or" => FilterExpression::Or(a, b),
+        "xor" => FilterExpression::Xor(a, b),
+        "nor" => FilterExpression::Nor(a, b),
+        "nand" => FilterExpression::Nand(a, b),
+        _ => FilterExpression::Xnor(a, b),
+    }
+}
 
-                    _ =>
-                    {
-                        let b = stack.pop()?;
-                        let a = stack.pop()?;
-                        stack.push(match operator.as_str()
-                        {
-                            "and" => FilterExpression::And(Box::new(a), Box::new(b)),
-                            "or" => FilterExpression::Or(Box::new(a), Box::new(b)),
-                            "xor" => FilterExpression::Xor(Box::new(a), Box::new(b)),
-                            "nor" => FilterExpression::Nor(Box::new(a), Box::new(b)),
-                            "nand" => FilterExpression::Nand(Box::new(a), Box::new(b)),
-                            "xnor" => FilterExpression::Xnor(Box::new(a), Box::new(b)),
-                            _ => return None,
-                        });
-                    }
-                }
-            },
+/// Parses the expression of the macro with the given name.
+fn resolve_macro(filter_type: &FilterType, name: String, macros: &FilterMacros,
+                 resolving: &mut Vec<String>) -> Result<FilterExpression, ExpressionError>
+{
+    if let Some(index) = resolving.iter().position(|other| other == &name)
+    {
+        return Err(ExpressionError::MacroCycle(resolving[index..].to_vec()));
+    }
 
-            Token::Invalid => return None,
+    let input = macros.get(&name).ok_or(ExpressionError::UnknownMacro(name.clone()))?;
 
-            // `LeftParenthesis` and `RightParenthesis` only serve to manage the operator
-            // precedence in the `infix_to_postfix` function => They should not appear by the time
-            // this function is called
-            _ => {},
-        }
-    }
+    resolving.push(name);
+    let result = parse_resolving(filter_type, input, macros, resolving);
+    let name = resolving.pop().unwrap_or_default();
 
-    stack.pop()
-}
\ No newline at end of file
+    // Errors inside the macro are reported as they are, unless they are only syntax errors,
+    // in which case the name of the broken macro is more helpful
+    result.map_err(|e| if e.is_syntax_error() {ExpressionError::InvalidMacro(name)} else {e})
+}
@@ -0,0 +1,171 @@
+//! Module for simplifying [`FilterExpression`]s into a normalized form.
+//!
+//! The simplified form evaluates the same as the original expression but removes
+//! differences in how it was written:
+//! - Double negations are removed
+//! - 'nand', 'nor' and 'xnor' are replaced by negations of 'and', 'or' and 'xor'
+//! - Negations are pushed down through 'and' and 'or' (De Morgan)
+//! - Nested 'and', 'or' and 'xor' are flattened, their operands sorted and duplicates removed
+//! - Constants and contradictions like `{a} and not {a}` are folded
+
+use crate::core::filters::filter_expression::FilterExpression;
+
+/// The commutative operators whose operands are flattened and sorted.
+#[derive(Clone, Copy, PartialEq)]
+enum Junction
+{
+    And, Or, Xor
+}
+
+/// Returns the simplified form of the given expression.
+pub fn simplify(expression: &FilterExpression) -> FilterExpression
+{
+    match expression
+    {
+        FilterExpression::SingleCommand(_) | FilterExpression::Constant(_) => expression.clone(),
+        FilterExpression::Not(inner) => negate(simplify(inner)),
+
+        // Macros are only a way of writing their expression
+        FilterExpression::Macro(_, inner) => simplify(inner),
+
+        FilterExpression::And(a, b) => combine(Junction::And, vec![simplify(a), simplify(b)]),
+        FilterExpression::Or(a, b) => combine(Junction::Or, vec![simplify(a), simplify(b)]),
+        FilterExpression::Xor(a, b) => combine(Junction::Xor, vec![simplify(a), simplify(b)]),
+
+        FilterExpression::Nand(a, b) => negate(combine(Junction::And, vec![simplify(a), simplify(b)])),
+        FilterExpression::Nor(a, b) => negate(combine(Junction::Or, vec![simplify(a), simplify(b)])),
+        FilterExpression::Xnor(a, b) => negate(combine(Junction::Xor, vec![simplify(a), simplify(b)])),
+    }
+}
+
+/// Negates an already simplified expression while keeping it simplified.
+fn negate(expression: FilterExpression) -> FilterExpression
+{
+    match expression
+    {
+        FilterExpression::Constant(value) => FilterExpression::Constant(!value),
+        FilterExpression::Not(inner) => *inner,
+
+        FilterExpression::And(_, _) =>
+            {
+                let operands = flatten(Junction::And, expression).into_iter().map(negate).collect();
+                combine(Junction::Or, operands)
+            }
+
+        FilterExpression::Or(_, _) =>
+            {
+                let operands = flatten(Junction::Or, expression).into_iter().map(negate).collect();
+                combine(Junction::And, operands)
+            }
+
+        _ => FilterExpression::Not(Box::new(expression))
+    }
+}
+
+/// Combines the given simplified operands with the given [`Junction`] into a simplified expression.
+fn combine(junction: Junction, operands: Vec<FilterExpression>) -> FilterExpression
+{
+    let mut flat: Vec<FilterExpression> = Vec::new();
+    for operand in operands
+    {
+        flat.extend(flatten(junction, operand));
+    }
+
+    // Operands that are equal as expressions also print the same, so sorting by the printed
+    // form puts duplicates next to each other
+    flat.sort_by_cached_key(|operand| operand.to_string());
+
+    match junction
+    {
+        Junction::And | Junction::Or =>
+            {
+                // 'true' is absorbing for 'or' and neutral for 'and', 'false' the other way around
+                let absorbing = junction == Junction::Or;
+                if flat.contains(&FilterExpression::Constant(absorbing))
+                    || contains_complement(&flat)
+                {
+                    return FilterExpression::Constant(absorbing);
+                }
+
+                flat.retain(|operand| operand != &FilterExpression::Constant(!absorbing));
+                flat.dedup();
+                build(junction, flat).unwrap_or(FilterExpression::Constant(!absorbing))
+            }
+
+        Junction::Xor =>
+            {
+                // Constants, pairs of equal operands and pairs of complements all collapse into
+                // whether the result has to be negated
+                let mut negated = false;
+                let mut kept: Vec<FilterExpression> = Vec::new();
+                for operand in flat
+                {
+                    if let FilterExpression::Constant(value) = operand
+                    {
+                        negated ^= value;
+                    }
+                    else if let Some(index) = kept.iter().position(|other| other == &operand)
+                    {
+                        kept.remove(index);
+                    }
+                    else if let Some(index) = kept.iter().position(|other| is_complement(other, &operand))
+                    {
+                        kept.remove(index);
+                        negated = !negated;
+                    }
+                    else
+                    {
+                        kept.push(operand);
+                    }
+                }
+
+                let result = build(junction, kept).unwrap_or(FilterExpression::Constant(false));
+                if negated {negate(result)} else {result}
+            }
+    }
+}
+
+/// Splits the given expression into its operands if it is a chain of the given [`Junction`].
+fn flatten(junction: Junction, expression: FilterExpression) -> Vec<FilterExpression>
+{
+    match (junction, expression)
+    {
+        (Junction::And, FilterExpression::And(a, b))
+        | (Junction::Or, FilterExpression::Or(a, b))
+        | (Junction::Xor, FilterExpression::Xor(a, b)) =>
+            {
+                let mut operands = flatten(junction, *a);
+                operands.extend(flatten(junction, *b));
+                operands
+            }
+
+        (_, expression) => vec![expression]
+    }
+}
+
+/// Chains the given operands with the given [`Junction`] from left to right.
+/// Returns None if there are no operands.
+fn build(junction: Junction, operands: Vec<FilterExpression>) -> Option<FilterExpression>
+{
+    operands.into_iter().reduce(|a, b|
+        {
+            let (a, b) = (Box::new(a), Box::new(b));
+            match junction
+            {
+                Junction::And => FilterExpression::And(a, b),
+                Junction::Or => FilterExpression::Or(a, b),
+                Junction::Xor => FilterExpression::Xor(a, b),
+            }
+        })
+}
+
+fn contains_complement(operands: &[FilterExpression]) -> bool
+{
+    operands.iter().any(|a| operands.iter().any(|b| is_complement(a, b)))
+}
+
+fn is_complement(a: &FilterExpression, b: &FilterExpression) -> bool
+{
+    matches!(a, FilterExpression::Not(inner) if inner.as_ref() == b)
+        || matches!(b, FilterExpression::Not(inner) if inner.as_ref() == a)
+}
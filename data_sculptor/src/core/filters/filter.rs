@@ -1,11 +1,15 @@
 use std::hash::{DefaultHasher, Hash, Hasher};
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use crate::core::data_containers::{DayDataParsed, EntryKey};
+use crate::core::filters::expression_simplifier;
+use crate::core::filters::filter_commands::FilterCommand;
 use crate::core::filters::filter_expression::{EvalData, FilterExpression};
 
 /// Struct representing a single user defined filter.
 /// The filter logic is defined by its [`FilterExpression`] attribute.
 /// Must be of a specific [`FilterType`].
+#[derive(Clone)]
 pub struct Filter
 {
     pub title: String,
@@ -20,22 +24,63 @@ pub enum FilterType
     Date, Key, Value
 }
 
+/// Defines how the key filters are combined.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyFilterMode
+{
+    /// Keys that match at least one key filter are shown.
+    #[default]
+    Any,
+
+    /// Only keys that match every key filter are shown.
+    All
+}
+
+/// Whether a [`Filter`] generated through [`Filter::from_keys`] shows or hides the keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeySelection
+{
+    Include, Exclude
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FilterID
 {
     pub numeric_id: u64
 }
 
+/// Computed from the simplified form of the expression, so that expressions that only differ
+/// in ways the simplifier normalizes (like operand order) get the same id.
 impl From<&FilterExpression> for FilterID
 {
     fn from(expression: &FilterExpression) -> Self
     {
         let mut hasher = DefaultHasher::new();
-        expression.hash(&mut hasher);
+        expression_simplifier::simplify(expression).hash(&mut hasher);
         Self{numeric_id: hasher.finish()}
     }
 }
 
+impl Filter
+{
+    /// Generates a key filter that matches all keys with any of the given titles
+    /// (or, for [`KeySelection::Exclude`], all keys with none of them).
+    /// Returns None if no keys are given.
+    pub fn from_keys(keys: &[String], selection: KeySelection) -> Option<Self>
+    {
+        let mut expression = keys.iter()
+            .map(|key| FilterExpression::SingleCommand(FilterCommand::Equals(key.clone())))
+            .reduce(|a, b| FilterExpression::Or(Box::new(a), Box::new(b)))?;
+
+        if selection == KeySelection::Exclude
+        {
+            expression = FilterExpression::Not(Box::new(expression));
+        }
+
+        Some(Self {title: expression.to_string(), expression, filter_type: FilterType::Key})
+    }
+}
+
 /// Runs the given [`DayDataParsed`] through all given
 /// filters and returns whether it is valid to show.
 /// This is the case if:
@@ -79,10 +124,10 @@ pub fn filter_day(day: &DayDataParsed, date_filters: &IndexMap<FilterID, Filter>
     return true;
 }
 
-/// Runs the given [`EntryKey`] through all given key
-/// filters and returns whether its key is valid under at least one filter condition.
+/// Runs the given [`EntryKey`] through all given key filters and returns whether its key
+/// is valid under at least one filter condition (or all of them for [`KeyFilterMode::All`]).
 pub fn filter_key(day: &DayDataParsed, key: &EntryKey,
-                  key_filters: &IndexMap<FilterID, Filter>) -> bool
+                  key_filters: &IndexMap<FilterID, Filter>, mode: KeyFilterMode) -> bool
 {
     if key_filters.is_empty()
     {
@@ -90,12 +135,10 @@ pub fn filter_key(day: &DayDataParsed, key: &EntryKey,
     }
 
     let data = EvalData{day, key, filter_type: &FilterType::Key};
-    for (_, filter) in key_filters
+    let mut results = key_filters.values().map(|filter| filter.expression.evaluate(&data));
+    match mode
     {
-        if filter.expression.evaluate(&data)
-        {
-            return true;
-        }
+        KeyFilterMode::Any => results.any(|valid| valid),
+        KeyFilterMode::All => results.all(|valid| valid)
     }
-    return false;
-}
\ No newline at end of file
+}
@@ -1,14 +1,22 @@
+use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 use chrono::NaiveDate;
+use crate::core::data_containers::{DATE_FORMAT, EntryKey};
 use crate::core::filters::command_parser;
+use crate::core::filters::filter::FilterType;
 use crate::core::filters::filter_expression::EvalData;
+use crate::core::filters::filter_matches::FilterMatches;
 
 /// Enum representing all supported ways to filter data in data_sculptor.
+#[derive(Clone, Debug)]
 pub enum FilterCommand
 {
     /// Checks if the value contains the given String
     Contains(String),
 
+    /// Checks if the value is exactly the given String
+    Equals(String),
+
     /// Checks if the value corresponding to the given key contains the given String
     ///
     /// # Args (index order)
@@ -71,6 +79,12 @@ impl Hash for FilterCommand
                     s.hash(state)
                 }
 
+            FilterCommand::Equals(s) =>
+                {
+                    command_parser::Keywords::Equals.hash(state);
+                    s.hash(state)
+                }
+
             FilterCommand::KeyValueContains(k, s) =>
                 {
                     command_parser::Keywords::KeyValueContains.hash(state);
@@ -102,8 +116,98 @@ impl Hash for FilterCommand
     }
 }
 
+/// Compares numbers by their bits to stay consistent with the [`Hash`] implementation.
+impl PartialEq for FilterCommand
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        match (self, other)
+        {
+            (FilterCommand::Contains(s1), FilterCommand::Contains(s2)) => s1 == s2,
+
+            (FilterCommand::Equals(s1), FilterCommand::Equals(s2)) => s1 == s2,
+
+            (FilterCommand::KeyValueContains(k1, s1), FilterCommand::KeyValueContains(k2, s2)) =>
+                k1 == k2 && s1 == s2,
+
+            (FilterCommand::NumOp(o1, f1), FilterCommand::NumOp(o2, f2)) =>
+                o1 == o2 && f1.to_bits() == f2.to_bits(),
+
+            (FilterCommand::KeyValueNumOp(k1, o1, f1), FilterCommand::KeyValueNumOp(k2, o2, f2)) =>
+                k1 == k2 && o1 == o2 && f1.to_bits() == f2.to_bits(),
+
+            (FilterCommand::Date(o1, d1), FilterCommand::Date(o2, d2)) => o1 == o2 && d1 == d2,
+
+            _ => false
+        }
+    }
+}
+
+impl Eq for FilterCommand {}
+
+/// Prints the command the way [`command_parser::parse`] reads it, without the surrounding {}.
+/// Quotation marks, backslashes and closing braces in the arguments are escaped.
+impl Display for FilterCommand
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        use command_parser::{escape, Keywords};
+
+        match self
+        {
+            FilterCommand::Contains(s) =>
+                write!(f, "{} \"{}\"", Keywords::Contains.cmd_str(), escape(s)),
+
+            FilterCommand::Equals(s) =>
+                write!(f, "{} \"{}\"", Keywords::Equals.cmd_str(), escape(s)),
+
+            FilterCommand::KeyValueContains(k, s) =>
+                write!(f, "{} \"{}\" \"{}\"", Keywords::KeyValueContains.cmd_str(), escape(k), escape(s)),
+
+            FilterCommand::NumOp(o, num) =>
+                write!(f, "{} \"{}\" \"{}\"", Keywords::NumOp.cmd_str(), escape(o), num),
+
+            FilterCommand::KeyValueNumOp(k, o, num) =>
+                write!(f, "{} \"{}\" \"{}\" \"{}\"", Keywords::KeyValueNumOp.cmd_str(), escape(k), escape(o), num),
+
+            FilterCommand::Date(o, date) =>
+                write!(f, "{} {} \"{}\"", Keywords::Date.cmd_str(), o, date.format(DATE_FORMAT))
+        }
+    }
+}
+
 impl FilterCommand
 {
+    /// Adds the text the command searches for to the given [`FilterMatches`] wherever it is found
+    /// in the given [`EvalData`]. Only 'contains', 'equals' and 'kv-contains' search for text.
+    pub fn find_matches(&self, data: &EvalData, matches: &mut FilterMatches)
+    {
+        match (self, data.filter_type)
+        {
+            (FilterCommand::Contains(keyword), FilterType::Date) => {matches.add_date(data.day, keyword);}
+            (FilterCommand::Contains(keyword), FilterType::Key) => {matches.add_key(data.key, keyword);}
+            (FilterCommand::Equals(keyword), FilterType::Date) if &data.day.date.date_string == keyword =>
+                {
+                    matches.add_date(data.day, keyword);
+                }
+            (FilterCommand::Equals(keyword), FilterType::Key) if &data.key.title == keyword =>
+                {
+                    matches.add_key(data.key, keyword);
+                }
+
+            (FilterCommand::KeyValueContains(key, keyword), FilterType::Value) =>
+                {
+                    let key = EntryKey {title: key.clone()};
+                    if let Some(value) = data.day.entries.get(&key)
+                    {
+                        matches.add_value(&key, &value.string_value, keyword);
+                    }
+                }
+
+            _ => {}
+        }
+    }
+
     /// Applies the [`FilterCommand`] to the given [`EvalData`] under date filter rules.
     ///
     /// # Returns
@@ -118,6 +222,11 @@ impl FilterCommand
                     data.day.date.date_string.contains(keyword)
                 },
 
+            FilterCommand::Equals(keyword) =>
+                {
+                    &data.day.date.date_string == keyword
+                },
+
             FilterCommand::NumOp(op, num) =>
                 {
                     num_op(&data.day.date.date_string, op, num)
@@ -146,6 +255,11 @@ impl FilterCommand
                     data.key.title.contains(keyword)
                 },
 
+            FilterCommand::Equals(keyword) =>
+                {
+                    &data.key.title == keyword
+                },
+
             FilterCommand::NumOp(op, num) =>
                 {
                     num_op(&data.key.title, op, num)
@@ -0,0 +1,100 @@
+//! Module for suggesting completions of partially typed filter expressions.
+
+use crate::core::filters::command_parser::{escape, unescaped_indices, Keywords};
+use crate::core::filters::filter::FilterType;
+
+/// Maximum number of completions returned by [`complete`].
+pub const MAX_COMPLETIONS: usize = 10;
+
+/// A suggestion to complete the input of a filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion
+{
+    /// Text shown for the suggestion.
+    pub label: String,
+
+    /// The whole input after accepting the suggestion.
+    pub input: String
+}
+
+/// Suggests completions for the filter command at the end of the given input.
+///
+/// While the keyword of a command is typed, all matching keywords valid for the given
+/// [`FilterType`] are suggested. While the first argument of a kv-* command is typed,
+/// all matching keys of the given key titles are suggested.
+pub fn complete(filter_type: &FilterType, input: &str, keys: &[String]) -> Vec<Completion>
+{
+    let Some(content_start) = open_command_start(input) else {return Vec::new()};
+    let content = &input[content_start..];
+
+    let Some(keyword_end) = content.find(' ') else
+    {
+        return complete_keyword(filter_type, &input[..content_start], content);
+    };
+
+    let keyword = Keywords::ALL.into_iter()
+        .find(|keyword| keyword.cmd_str() == &content[..keyword_end]);
+
+    match keyword
+    {
+        Some(keyword) if keyword.takes_key() && keyword.is_valid_for(filter_type) =>
+            {
+                complete_key(input, content_start + keyword_end, keys)
+            }
+        _ => {Vec::new()}
+    }
+}
+
+/// Returns the start of the content of the filter command that is still open at the end of
+/// the input. Inputs without any braces are treated as a single command.
+fn open_command_start(input: &str) -> Option<usize>
+{
+    if !input.contains('{') && unescaped_indices(input, '}').is_empty()
+    {
+        return Some(input.len() - input.trim_start().len());
+    }
+
+    let open = input.rfind('{')?;
+    if !unescaped_indices(&input[open..], '}').is_empty()
+    {
+        return None;
+    }
+    Some(open + 1)
+}
+
+fn complete_keyword(filter_type: &FilterType, before: &str, prefix: &str) -> Vec<Completion>
+{
+    Keywords::ALL.iter()
+        .filter(|keyword| keyword.is_valid_for(filter_type))
+        .filter(|keyword| keyword.cmd_str().starts_with(prefix))
+        .map(|keyword| Completion
+        {
+            label: String::from(keyword.cmd_str()),
+            input: format!("{}{} \"", before, keyword.cmd_str())
+        })
+        .collect()
+}
+
+/// Completes the key argument of a kv-* command whose keyword ends at the given position.
+fn complete_key(input: &str, keyword_end: usize, keys: &[String]) -> Vec<Completion>
+{
+    let arguments = &input[keyword_end..];
+
+    // Only while the first argument is being typed, i.e. after exactly one quotation mark
+    let [quote] = unescaped_indices(arguments, '"')[..] else {return Vec::new()};
+
+    let quote = keyword_end + quote;
+    let prefix = input[quote + 1..].to_lowercase();
+
+    // Keys are compared and inserted the way they have to be typed inside the argument
+    keys.iter()
+        .map(|key| (key, escape(key)))
+        .filter(|(_, escaped)| escaped.to_lowercase().starts_with(&prefix))
+        .take(MAX_COMPLETIONS)
+        .map(|(key, escaped)| Completion
+        {
+            label: key.clone(),
+            input: format!("{}{}\" \"", &input[..quote + 1], escaped)
+        })
+        .collect()
+}
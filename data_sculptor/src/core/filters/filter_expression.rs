@@ -1,13 +1,23 @@
+use std::fmt::{Display, Formatter};
 use crate::core::data_containers::{DayDataParsed, EntryKey};
+use crate::core::filters::expression_parser::logical_precedence;
 use crate::core::filters::filter::FilterType;
 use crate::core::filters::filter_commands::FilterCommand;
+use crate::core::filters::filter_matches::FilterMatches;
 
 /// A logical expression made up of [`FilterCommand`]s connected
 /// to each other by logical operators like 'and', 'or', etc.
-#[derive(Hash)]
+#[derive(Hash, Clone, PartialEq, Eq, Debug)]
 pub enum FilterExpression
 {
     SingleCommand(FilterCommand),
+
+    /// A fixed result. Written as the `true` and `false` literals or produced by simplifying expressions.
+    Constant(bool),
+
+    /// A reference to a named filter macro and the expression it resolved to.
+    Macro(String, Box<FilterExpression>),
+
     Not(Box<FilterExpression>),
     And(Box<FilterExpression>, Box<FilterExpression>),
     Or(Box<FilterExpression>, Box<FilterExpression>),
@@ -53,6 +63,8 @@ impl FilterExpression
                 FilterType::Key => cmd.apply_key_filter(data),
                 FilterType::Value => cmd.apply_value_filter(data),
             },
+            FilterExpression::Constant(value) => *value,
+            FilterExpression::Macro(_, inner) => inner.evaluate(data),
             FilterExpression::Not(inner) => !inner.evaluate(data),
             FilterExpression::And(a, b) => a.evaluate(data) && b.evaluate(data),
             FilterExpression::Or(a, b) => a.evaluate(data) || b.evaluate(data),
@@ -62,4 +74,112 @@ impl FilterExpression
             FilterExpression::Xnor(a, b) => !(a.evaluate(data) ^ b.evaluate(data)),
         }
     }
-}
\ No newline at end of file
+
+    /// Same as [`FilterExpression::evaluate`], but also adds the text matched by the commands
+    /// that made the expression true to the given [`FilterMatches`].
+    ///
+    /// Only commands connected through 'and' and 'or' can be the reason for a match.
+    /// Everything below any other operator is evaluated without looking for matches.
+    pub fn evaluate_with_matches(&self, data: &EvalData, matches: &mut FilterMatches) -> bool
+    {
+        match self
+        {
+            FilterExpression::SingleCommand(cmd) =>
+                {
+                    let valid = self.evaluate(data);
+                    if valid
+                    {
+                        cmd.find_matches(data, matches);
+                    }
+                    valid
+                }
+            FilterExpression::Macro(_, inner) => inner.evaluate_with_matches(data, matches),
+            FilterExpression::And(a, b) =>
+                {
+                    // Matches of one side do not count if the other side fails
+                    let mut found = FilterMatches::default();
+                    let valid = a.evaluate_with_matches(data, &mut found)
+                        && b.evaluate_with_matches(data, &mut found);
+                    if valid
+                    {
+                        matches.extend(found);
+                    }
+                    valid
+                }
+            FilterExpression::Or(a, b) =>
+                {
+                    // Both sides are evaluated to find the matches of each
+                    let a_valid = a.evaluate_with_matches(data, matches);
+                    let b_valid = b.evaluate_with_matches(data, matches);
+                    a_valid || b_valid
+                }
+            _ => self.evaluate(data)
+        }
+    }
+
+    /// The keyword of the logical operator at the root of the expression.
+    /// None if the expression is a single command.
+    pub fn operator(&self) -> Option<&'static str>
+    {
+        match self
+        {
+            FilterExpression::SingleCommand(_) | FilterExpression::Constant(_)
+            | FilterExpression::Macro(_, _) => None,
+            FilterExpression::Not(_) => Some("not"),
+            FilterExpression::And(_, _) => Some("and"),
+            FilterExpression::Or(_, _) => Some("or"),
+            FilterExpression::Xor(_, _) => Some("xor"),
+            FilterExpression::Nor(_, _) => Some("nor"),
+            FilterExpression::Nand(_, _) => Some("nand"),
+            FilterExpression::Xnor(_, _) => Some("xnor"),
+        }
+    }
+
+    /// How strongly the root of the expression binds its operands.
+    /// Single commands, constants and macros bind stronger than any operator.
+    fn precedence(&self) -> i32
+    {
+        match self.operator()
+        {
+            Some(operator) => logical_precedence(operator),
+            None => i32::MAX
+        }
+    }
+}
+
+/// Prints the expression in its canonical form: Every command in {}, operators in lowercase
+/// separated by single spaces and only the parentheses needed to parse it back the same way.
+impl Display for FilterExpression
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            FilterExpression::SingleCommand(cmd) => write!(f, "{{{}}}", cmd),
+            FilterExpression::Constant(value) => write!(f, "{}", value),
+            FilterExpression::Macro(name, _) => write!(f, "@{}", name),
+
+            // 'not' binds stronger than any binary operator
+            FilterExpression::Not(inner) => write_operand(f, "not ", inner, inner.precedence() < self.precedence()),
+
+            FilterExpression::And(a, b) | FilterExpression::Or(a, b)
+            | FilterExpression::Xor(a, b) | FilterExpression::Nor(a, b)
+            | FilterExpression::Nand(a, b) | FilterExpression::Xnor(a, b) =>
+                {
+                    // Operators of equal precedence are evaluated left to right,
+                    // so only the right operand needs parentheses in that case
+                    let precedence = self.precedence();
+                    write_operand(f, "", a, a.precedence() < precedence)?;
+                    write_operand(f, &format!(" {} ", self.operator().unwrap_or_default()), b,
+                                  b.precedence() <= precedence)
+                }
+        }
+    }
+}
+
+/// Writes the given prefix followed by the operand, in parentheses if wanted.
+fn write_operand(f: &mut Formatter<'_>, prefix: &str, operand: &FilterExpression, parenthesize: bool)
+    -> std::fmt::Result
+{
+    if parenthesize {write!(f, "{}({})", prefix, operand)} else {write!(f, "{}{}", prefix, operand)}
+}
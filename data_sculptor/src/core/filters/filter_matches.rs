@@ -0,0 +1,198 @@
+//! Module for finding the parts of the data that made it pass the filters.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use indexmap::IndexMap;
+use crate::core::data_containers::{DayDataParsed, EntryKey};
+use crate::core::filters::filter::{Filter, FilterID, FilterType, KeyFilterMode};
+use crate::core::filters::filter_expression::EvalData;
+
+/// Byte ranges of the text of a single day that were matched by the filters it passed.
+/// Only filter commands that search for text (like 'contains') produce matches.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FilterMatches
+{
+    /// Ranges in the date string of the day.
+    pub date: Vec<Range<usize>>,
+
+    /// Ranges in the titles of the keys.
+    pub keys: HashMap<EntryKey, Vec<Range<usize>>>,
+
+    /// Ranges in the values of the keys.
+    pub values: HashMap<EntryKey, Vec<Range<usize>>>
+}
+
+impl FilterMatches
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.date.is_empty() && self.keys.is_empty() && self.values.is_empty()
+    }
+
+    /// Sorted and merged ranges in the date string.
+    pub fn date_ranges(&self) -> Vec<Range<usize>>
+    {
+        merge(&self.date)
+    }
+
+    /// Sorted and merged ranges in the title of the given key.
+    pub fn key_ranges(&self, key: &EntryKey) -> Vec<Range<usize>>
+    {
+        self.keys.get(key).map(|ranges| merge(ranges)).unwrap_or_default()
+    }
+
+    /// Sorted and merged ranges in the value of the given key.
+    pub fn value_ranges(&self, key: &EntryKey) -> Vec<Range<usize>>
+    {
+        self.values.get(key).map(|ranges| merge(ranges)).unwrap_or_default()
+    }
+
+    /// Adds all occurrences of the keyword in the date string of the given day.
+    pub(crate) fn add_date(&mut self, day: &DayDataParsed, keyword: &str)
+    {
+        self.date.extend(occurrences(&day.date.date_string, keyword));
+    }
+
+    /// Adds all occurrences of the keyword in the title of the given key.
+    pub(crate) fn add_key(&mut self, key: &EntryKey, keyword: &str)
+    {
+        let ranges = occurrences(&key.title, keyword);
+        if !ranges.is_empty()
+        {
+            self.keys.entry(key.clone()).or_default().extend(ranges);
+        }
+    }
+
+    /// Adds all occurrences of the keyword in the given value of the given key.
+    pub(crate) fn add_value(&mut self, key: &EntryKey, value: &str, keyword: &str)
+    {
+        let ranges = occurrences(value, keyword);
+        if !ranges.is_empty()
+        {
+            self.values.entry(key.clone()).or_default().extend(ranges);
+        }
+    }
+
+    pub(crate) fn extend(&mut self, other: FilterMatches)
+    {
+        self.date.extend(other.date);
+        for (key, ranges) in other.keys
+        {
+            self.keys.entry(key).or_default().extend(ranges);
+        }
+        for (key, ranges) in other.values
+        {
+            self.values.entry(key).or_default().extend(ranges);
+        }
+    }
+}
+
+/// Finds the matches of all given filters in the given day.
+/// The day and the keys the matches are reported for are expected to pass the filters.
+/// See [`filter::filter_day`](crate::core::filters::filter::filter_day)
+/// and [`filter::filter_key`](crate::core::filters::filter::filter_key).
+pub fn find_matches(day: &DayDataParsed, date_filters: &IndexMap<FilterID, Filter>,
+                    key_filters: &IndexMap<FilterID, Filter>, key_filter_mode: KeyFilterMode,
+                    value_filters: &IndexMap<FilterID, Filter>) -> FilterMatches
+{
+    let mut matches = FilterMatches::default();
+
+    // DATE
+    if let Some(key) = day.entries.keys().next()
+    {
+        let data = EvalData{day, key, filter_type: &FilterType::Date};
+        add_filter_matches(&mut matches, date_filters, &data, true);
+    }
+
+    for key in day.entries.keys()
+    {
+        // KEY
+        let data = EvalData{day, key, filter_type: &FilterType::Key};
+        add_filter_matches(&mut matches, key_filters, &data, key_filter_mode == KeyFilterMode::All);
+
+        // VALUE
+        let data = EvalData{day, key, filter_type: &FilterType::Value};
+        add_filter_matches(&mut matches, value_filters, &data, true);
+    }
+
+    matches
+}
+
+/// Adds the matches of the given filters. If all filters are required to pass, nothing is added
+/// unless they do.
+fn add_filter_matches(matches: &mut FilterMatches, filters: &IndexMap<FilterID, Filter>,
+                      data: &EvalData, require_all: bool)
+{
+    let mut found = FilterMatches::default();
+    for filter in filters.values()
+    {
+        let mut filter_matches = FilterMatches::default();
+        if filter.expression.evaluate_with_matches(data, &mut filter_matches)
+        {
+            found.extend(filter_matches);
+        }
+        else if require_all
+        {
+            return;
+        }
+    }
+    matches.extend(found);
+}
+
+/// Splits the given text into pieces that are alternately outside and inside the given
+/// sorted, non-overlapping ranges. Returns each piece with whether it is inside a range.
+/// Ranges that do not lie on char boundaries of the text are ignored.
+pub fn split_by_ranges<'a>(text: &'a str, ranges: &[Range<usize>]) -> Vec<(&'a str, bool)>
+{
+    let mut pieces = Vec::new();
+    let mut position = 0;
+    for range in ranges
+    {
+        if range.start < position || text.get(range.clone()).is_none()
+        {
+            continue;
+        }
+
+        if position < range.start
+        {
+            pieces.push((&text[position..range.start], false));
+        }
+        pieces.push((&text[range.clone()], true));
+        position = range.end;
+    }
+
+    if position < text.len()
+    {
+        pieces.push((&text[position..], false));
+    }
+    pieces
+}
+
+/// All ranges of the keyword in the text. Empty keywords are not searched for.
+fn occurrences(text: &str, keyword: &str) -> Vec<Range<usize>>
+{
+    if keyword.is_empty()
+    {
+        return Vec::new();
+    }
+
+    text.match_indices(keyword).map(|(start, found)| start..start + found.len()).collect()
+}
+
+/// Sorts the ranges and merges the ones that overlap or touch.
+fn merge(ranges: &[Range<usize>]) -> Vec<Range<usize>>
+{
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in sorted
+    {
+        match merged.last_mut()
+        {
+            Some(last) if range.start <= last.end => {last.end = last.end.max(range.end);}
+            _ => {merged.push(range);}
+        }
+    }
+    merged
+}
@@ -0,0 +1,107 @@
+//! Module for the [`FilteredIndex`] that caches which parts of the data pass the filters.
+
+use std::collections::HashSet;
+use indexmap::{IndexMap, IndexSet};
+use crate::core::data_containers::{DayDataParsed, EntryKey};
+use crate::core::filters::filter;
+use crate::core::filters::filter::{Filter, FilterID, FilterType, KeyFilterMode};
+
+/// A single day that passed the date and value filters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilteredDay
+{
+    /// Index of the day in the data it was built from.
+    pub day_index: usize,
+
+    /// Indices of all entries of the day whose keys pass the key filters.
+    pub entry_indices: Vec<usize>
+}
+
+/// Caches the result of running the data through all filters so that it does not have to be
+/// recomputed every time the data is displayed.
+/// It has to be rebuilt whenever the data or the filters change.
+#[derive(Debug, Default)]
+pub struct FilteredIndex
+{
+    pub days: Vec<FilteredDay>,
+
+    /// All keys of the filtered days that pass the key filters in the order they first appear in.
+    pub keys: Vec<EntryKey>
+}
+
+impl FilteredIndex
+{
+    /// Runs the given data through the given filters and indexes everything that passes.
+    /// See [`filter::filter_day`] and [`filter::filter_key`].
+    pub fn build(data: &[DayDataParsed], date_filters: &IndexMap<FilterID, Filter>,
+                 key_filters: &IndexMap<FilterID, Filter>, key_filter_mode: KeyFilterMode,
+                 value_filters: &IndexMap<FilterID, Filter>) -> Self
+    {
+        let mut days: Vec<FilteredDay> = Vec::new();
+        let mut keys: IndexSet<EntryKey> = IndexSet::new();
+
+        for (day_index, day) in data.iter().enumerate()
+        {
+            if !filter::filter_day(day, date_filters, value_filters)
+            {
+                continue;
+            }
+
+            let mut entry_indices: Vec<usize> = Vec::new();
+            for (entry_index, key) in day.entries.keys().enumerate()
+            {
+                if filter::filter_key(day, key, key_filters, key_filter_mode)
+                {
+                    entry_indices.push(entry_index);
+                    if !keys.contains(key)
+                    {
+                        keys.insert(key.clone());
+                    }
+                }
+            }
+
+            days.push(FilteredDay {day_index, entry_indices});
+        }
+
+        Self {days, keys: keys.into_iter().collect()}
+    }
+
+    /// Counts the indexed days of the given data that also pass the given date or value filter,
+    /// i.e. the days that would be left if it was added. Only the filter itself is evaluated.
+    pub fn count_days_passing(&self, data: &[DayDataParsed], filter: &Filter) -> usize
+    {
+        let filters = IndexMap::from([(FilterID::from(&filter.expression), filter.clone())]);
+        let none = IndexMap::new();
+        let (date_filters, value_filters) = match filter.filter_type
+        {
+            FilterType::Date => {(&filters, &none)}
+            _ => {(&none, &filters)}
+        };
+
+        self.resolve(data).into_iter()
+            .filter(|day| filter::filter_day(day, date_filters, value_filters))
+            .count()
+    }
+
+    /// Counts the distinct keys of the indexed days of the given data that pass the given
+    /// key filters. The days are not run through the date and value filters again.
+    pub fn count_keys_passing(&self, data: &[DayDataParsed], key_filters: &IndexMap<FilterID, Filter>,
+                              key_filter_mode: KeyFilterMode) -> usize
+    {
+        let mut keys: HashSet<&EntryKey> = HashSet::new();
+        for day in self.resolve(data)
+        {
+            keys.extend(day.entries.keys().filter(|key| filter::filter_key(day, key, key_filters, key_filter_mode)));
+        }
+        keys.len()
+    }
+
+    /// Returns references to all indexed days of the given data.
+    /// The data has to be the same that the index was built from.
+    pub fn resolve<'a>(&self, data: &'a [DayDataParsed]) -> Vec<&'a DayDataParsed>
+    {
+        self.days.iter()
+            .filter_map(|filtered_day| data.get(filtered_day.day_index))
+            .collect()
+    }
+}
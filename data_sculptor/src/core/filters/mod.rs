@@ -1,5 +1,9 @@
 pub mod filter;
 pub mod expression_parser;
+pub mod expression_simplifier;
+pub mod filtered_index;
+pub mod filter_completion;
+pub mod filter_matches;
 mod command_parser;
 mod filter_commands;
 mod filter_expression;
@@ -2,5 +2,10 @@
 //! in specific data structures.
 
 pub mod data_containers;
+pub mod data_edit;
 pub mod data_manager;
+pub mod data_sorting;
 pub mod filters;
+pub mod palette;
+pub mod search_index;
+pub mod settings;
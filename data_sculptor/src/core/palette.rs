@@ -0,0 +1,171 @@
+//! Module defining the [`Palette`] that all colors of the gui are derived from.
+
+use iced::Color;
+use serde::{Deserialize, Serialize};
+
+/// The colors of the gui. Widget styles get them through the theme built from the palette,
+/// the colors of displayed data are read from the palette directly.
+///
+/// Stored in palette files as hex strings (`#rrggbb` or `#rrggbbaa`).
+/// Colors missing from a file are taken from the dark palette.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Palette
+{
+    pub name: String,
+
+    // GENERAL
+    #[serde(with = "hex_color")]
+    pub background: Color,
+
+    /// Background of bars and panels.
+    #[serde(with = "hex_color")]
+    pub surface: Color,
+
+    #[serde(with = "hex_color")]
+    pub text: Color,
+
+    /// Text of less important notes.
+    #[serde(with = "hex_color")]
+    pub muted_text: Color,
+
+    #[serde(with = "hex_color")]
+    pub error: Color,
+
+    // BUTTONS
+    #[serde(with = "hex_color")]
+    pub primary: Color,
+
+    /// Background of filter and toggle buttons.
+    #[serde(with = "hex_color")]
+    pub secondary: Color,
+
+    /// Background of buttons that delete something.
+    #[serde(with = "hex_color")]
+    pub danger: Color,
+
+    #[serde(with = "hex_color")]
+    pub button_text: Color,
+
+    // DATA
+    #[serde(with = "hex_color")]
+    pub date: Color,
+
+    #[serde(with = "hex_color")]
+    pub value: Color,
+
+    // FILTER SYNTAX
+    /// Quoted strings in filter inputs. Also used to highlight matches in the data.
+    #[serde(with = "hex_color")]
+    pub string: Color,
+
+    /// Operators and literals in filter inputs.
+    #[serde(with = "hex_color")]
+    pub operator: Color
+}
+
+impl Default for Palette
+{
+    fn default() -> Self
+    {
+        Palette::dark()
+    }
+}
+
+impl Palette
+{
+    pub fn dark() -> Self
+    {
+        Self
+        {
+            name: String::from("Dark"),
+            background: Color::from_rgb8(0x20, 0x22, 0x25),
+            surface: Color::from_rgb(0.2, 0.22, 0.23),
+            text: Color::from_rgb(0.9, 0.9, 0.9),
+            muted_text: Color::from_rgb(0.6, 0.6, 0.6),
+            error: Color::from_rgb(1.0, 0.2, 0.2),
+            primary: Color::from_rgb(0.0, 0.6, 0.3),
+            secondary: Color::from_rgb(0.0, 0.4, 0.2),
+            danger: Color::from_rgb(0.6, 0.25, 0.25),
+            button_text: Color::WHITE,
+            date: Color::from_rgb(0.4, 0.8, 0.5),
+            value: Color::from_rgb(0.6, 0.8, 1.0),
+            string: Color::from_rgb(0.95, 0.75, 0.45),
+            operator: Color::from_rgb(0.8, 0.55, 0.95)
+        }
+    }
+
+    pub fn light() -> Self
+    {
+        Self
+        {
+            name: String::from("Light"),
+            background: Color::from_rgb8(0xF5, 0xF5, 0xF2),
+            surface: Color::from_rgb8(0xE1, 0xE4, 0xE6),
+            text: Color::from_rgb8(0x1E, 0x1E, 0x1E),
+            muted_text: Color::from_rgb8(0x6B, 0x6B, 0x6B),
+            error: Color::from_rgb8(0xC8, 0x1E, 0x1E),
+            primary: Color::from_rgb8(0x1F, 0x8A, 0x4C),
+            secondary: Color::from_rgb8(0x4F, 0x8F, 0x68),
+            danger: Color::from_rgb8(0xB0, 0x3A, 0x3A),
+            button_text: Color::WHITE,
+            date: Color::from_rgb8(0x1B, 0x7F, 0x3B),
+            value: Color::from_rgb8(0x1F, 0x5F, 0xA8),
+            string: Color::from_rgb8(0xA6, 0x5E, 0x00),
+            operator: Color::from_rgb8(0x7B, 0x3F, 0xB0)
+        }
+    }
+
+    /// Pure black and white with saturated accents.
+    pub fn high_contrast() -> Self
+    {
+        Self
+        {
+            name: String::from("High contrast"),
+            background: Color::BLACK,
+            surface: Color::from_rgb8(0x1A, 0x1A, 0x1A),
+            text: Color::WHITE,
+            muted_text: Color::from_rgb8(0xD0, 0xD0, 0xD0),
+            error: Color::from_rgb8(0xFF, 0x40, 0x40),
+            primary: Color::from_rgb8(0xFF, 0xD4, 0x00),
+            secondary: Color::from_rgb8(0x00, 0xE5, 0xFF),
+            danger: Color::from_rgb8(0xFF, 0x60, 0x60),
+            button_text: Color::BLACK,
+            date: Color::from_rgb8(0x00, 0xFF, 0x7F),
+            value: Color::from_rgb8(0x66, 0xCC, 0xFF),
+            string: Color::from_rgb8(0xFF, 0xB0, 0x00),
+            operator: Color::from_rgb8(0xFF, 0x80, 0xFF)
+        }
+    }
+
+    /// The palettes that ship with data_sculptor.
+    pub fn built_in() -> Vec<Palette>
+    {
+        vec![Palette::dark(), Palette::light(), Palette::high_contrast()]
+    }
+}
+
+/// (De)serializes colors as hex strings.
+mod hex_color
+{
+    use iced::Color;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        let [r, g, b, a] = color.into_rgba8();
+        let hex = if a == 255 {format!("#{:02x}{:02x}{:02x}", r, g, b)}
+            else {format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)};
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error>
+    {
+        let hex = String::deserialize(deserializer)?;
+
+        // Color::parse slices the string by bytes
+        hex.is_ascii().then(|| Color::parse(&hex)).flatten()
+            .ok_or_else(|| D::Error::custom(format!("invalid color '{}'", hex)))
+    }
+}
@@ -0,0 +1,305 @@
+//! Module for the [`SearchIndex`] that finds words in the values of all days.
+//!
+//! Values are split into words at every character that is not alphanumeric.
+//! Words are compared case-insensitively.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use crate::core::data_containers::DayDataParsed;
+
+/// A parsed search input.
+///
+/// # Syntax
+/// * `word` - Values containing the word.
+/// * `wor*` - Values containing a word that starts with 'wor'.
+/// * `"two words"` - Values containing the words directly after each other.
+///
+/// All parts of the input have to be found in the same value.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SearchQuery
+{
+    pub parts: Vec<QueryPart>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryPart
+{
+    Word {word: String, prefix: bool},
+    Phrase(Vec<String>)
+}
+
+/// A value that contains all parts of a [`SearchQuery`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit
+{
+    /// Index of the day in the data the index was built from.
+    pub day_index: usize,
+
+    /// Index of the entry inside the day.
+    pub entry_index: usize,
+
+    /// Byte ranges of the matched words in the value.
+    pub ranges: Vec<Range<usize>>
+}
+
+/// Position of a word inside the values of the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct WordPosition
+{
+    day_index: usize,
+    entry_index: usize,
+
+    /// Number of the word inside the value.
+    position: usize
+}
+
+/// Identifies a single value by the indices of its day and entry.
+type ValueID = (usize, usize);
+
+/// Inverted index from every word to the values it occurs in.
+/// It has to be rebuilt whenever the data is replaced. Single days can be updated through
+/// [`SearchIndex::update_day`].
+#[derive(Debug, Default, Clone)]
+pub struct SearchIndex
+{
+    /// Sorted so that all words with the same prefix are next to each other.
+    words: BTreeMap<String, Vec<WordPosition>>
+}
+
+impl SearchQuery
+{
+    /// Parses the given search input. See [`SearchQuery`] for the syntax.
+    /// A quote that is never closed lasts until the end of the input.
+    pub fn parse(input: &str) -> Self
+    {
+        let mut parts = Vec::new();
+
+        for (i, piece) in input.split('"').enumerate()
+        {
+            // Every second piece is inside quotes
+            if i % 2 == 1
+            {
+                let words: Vec<String> = tokenize(piece).into_iter().map(|(word, _)| word).collect();
+                match words.len()
+                {
+                    0 => {}
+                    1 => {parts.push(QueryPart::Word {word: words[0].clone(), prefix: false});}
+                    _ => {parts.push(QueryPart::Phrase(words));}
+                }
+                continue;
+            }
+
+            for term in piece.split_whitespace()
+            {
+                let prefix = term.ends_with('*');
+                let mut words = tokenize(term);
+                let last = words.pop();
+                parts.extend(words.into_iter().map(|(word, _)| QueryPart::Word {word, prefix: false}));
+                parts.extend(last.map(|(word, _)| QueryPart::Word {word, prefix}));
+            }
+        }
+
+        Self {parts}
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.parts.is_empty()
+    }
+}
+
+impl SearchIndex
+{
+    /// Indexes every word of every value in the given data.
+    pub fn build(data: &[DayDataParsed]) -> Self
+    {
+        let mut index = Self::default();
+        for (day_index, day) in data.iter().enumerate()
+        {
+            index.add_day(day_index, day);
+        }
+        index
+    }
+
+    /// Updates the index for a single day of the data it was built from that changed.
+    /// `old` is the day before the change with its index in the data, `new` the day after it.
+    /// A day that was inserted has no old day and a day that was removed has no new one.
+    pub fn update_day(&mut self, old: Option<(usize, &DayDataParsed)>, new: Option<(usize, &DayDataParsed)>)
+    {
+        if let Some((day_index, day)) = old
+        {
+            self.remove_day(day_index, day);
+        }
+
+        // Days after an inserted or removed one move
+        match (old, new)
+        {
+            (Some((day_index, _)), None) => {self.shift_days(day_index + 1, -1);}
+            (None, Some((day_index, _))) => {self.shift_days(day_index, 1);}
+            _ => {}
+        }
+
+        if let Some((day_index, day)) = new
+        {
+            self.add_day(day_index, day);
+        }
+    }
+
+    fn add_day(&mut self, day_index: usize, day: &DayDataParsed)
+    {
+        for (entry_index, value) in day.entries.values().enumerate()
+        {
+            for (position, (word, _)) in tokenize(&value.string_value).into_iter().enumerate()
+            {
+                self.words.entry(word).or_default().push(WordPosition {day_index, entry_index, position});
+            }
+        }
+    }
+
+    /// Removes the positions of all words of the given day without moving the other days.
+    fn remove_day(&mut self, day_index: usize, day: &DayDataParsed)
+    {
+        let words: HashSet<String> = day.entries.values()
+            .flat_map(|value| tokenize(&value.string_value))
+            .map(|(word, _)| word)
+            .collect();
+
+        for word in words
+        {
+            let Some(positions) = self.words.get_mut(&word) else {continue};
+            positions.retain(|position| position.day_index != day_index);
+            if positions.is_empty()
+            {
+                self.words.remove(&word);
+            }
+        }
+    }
+
+    /// Moves the positions of all days starting at the given index by the given amount.
+    fn shift_days(&mut self, start: usize, amount: isize)
+    {
+        for position in self.words.values_mut().flatten().filter(|position| position.day_index >= start)
+        {
+            position.day_index = position.day_index.wrapping_add_signed(amount);
+        }
+    }
+
+    /// Finds all values that contain every part of the given query, sorted by their
+    /// position in the given data, which has to be the data the index was built from.
+    pub fn search(&self, query: &SearchQuery, data: &[DayDataParsed]) -> Vec<SearchHit>
+    {
+        let mut found: Option<HashMap<ValueID, HashSet<usize>>> = None;
+
+        for part in &query.parts
+        {
+            let part_found = match part
+            {
+                QueryPart::Word {word, prefix} => {self.find_word(word, *prefix)}
+                QueryPart::Phrase(words) => {self.find_phrase(words)}
+            };
+
+            found = Some(match found
+            {
+                None => {part_found}
+                Some(mut found) =>
+                    {
+                        found.retain(|value, _| part_found.contains_key(value));
+                        for (value, positions) in part_found
+                        {
+                            if let Some(found_positions) = found.get_mut(&value)
+                            {
+                                found_positions.extend(positions);
+                            }
+                        }
+                        found
+                    }
+            });
+        }
+
+        let mut hits: Vec<SearchHit> = found.unwrap_or_default().into_iter()
+            .filter_map(|((day_index, entry_index), positions)|
+                {
+                    let (_, value) = data.get(day_index)?.entries.get_index(entry_index)?;
+                    let ranges = tokenize(&value.string_value).into_iter()
+                        .enumerate()
+                        .filter(|(position, _)| positions.contains(position))
+                        .map(|(_, (_, range))| range)
+                        .collect();
+                    Some(SearchHit {day_index, entry_index, ranges})
+                })
+            .collect();
+
+        hits.sort_by_key(|hit| (hit.day_index, hit.entry_index));
+        hits
+    }
+
+    /// The positions of the given word, or of all words starting with it, grouped by value.
+    fn find_word(&self, word: &str, prefix: bool) -> HashMap<ValueID, HashSet<usize>>
+    {
+        let mut found: HashMap<ValueID, HashSet<usize>> = HashMap::new();
+
+        let positions: Vec<&WordPosition> = if prefix
+        {
+            self.words.range(word.to_string()..)
+                .take_while(|(indexed, _)| indexed.starts_with(word))
+                .flat_map(|(_, positions)| positions)
+                .collect()
+        }
+        else {self.words.get(word).into_iter().flatten().collect()};
+
+        for position in positions
+        {
+            found.entry((position.day_index, position.entry_index)).or_default().insert(position.position);
+        }
+        found
+    }
+
+    /// The positions of all words of every occurrence of the given words directly
+    /// after each other, grouped by value.
+    fn find_phrase(&self, words: &[String]) -> HashMap<ValueID, HashSet<usize>>
+    {
+        let mut found: HashMap<ValueID, HashSet<usize>> = HashMap::new();
+        let Some(first) = words.first() else {return found};
+
+        let following: Vec<HashSet<WordPosition>> = words[1..].iter()
+            .map(|word| self.words.get(word).into_iter().flatten().copied().collect())
+            .collect();
+
+        for start in self.words.get(first).into_iter().flatten()
+        {
+            let is_phrase = following.iter().enumerate().all(|(i, positions)|
+                {
+                    positions.contains(&WordPosition {position: start.position + i + 1, ..*start})
+                });
+
+            if is_phrase
+            {
+                found.entry((start.day_index, start.entry_index)).or_default()
+                    .extend(start.position..start.position + words.len());
+            }
+        }
+        found
+    }
+}
+
+/// Splits the given text into lowercase words and the byte ranges they occupy in the text.
+pub fn tokenize(text: &str) -> Vec<(String, Range<usize>)>
+{
+    let mut words = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')])
+    {
+        match (c.is_alphanumeric(), start)
+        {
+            (true, None) => {start = Some(i);}
+            (false, Some(word_start)) =>
+                {
+                    words.push((text[word_start..i].to_lowercase(), word_start..i));
+                    start = None;
+                }
+            _ => {}
+        }
+    }
+    words
+}
@@ -0,0 +1,137 @@
+//! Module for the [`Settings`] of data_sculptor that are kept between runs.
+
+use std::fmt::Write;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use crate::core::data_containers::{DateKey, DATE_FORMAT};
+use crate::core::filters::expression_parser::FilterMacros;
+use crate::core::filters::filter::KeyFilterMode;
+
+pub const DEFAULT_FONT_SIZE: u16 = 16;
+pub const MIN_FONT_SIZE: u16 = 8;
+pub const MAX_FONT_SIZE: u16 = 40;
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// All user settings. Missing fields of a config file are filled with their defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings
+{
+    /// Name of the theme of the GUI.
+    pub theme: String,
+
+    /// Default size of all text. Only applied on startup.
+    pub font_size: u16,
+
+    /// Format (see [`chrono::format::strftime`]) in which dates are displayed.
+    pub date_display_format: String,
+
+    /// Directory that file dialogs open in.
+    pub default_directory: Option<String>,
+
+    /// Path of the data file that was loaded last.
+    pub last_opened_file: Option<String>,
+
+    /// Paths of the data files that were loaded recently, most recent first.
+    pub recent_files: Vec<String>,
+
+    /// Whether the last opened file is loaded on startup.
+    pub reopen_last_file: bool,
+
+    /// Whether the filters of the last run are restored on startup.
+    pub restore_filters: bool,
+
+    /// Whether the loaded file is reloaded whenever it changes on disk.
+    pub watch_file: bool,
+
+    /// Whether exported reports include summary statistics of every key.
+    pub report_statistics: bool,
+
+    /// The filters of the last run.
+    pub filters: SavedFilters
+}
+
+/// The filters and macros of all filter views. Filters are stored as their titles.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct SavedFilters
+{
+    pub date: SavedFilterSet,
+    pub key: SavedFilterSet,
+    pub value: SavedFilterSet,
+    pub key_filter_mode: KeyFilterMode
+}
+
+/// The filters and macros of a single filter view.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct SavedFilterSet
+{
+    pub filters: Vec<String>,
+    pub macros: FilterMacros
+}
+
+impl Default for Settings
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            theme: String::from("Dark"),
+            font_size: DEFAULT_FONT_SIZE,
+            date_display_format: String::from(DATE_FORMAT),
+            default_directory: None,
+            last_opened_file: None,
+            recent_files: Vec::new(),
+            reopen_last_file: false,
+            restore_filters: true,
+            watch_file: false,
+            report_statistics: true,
+            filters: SavedFilters::default()
+        }
+    }
+}
+
+impl Settings
+{
+    /// Replaces all values that can not be used (e.g. from a manually edited config file)
+    /// with usable ones.
+    pub fn sanitize(&mut self)
+    {
+        self.font_size = self.font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+        if !is_valid_date_format(&self.date_display_format)
+        {
+            self.date_display_format = String::from(DATE_FORMAT);
+        }
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    /// Remembers the given path as the last opened file and moves it to the top of the
+    /// recent files. Only the [`MAX_RECENT_FILES`] most recent files are kept.
+    pub fn add_recent_file(&mut self, path: &str)
+    {
+        self.last_opened_file = Some(path.to_string());
+        self.recent_files.retain(|recent| recent != path);
+        self.recent_files.insert(0, path.to_string());
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+}
+
+/// Whether the given string is a non-empty format that chrono can display dates in.
+/// Formats that need more than a date (like a time) can not be displayed.
+pub fn is_valid_date_format(format: &str) -> bool
+{
+    let Some(sample) = NaiveDate::from_ymd_opt(2024, 1, 1) else {return false};
+    !format.trim().is_empty() && write!(String::new(), "{}", sample.format(format)).is_ok()
+}
+
+/// Displays the given date in the given format. The format has to be valid.
+/// See [`is_valid_date_format`].
+pub fn format_date(date: &DateKey, format: &str) -> String
+{
+    if format == DATE_FORMAT
+    {
+        return date.date_string.clone();
+    }
+    date.naive_date.format(format).to_string()
+}
@@ -0,0 +1,143 @@
+//! Handles reading and writing the [`Settings`] config file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::core::settings::Settings;
+use crate::core::palette::Palette;
+
+/// Path of the config file inside the config directory of the platform.
+/// None if the platform has no config directory.
+pub fn config_path() -> Option<PathBuf>
+{
+    Some(dirs::config_dir()?.join("data_sculptor").join("config.json"))
+}
+
+/// Path of the directory holding the custom palette files.
+/// None if the platform has no config directory.
+pub fn palettes_path() -> Option<PathBuf>
+{
+    Some(dirs::config_dir()?.join("data_sculptor").join("palettes"))
+}
+
+/// Loads the settings from the config file. Returns the default settings if there is no
+/// config file yet. If the file exists but can not be read, the default settings are returned
+/// together with the reason.
+pub fn load_settings() -> (Settings, Option<String>)
+{
+    let Some(path) = config_path() else {return (Settings::default(), None)};
+    if !path.exists()
+    {
+        return (Settings::default(), None);
+    }
+
+    match read_settings(&path)
+    {
+        Ok(settings) => {(settings, None)}
+        Err(e) => {(Settings::default(), Some(e))}
+    }
+}
+
+/// Writes the settings to the config file.
+pub fn save_settings(settings: &Settings) -> Result<(), String>
+{
+    let path = config_path().ok_or(String::from("No config directory found"))?;
+    write_settings(&path, settings)
+}
+
+/// Reads the settings from the file at the given path.
+/// Values that can not be used are replaced, see [`Settings::sanitize`].
+pub fn read_settings(path: &Path) -> Result<Settings, String>
+{
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file: {}", e))?;
+    let mut settings: Settings = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid config file: {}", e))?;
+
+    settings.sanitize();
+    Ok(settings)
+}
+
+/// Writes the settings to the file at the given path, creating its directory if needed.
+pub fn write_settings(path: &Path, settings: &Settings) -> Result<(), String>
+{
+    if let Some(directory) = path.parent()
+    {
+        fs::create_dir_all(directory)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write config file: {}", e))
+}
+
+// PALETTES
+/// Loads all custom palettes from the palettes directory, sorted by name.
+/// Returns them together with the reasons why any palette files could not be read.
+pub fn load_palettes() -> (Vec<Palette>, Vec<String>)
+{
+    let Some(directory) = palettes_path() else {return (Vec::new(), Vec::new())};
+    let Ok(entries) = fs::read_dir(&directory) else {return (Vec::new(), Vec::new())};
+
+    let mut palettes = Vec::new();
+    let mut errors = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path())
+    {
+        if path.extension().is_none_or(|extension| extension != "json")
+        {
+            continue;
+        }
+
+        match read_palette(&path)
+        {
+            Ok(palette) => {palettes.push(palette);}
+            Err(e) => {errors.push(e);}
+        }
+    }
+
+    palettes.sort_by(|a, b| a.name.cmp(&b.name));
+    (palettes, errors)
+}
+
+/// Copies the palette to the palettes directory, so that it is loaded on every startup.
+/// A palette with the same name is overwritten.
+pub fn save_palette(palette: &Palette) -> Result<(), String>
+{
+    let directory = palettes_path().ok_or(String::from("No config directory found"))?;
+    let file_name: String = palette.name.chars()
+        .map(|c| if c.is_alphanumeric() {c} else {'_'})
+        .collect();
+
+    write_palette(&directory.join(format!("{}.json", file_name)), palette)
+}
+
+/// Reads a palette from the file at the given path. Its name has to differ from those of
+/// the built-in palettes. Colors missing from the file are taken from the dark palette.
+pub fn read_palette(path: &Path) -> Result<Palette, String>
+{
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read palette file: {}", e))?;
+    let palette: Palette = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid palette file {}: {}", path.display(), e))?;
+
+    if palette.name.trim().is_empty() || Palette::built_in().iter().any(|built_in| built_in.name == palette.name)
+    {
+        return Err(format!("Palette file {} needs a name that differs from the built-in palettes",
+                           path.display()));
+    }
+    Ok(palette)
+}
+
+/// Writes the palette to the file at the given path, creating its directory if needed.
+pub fn write_palette(path: &Path, palette: &Palette) -> Result<(), String>
+{
+    if let Some(directory) = path.parent()
+    {
+        fs::create_dir_all(directory)
+            .map_err(|e| format!("Failed to create palette directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(palette)
+        .map_err(|e| format!("Failed to serialize palette: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write palette file: {}", e))
+}
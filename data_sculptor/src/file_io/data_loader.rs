@@ -1,10 +1,24 @@
 //! Handles the loading of json files into a list of [`DayDataUnparsed`]
 
 use std::fs;
-use std::io::Error;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use indexmap::IndexMap;
 use crate::core::data_containers::DayDataUnparsed;
 
+/// Extensions of the files data can be loaded from.
+pub const SUPPORTED_EXTENSIONS: [&str; 1] = ["json"];
+
+/// Whether data can be loaded from the file at the given path, judging by its extension.
+pub fn is_supported_file(file_path: &str) -> bool
+{
+    Path::new(file_path).extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .is_some_and(|extension| SUPPORTED_EXTENSIONS.contains(&extension.as_str()))
+}
+
 pub fn load_data_str(data_str: &str) -> Result<Vec<DayDataUnparsed>, serde_json::Error>
 {
     let data_map: IndexMap<String, IndexMap<String, String>> = serde_json::from_str(&data_str)?;
@@ -25,4 +39,46 @@ pub fn load_data_file(file_path: &str) -> Result<Vec<DayDataUnparsed>, Error>
         Ok(data) => {Ok(data)}
         Err(e) => {Err(Error::from(e))}
     }
-}
\ No newline at end of file
+}
+
+/// Loads the data file at the given path in chunks so that the progress can be reported.
+///
+/// # Arguments
+/// * `file_path` - Path of the file to load.
+/// * `on_progress` - Called with the fraction (0.0 to 1.0) of bytes read so far.
+/// * `cancelled` - Checked after every chunk. If it is set, loading stops with an
+///   [`ErrorKind::Interrupted`] error.
+pub fn load_data_file_with_progress(file_path: &str, on_progress: &mut dyn FnMut(f32),
+                                    cancelled: &AtomicBool) -> Result<Vec<DayDataUnparsed>, Error>
+{
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    let mut file = File::open(file_path)?;
+    let total_bytes = file.metadata()?.len().max(1) as f32;
+
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    loop
+    {
+        if cancelled.load(Ordering::Relaxed)
+        {
+            return Err(Error::new(ErrorKind::Interrupted, "Loading was cancelled"));
+        }
+
+        let read = file.read(&mut chunk)?;
+        if read == 0
+        {
+            break;
+        }
+
+        bytes.extend_from_slice(&chunk[..read]);
+        on_progress((bytes.len() as f32 / total_bytes).min(1.0));
+    }
+
+    let data_str = String::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    match load_data_str(data_str.as_str())
+    {
+        Ok(data) => {Ok(data)}
+        Err(e) => {Err(Error::from(e))}
+    }
+}
@@ -1,13 +1,39 @@
 //! Handles writing [`DayDataParsed`] to a json file.
 
 use std::fs::File;
-use std::io::{Write};
+use std::io::{Error, Write};
 use indexmap::IndexMap;
-use crate::core::data_containers::{DayDataParsed};
+use crate::core::data_containers::{DayDataParsed, unparse};
 use crate::core::filters::filter;
 use crate::core::filters::filter::{filter_key, FilterType};
 use crate::gui::views::list::list_view_control::ListView;
 
+/// Writes all given data unfiltered to a file at the given filepath, overwriting it if it exists.
+/// The result has the same format the data is loaded from.
+///
+/// # Arguments
+/// * `file_path` - `&str` specifying the path of the file to write to.
+/// * `days` - Slice of `DayDataParsed` structs that hold the data to write.
+pub fn write_data(file_path: &str, days: &[DayDataParsed]) -> Result<(), Error>
+{
+    let json_data = to_json(days)?;
+    let mut file = File::create(file_path)?;
+    file.write_all(json_data.as_bytes())
+}
+
+/// Converts the given days to a pretty printed json string in the format the data is loaded from.
+pub fn to_json(days: &[DayDataParsed]) -> Result<String, serde_json::Error>
+{
+    let mut all_days: IndexMap<String, IndexMap<String, String>> = IndexMap::new();
+    for day in days
+    {
+        let unparsed = unparse(day);
+        all_days.insert(unparsed.date, unparsed.entries);
+    }
+
+    serde_json::to_string_pretty(&all_days)
+}
+
 /// Writes the filtered data to a file at the given filepath.
 ///
 /// # Arguments
@@ -28,11 +54,22 @@ pub fn write_data_filtered(file_path: String, days: &Vec<DayDataParsed>, list_vi
 }
 
 fn _write_filtered_to_file(mut file: File, days: &Vec<DayDataParsed>, list_view: &ListView)
+{
+    let filtered_days = filter_data(days, list_view);
+
+    let json_data = serde_json::to_string_pretty(&filtered_days).unwrap();
+    file.write_all(json_data.as_bytes()).expect("Failed to save to file");
+}
+
+/// Returns the entries of all given days that pass the filters of the given [`ListView`],
+/// keyed by date. Days that do not pass the filters are left out.
+pub fn filter_data(days: &[DayDataParsed], list_view: &ListView) -> IndexMap<String, IndexMap<String, String>>
 {
     let mut filtered_days: IndexMap<String, IndexMap<String, String>> = IndexMap::new();
 
     let date_filters = list_view.get_filters(&FilterType::Date);
     let key_filters = list_view.get_filters(&FilterType::Key);
+    let key_filter_mode = list_view.get_filter_view(&FilterType::Key).key_filter_mode;
     let value_filters = list_view.get_filters(&FilterType::Value);
 
     for day in days
@@ -43,7 +80,7 @@ fn _write_filtered_to_file(mut file: File, days: &Vec<DayDataParsed>, list_view:
 
             for (key, value) in &day.entries
             {
-                if filter_key(day, &key, &key_filters)
+                if filter_key(day, key, key_filters, key_filter_mode)
                 {
                     filtered_entries.insert(key.title.clone(), value.string_value.clone());
                 }
@@ -53,6 +90,5 @@ fn _write_filtered_to_file(mut file: File, days: &Vec<DayDataParsed>, list_view:
         }
     }
 
-    let json_data = serde_json::to_string_pretty(&filtered_days).unwrap();
-    file.write_all(json_data.as_bytes()).expect("Failed to save to file");
-}
\ No newline at end of file
+    filtered_days
+}
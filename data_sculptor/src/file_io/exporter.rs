@@ -0,0 +1,141 @@
+//! Module defining the [`Exporter`] interface that filtered data is exported through.
+//!
+//! The json exporter lives here, the report exporters in
+//! [`report_exporter`](crate::file_io::report_exporter).
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{Error, Write};
+use crate::core::data_containers::DayDataParsed;
+use crate::core::filters::filter::{FilterType, KeyFilterMode};
+use crate::file_io::data_writer;
+use crate::file_io::report_exporter::{HtmlExporter, MarkdownExporter};
+use crate::file_io::text_formats::DayEntries;
+use crate::gui::views::list::list_view_control::ListView;
+
+/// Converts an [`ExportContent`] to the text of a file.
+pub trait Exporter
+{
+    /// Name of the format shown to the user.
+    fn name(&self) -> &'static str;
+
+    /// Extension of the written files, without the dot.
+    fn extension(&self) -> &'static str;
+
+    fn export(&self, content: &ExportContent) -> Result<String, Error>;
+}
+
+/// Everything an [`Exporter`] can write.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportContent
+{
+    pub title: String,
+
+    /// The filters the days passed. Types without active filters are left out.
+    pub filters: Vec<FilterGroup>,
+
+    /// The entries of the filtered days keyed by date.
+    pub days: DayEntries,
+
+    /// Whether summary statistics of every key are included.
+    pub statistics: bool
+}
+
+/// The expressions of the active filters of one [`FilterType`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterGroup
+{
+    /// What the filters act on, e.g. "Key filters (any)".
+    pub name: String,
+    pub expressions: Vec<String>
+}
+
+impl ExportContent
+{
+    /// Collects the days of the given data that pass the filters of the given [`ListView`]
+    /// (see [`data_writer::filter_data`]) and the expressions of those filters.
+    pub fn from_list_view(title: String, data: &[DayDataParsed], list_view: &ListView, statistics: bool) -> Self
+    {
+        let key_filter_mode = match list_view.get_filter_view(&FilterType::Key).key_filter_mode
+        {
+            KeyFilterMode::Any => {"Key filters (keys matching any filter)"}
+            KeyFilterMode::All => {"Key filters (keys matching all filters)"}
+        };
+        let groups = [(FilterType::Date, "Date filters"), (FilterType::Key, key_filter_mode),
+            (FilterType::Value, "Value filters")];
+
+        let filters = groups.into_iter()
+            .map(|(filter_type, name)| FilterGroup
+            {
+                name: String::from(name),
+                expressions: list_view.get_filters(&filter_type).values()
+                    .map(|filter| filter.title.clone())
+                    .collect()
+            })
+            .filter(|group| !group.expressions.is_empty())
+            .collect();
+
+        Self {title, filters, days: data_writer::filter_data(data, list_view), statistics}
+    }
+}
+
+/// Exports the given content with the given exporter to a file at the given path,
+/// overwriting it if it exists.
+pub fn write_export(exporter: &dyn Exporter, content: &ExportContent, file_path: &str) -> Result<(), Error>
+{
+    let text = exporter.export(content)?;
+    let mut file = File::create(file_path)?;
+    file.write_all(text.as_bytes())
+}
+
+/// Writes only the days, in the format the data is loaded from.
+/// The result is the same as saving the filtered data.
+pub struct JsonExporter;
+
+impl Exporter for JsonExporter
+{
+    fn name(&self) -> &'static str
+    {
+        "JSON"
+    }
+
+    fn extension(&self) -> &'static str
+    {
+        "json"
+    }
+
+    fn export(&self, content: &ExportContent) -> Result<String, Error>
+    {
+        Ok(serde_json::to_string_pretty(&content.days)?)
+    }
+}
+
+/// All formats data can be exported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat
+{
+    Json, Markdown, Html
+}
+
+impl ExportFormat
+{
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Json, ExportFormat::Markdown, ExportFormat::Html];
+
+    pub fn exporter(&self) -> &'static dyn Exporter
+    {
+        match self
+        {
+            ExportFormat::Json => {&JsonExporter}
+            ExportFormat::Markdown => {&MarkdownExporter}
+            ExportFormat::Html => {&HtmlExporter}
+        }
+    }
+}
+
+impl Display for ExportFormat
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", self.exporter().name())
+    }
+}
@@ -1,19 +1,53 @@
 //! Module specifying all file dialogs used in data_sculptor
 
 use rfd::FileDialog;
+use crate::file_io::data_loader::SUPPORTED_EXTENSIONS;
+use crate::file_io::exporter::Exporter;
 
-/// Opens a new file dialog and optionally returns a file path if a file is chosen
-pub fn pick_file() -> Option<String>
+/// Opens a new file dialog for data files and optionally returns a file path if a file is chosen.
+/// The dialog starts in the given directory if there is one.
+pub fn pick_file(directory: Option<&str>) -> Option<String>
 {
-    let file = FileDialog::new().pick_file();
+    let file = dialog(directory)
+        .add_filter("Data Files", &SUPPORTED_EXTENSIONS)
+        .add_filter("All Files", &["*"])
+        .pick_file();
     file.map(|f| f.as_path().to_string_lossy().into_owned())
 }
 
-/// Opens a new save dialogue for json files and optionally returns a file path if one is chosen
-pub fn save_json_file() -> Option<String>
+/// Opens a new save dialogue for json files and optionally returns a file path if one is chosen.
+/// The dialog starts in the given directory if there is one.
+pub fn save_json_file(directory: Option<&str>) -> Option<String>
 {
-    let file = FileDialog::new()
+    let file = dialog(directory)
         .add_filter("Json Files (*.json)", &["json"])
         .save_file();
     file.map(|f| f.as_path().to_string_lossy().into_owned())
-}
\ No newline at end of file
+}
+
+/// Opens a new save dialogue for files of the given [`Exporter`] and optionally returns
+/// a file path if one is chosen.
+pub fn save_export_file(directory: Option<&str>, exporter: &dyn Exporter) -> Option<String>
+{
+    let filter_name = format!("{} Files (*.{})", exporter.name(), exporter.extension());
+    let file = dialog(directory)
+        .add_filter(filter_name, &[exporter.extension()])
+        .save_file();
+    file.map(|f| f.as_path().to_string_lossy().into_owned())
+}
+
+/// Opens a new folder dialog and optionally returns a directory path if one is chosen.
+pub fn pick_folder(directory: Option<&str>) -> Option<String>
+{
+    let folder = dialog(directory).pick_folder();
+    folder.map(|f| f.as_path().to_string_lossy().into_owned())
+}
+
+fn dialog(directory: Option<&str>) -> FileDialog
+{
+    match directory
+    {
+        Some(directory) => {FileDialog::new().set_directory(directory)}
+        None => {FileDialog::new()}
+    }
+}
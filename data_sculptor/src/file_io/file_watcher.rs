@@ -0,0 +1,69 @@
+//! Module for noticing when a data file changes on disk.
+//!
+//! Files are polled instead of using file system notifications, which keeps the watcher
+//! independent of the platform and of how the file is written.
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use iced::futures::{SinkExt, Stream, StreamExt};
+use iced::futures::channel::mpsc;
+
+/// Time between two checks of the watched file.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The modification time and length of a file. The file changed if either of them changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileStamp
+{
+    pub modified: Option<SystemTime>,
+    pub len: u64
+}
+
+/// Returns the current [`FileStamp`] of the file at the given path
+/// or None if the file can not be accessed.
+pub fn file_stamp(file_path: &str) -> Option<FileStamp>
+{
+    let metadata = fs::metadata(file_path).ok()?;
+    Some(FileStamp {modified: metadata.modified().ok(), len: metadata.len()})
+}
+
+/// Streams the given path every time the file at it changes. The file is polled on its own
+/// thread every [`POLL_INTERVAL`], which stops once the stream is dropped.
+/// A file that can not be accessed (e.g. while it is being replaced) is not reported.
+pub fn watch(file_path: String) -> impl Stream<Item = String>
+{
+    iced::stream::channel(1, move |mut output| async move
+        {
+            let (sender, mut changes) = mpsc::unbounded();
+            let polled_path = file_path.clone();
+            thread::spawn(move || poll_changes(&polled_path, sender));
+
+            while changes.next().await.is_some()
+            {
+                if output.send(file_path.clone()).await.is_err()
+                {
+                    break;
+                }
+            }
+        })
+}
+
+/// Compares the [`FileStamp`] of the file at the given path to the previous one every
+/// [`POLL_INTERVAL`] and sends a notification on every change. Returns once the
+/// receiving end is dropped.
+fn poll_changes(file_path: &str, sender: mpsc::UnboundedSender<()>)
+{
+    let mut last_stamp = file_stamp(file_path);
+    while !sender.is_closed()
+    {
+        thread::sleep(POLL_INTERVAL);
+
+        let stamp = file_stamp(file_path);
+        if stamp != last_stamp && stamp.is_some()
+        {
+            let _ = sender.unbounded_send(());
+        }
+        last_stamp = stamp;
+    }
+}
@@ -3,4 +3,9 @@
 pub mod data_loader;
 pub mod data_writer;
 pub mod file_dialogs;
-pub mod asset_handler;
\ No newline at end of file
+pub mod asset_handler;
+pub mod config_handler;
+pub mod file_watcher;
+pub mod text_formats;
+pub mod exporter;
+pub mod report_exporter;
@@ -0,0 +1,314 @@
+//! Module for the [`Exporter`]s that render the filtered data into a self-contained report
+//! to share with people who do not use data_sculptor.
+//!
+//! A report holds the title, the active filters, optional [`KeyStatistics`]
+//! and one section per day.
+
+use std::collections::HashSet;
+use std::io::Error;
+use crate::file_io::exporter::{ExportContent, Exporter};
+use crate::file_io::text_formats;
+use crate::file_io::text_formats::{markdown_cell, DayEntries};
+
+/// Summary of the values of a single key across all days of a report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyStatistics
+{
+    pub key: String,
+
+    /// Number of days that have the key.
+    pub count: usize,
+    pub distinct_values: usize,
+
+    /// Only computed if every value of the key is a number.
+    pub numeric: Option<NumericStatistics>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericStatistics
+{
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub sum: f64
+}
+
+/// Computes the [`KeyStatistics`] of every key of the given days in the order the keys
+/// first appear in.
+pub fn key_statistics(days: &DayEntries) -> Vec<KeyStatistics>
+{
+    text_formats::all_keys(days).into_iter()
+        .map(|key|
+            {
+                let values: Vec<&str> = days.values()
+                    .filter_map(|entries| entries.get(key))
+                    .map(String::as_str)
+                    .collect();
+
+                let numbers: Option<Vec<f64>> = values.iter()
+                    .map(|value| value.trim().parse::<f64>().ok().filter(|number| number.is_finite()))
+                    .collect();
+
+                let numeric = numbers.filter(|numbers| !numbers.is_empty()).map(|numbers|
+                    {
+                        let sum: f64 = numbers.iter().sum();
+                        NumericStatistics
+                        {
+                            min: numbers.iter().copied().fold(f64::INFINITY, f64::min),
+                            max: numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                            mean: sum / numbers.len() as f64,
+                            sum
+                        }
+                    });
+
+                KeyStatistics
+                {
+                    key: key.to_string(),
+                    count: values.len(),
+                    distinct_values: values.iter().collect::<HashSet<_>>().len(),
+                    numeric
+                }
+            })
+        .collect()
+}
+
+/// Renders a Markdown document. Every day is a table of its keys and values.
+pub struct MarkdownExporter;
+
+impl Exporter for MarkdownExporter
+{
+    fn name(&self) -> &'static str
+    {
+        "Markdown"
+    }
+
+    fn extension(&self) -> &'static str
+    {
+        "md"
+    }
+
+    fn export(&self, content: &ExportContent) -> Result<String, Error>
+    {
+        let mut lines: Vec<String> = vec![format!("# {}", markdown_text(&content.title)), String::new(),
+            day_summary(&content.days)];
+
+        // FILTERS
+        lines.extend([String::new(), String::from("## Filters"), String::new()]);
+        if content.filters.is_empty()
+        {
+            lines.push(String::from(NO_FILTERS));
+        }
+        for group in &content.filters
+        {
+            let expressions: Vec<String> = group.expressions.iter()
+                .map(|expression| markdown_code(expression))
+                .collect();
+            lines.push(format!("- {}: {}", markdown_text(&group.name), expressions.join(", ")));
+        }
+
+        // STATISTICS
+        if content.statistics
+        {
+            lines.extend([String::new(), String::from("## Summary"), String::new()]);
+            lines.push(markdown_row(&STATISTICS_HEADER.map(String::from)));
+            lines.push(markdown_row(&STATISTICS_HEADER.map(|_| String::from("---"))));
+            for statistics in key_statistics(&content.days)
+            {
+                lines.push(markdown_row(&statistics_cells(&statistics).map(|cell| markdown_cell(&cell))));
+            }
+        }
+
+        // DAYS
+        for (date, entries) in &content.days
+        {
+            lines.extend([String::new(), format!("## {}", markdown_text(date)), String::new()]);
+            lines.push(String::from("| Key | Value |"));
+            lines.push(String::from("| --- | --- |"));
+            for (key, value) in entries
+            {
+                lines.push(markdown_row(&[markdown_cell(key), markdown_cell(value)]));
+            }
+        }
+
+        lines.push(String::new());
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Renders an HTML page that includes its own styling.
+pub struct HtmlExporter;
+
+impl Exporter for HtmlExporter
+{
+    fn name(&self) -> &'static str
+    {
+        "HTML"
+    }
+
+    fn extension(&self) -> &'static str
+    {
+        "html"
+    }
+
+    fn export(&self, content: &ExportContent) -> Result<String, Error>
+    {
+        let title = escape_html(&content.title);
+        let mut body: Vec<String> = vec![format!("<h1>{}</h1>", title),
+            format!("<p>{}</p>", escape_html(&day_summary(&content.days)))];
+
+        // FILTERS
+        body.push(String::from("<h2>Filters</h2>"));
+        if content.filters.is_empty()
+        {
+            body.push(format!("<p>{}</p>", NO_FILTERS));
+        }
+        else
+        {
+            body.push(String::from("<ul>"));
+            for group in &content.filters
+            {
+                let expressions: Vec<String> = group.expressions.iter()
+                    .map(|expression| format!("<code>{}</code>", escape_html(expression)))
+                    .collect();
+                body.push(format!("<li>{}: {}</li>", escape_html(&group.name), expressions.join(", ")));
+            }
+            body.push(String::from("</ul>"));
+        }
+
+        // STATISTICS
+        if content.statistics
+        {
+            body.push(String::from("<h2>Summary</h2>"));
+            body.push(String::from("<table>"));
+            body.push(html_row("th", &STATISTICS_HEADER.map(String::from)));
+            for statistics in key_statistics(&content.days)
+            {
+                body.push(html_row("td", &statistics_cells(&statistics)));
+            }
+            body.push(String::from("</table>"));
+        }
+
+        // DAYS
+        for (date, entries) in &content.days
+        {
+            body.push(String::from("<section>"));
+            body.push(format!("<h2>{}</h2>", escape_html(date)));
+            body.push(String::from("<table>"));
+            for (key, value) in entries
+            {
+                body.push(html_row("td", &[key.clone(), value.clone()]));
+            }
+            body.push(String::from("</table>"));
+            body.push(String::from("</section>"));
+        }
+
+        Ok(format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+            <style>{}</style>\n</head>\n<body>\n{}\n</body>\n</html>\n", title, HTML_STYLE, body.join("\n")))
+    }
+}
+
+const NO_FILTERS: &str = "No filters are active.";
+const STATISTICS_HEADER: [&str; 7] = ["Key", "Days", "Distinct values", "Min", "Max", "Mean", "Sum"];
+const HTML_STYLE: &str = "body {font-family: sans-serif; max-width: 1000px; margin: 2em auto; padding: 0 1em;} \
+    table {border-collapse: collapse; margin-bottom: 1em;} \
+    th, td {border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top;} \
+    td {white-space: pre-wrap;} \
+    code {background: #eee; padding: 1px 4px;}";
+
+/// Describes how many days the report holds and which dates they span.
+fn day_summary(days: &DayEntries) -> String
+{
+    let (Some((first, _)), Some((last, _))) = (days.first(), days.last()) else
+    {
+        return String::from("No days passed the filters.");
+    };
+
+    match days.len()
+    {
+        1 => {format!("1 day: {}", first)}
+        count => {format!("{} days from {} to {}", count, first, last)}
+    }
+}
+
+/// The cells of a row of the summary table, see [`STATISTICS_HEADER`].
+fn statistics_cells(statistics: &KeyStatistics) -> [String; 7]
+{
+    let numeric = |value: fn(&NumericStatistics) -> f64|
+        {
+            statistics.numeric.as_ref().map(|numeric| format_number(value(numeric))).unwrap_or_default()
+        };
+
+    [
+        statistics.key.clone(),
+        statistics.count.to_string(),
+        statistics.distinct_values.to_string(),
+        numeric(|numeric| numeric.min),
+        numeric(|numeric| numeric.max),
+        numeric(|numeric| numeric.mean),
+        numeric(|numeric| numeric.sum)
+    ]
+}
+
+/// Rounds the given number to at most two decimals.
+pub fn format_number(number: f64) -> String
+{
+    let rounded = format!("{:.2}", number);
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {String::from("0")} else {trimmed.to_string()}
+}
+
+/// Characters that are escaped with a backslash in Markdown text so that they are not read
+/// as formatting.
+const MARKDOWN_SPECIAL_CHARS: [char; 13] = ['\\', '`', '*', '_', '[', ']', '<', '>', '#', '!', '|', '~', '&'];
+
+/// Escapes the given text for a single line of Markdown, e.g. a heading.
+/// Line breaks are replaced with spaces.
+pub fn markdown_text(text: &str) -> String
+{
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars()
+    {
+        match ch
+        {
+            '\r' | '\n' => {escaped.push(' ');}
+            ch if MARKDOWN_SPECIAL_CHARS.contains(&ch) => {escaped.push('\\'); escaped.push(ch);}
+            ch => {escaped.push(ch);}
+        }
+    }
+    escaped
+}
+
+/// Wraps the given text in a Markdown code span. The span is delimited by more backticks
+/// than the longest run of backticks in the text, so the text is kept as it is.
+pub fn markdown_code(text: &str) -> String
+{
+    let longest_run = text.split(|ch| ch != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+
+    // Spaces keep backticks at the edges apart from the fence, they are stripped when rendered
+    let padding = if text.starts_with('`') || text.ends_with('`') {" "} else {""};
+    format!("{fence}{padding}{text}{padding}{fence}")
+}
+
+fn markdown_row(cells: &[String]) -> String
+{
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Builds a table row with the given cells in elements with the given tag.
+fn html_row(tag: &str, cells: &[String]) -> String
+{
+    let cells: Vec<String> = cells.iter()
+        .map(|cell| format!("<{}>{}</{}>", tag, escape_html(cell), tag))
+        .collect();
+    format!("<tr>{}</tr>", cells.concat())
+}
+
+pub fn escape_html(text: &str) -> String
+{
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,137 @@
+//! Converts data to the text formats it can be copied to the clipboard in.
+
+use std::fmt::{Display, Formatter};
+use indexmap::{IndexMap, IndexSet};
+
+/// The entries of days keyed by date, as returned by
+/// [`data_writer::filter_data`](crate::file_io::data_writer::filter_data).
+pub type DayEntries = IndexMap<String, IndexMap<String, String>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFormat
+{
+    /// The format the data is loaded from.
+    Json,
+
+    /// One row per day and one column per key.
+    Csv,
+
+    /// A table like [`TextFormat::Csv`].
+    Markdown,
+
+    /// Every day as its date followed by one indented line per entry.
+    PlainText
+}
+
+impl TextFormat
+{
+    pub const ALL: [TextFormat; 4] = [TextFormat::Json, TextFormat::Csv, TextFormat::Markdown, TextFormat::PlainText];
+}
+
+impl Display for TextFormat
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            TextFormat::Json => {write!(f, "JSON")}
+            TextFormat::Csv => {write!(f, "CSV")}
+            TextFormat::Markdown => {write!(f, "Markdown")}
+            TextFormat::PlainText => {write!(f, "Plain text")}
+        }
+    }
+}
+
+/// Converts the given days to the given format.
+pub fn format_days(days: &DayEntries, format: TextFormat) -> Result<String, serde_json::Error>
+{
+    match format
+    {
+        TextFormat::Json => {serde_json::to_string_pretty(days)}
+        TextFormat::Csv => {Ok(to_csv(days))}
+        TextFormat::Markdown => {Ok(to_markdown_table(days))}
+        TextFormat::PlainText => {Ok(to_plain_text(days))}
+    }
+}
+
+/// All keys of the given days in the order they first appear in.
+pub fn all_keys(days: &DayEntries) -> Vec<&str>
+{
+    let keys: IndexSet<&str> = days.values()
+        .flat_map(|entries| entries.keys())
+        .map(String::as_str)
+        .collect();
+    keys.into_iter().collect()
+}
+
+/// Builds a table with a date column and one column per key. Cells of keys a day does not
+/// have are left empty. Fields are quoted as described in RFC 4180.
+pub fn to_csv(days: &DayEntries) -> String
+{
+    let keys = all_keys(days);
+    let mut lines: Vec<String> = Vec::with_capacity(days.len() + 1);
+
+    let header = std::iter::once("date").chain(keys.iter().copied());
+    lines.push(header.map(csv_field).collect::<Vec<String>>().join(","));
+
+    for (date, entries) in days
+    {
+        let values = keys.iter().map(|key| entries.get(*key).map(String::as_str).unwrap_or(""));
+        let row = std::iter::once(date.as_str()).chain(values);
+        lines.push(row.map(csv_field).collect::<Vec<String>>().join(","));
+    }
+
+    lines.join("\r\n")
+}
+
+/// Builds a Markdown table with the same rows and columns as [`to_csv`].
+pub fn to_markdown_table(days: &DayEntries) -> String
+{
+    let keys = all_keys(days);
+    let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines: Vec<String> = Vec::with_capacity(days.len() + 2);
+    lines.push(row(std::iter::once("date").chain(keys.iter().copied()).map(markdown_cell).collect()));
+    lines.push(row(vec![String::from("---"); keys.len() + 1]));
+
+    for (date, entries) in days
+    {
+        let values = keys.iter().map(|key| entries.get(*key).map(String::as_str).unwrap_or(""));
+        lines.push(row(std::iter::once(date.as_str()).chain(values).map(markdown_cell).collect()));
+    }
+
+    lines.join("\n")
+}
+
+/// Lists every day as its date followed by one indented `key: value` line per entry.
+/// Days are separated by an empty line.
+pub fn to_plain_text(days: &DayEntries) -> String
+{
+    days.iter()
+        .map(|(date, entries)|
+            {
+                let lines = entries.iter().map(|(key, value)| format!("  {}: {}", key, value));
+                std::iter::once(date.clone()).chain(lines).collect::<Vec<String>>().join("\n")
+            })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Quotes the given field if it contains a comma, a quote or a line break.
+fn csv_field(field: &str) -> String
+{
+    if field.contains([',', '"', '\n', '\r'])
+    {
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+    field.to_string()
+}
+
+/// Escapes everything that would end the given cell of a Markdown table early.
+pub fn markdown_cell(cell: &str) -> String
+{
+    cell.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
@@ -3,23 +3,55 @@
 //! It manages the iced application and, based on its current state,
 //! switches between displaying the different views of the 'views' module.
 
-use iced::{Task, Element, Theme};
+use iced::{event, keyboard, window, Alignment, Element, Event, Length, Subscription, Task, Theme};
 use std::sync::{Arc, Mutex};
-use iced::widget::Column;
-use crate::core::data_manager::DataManager;
+use iced::widget::{button, Column, Container, Row, Text};
+use crate::core::data_manager::{DataManager, LoadingEvent};
 use crate::gui::views::gui_view_type::GUIViewType;
 use crate::gui::gui_message::GUIMessage;
 use crate::gui::views::list::list_view_control::ListView;
 use crate::gui::views::menu::menu_view_control::MenuView;
-use crate::file_io::asset_handler;
+use crate::core::settings::Settings;
+use crate::file_io::{asset_handler, config_handler, file_watcher};
+use crate::gui::views::settings::settings_view_control::{palette_by_name, SettingsView};
+use crate::core::palette::Palette;
+use crate::gui::gui_style;
+use crate::gui::views::command_palette::command_palette_control;
+use crate::gui::views::command_palette::command_palette_control::CommandPalette;
 
 /// Initializes the iced application using an [`Arc`] of the [`DataManager`] that is shared
 /// between all submodules of data_sculptor.
+/// The [`Settings`] are loaded from the config file beforehand.
 pub fn init(data_manager: Arc<Mutex<DataManager>>) -> iced::Result
 {
+    let (settings, config_error) = config_handler::load_settings();
+    let (custom_palettes, palette_errors) = config_handler::load_palettes();
+
+    let mut palettes = Palette::built_in();
+    palettes.extend(custom_palettes);
+    let config_errors: Vec<String> = config_error.into_iter().chain(palette_errors).collect();
+
     iced::application("Data Sculptor", MainGUI::update, MainGUI::view)
-        .theme(|_| Theme::Dark)
-        .run_with(| | MainGUI::new(data_manager))
+        .settings(iced::Settings
+        {
+            default_text_size: settings.font_size.into(),
+            ..Default::default()
+        })
+        .theme(MainGUI::theme)
+        .subscription(MainGUI::subscription)
+        .exit_on_close_request(false)
+        .run_with(| | MainGUI::new(data_manager, settings, palettes, config_errors))
+}
+
+/// Action that waits for the user to decide what to do with unsaved edits.
+pub enum PendingAction
+{
+    /// Closing the window with the given id.
+    Close(window::Id),
+
+    /// Loading files, by the held back [`GUIMessage::FileSelected`] or
+    /// [`GUIMessage::FileDropped`] messages.
+    Load(Vec<GUIMessage>)
 }
 
 /// Struct implementing the iced application. Also holds the shared [`DataManager`]
@@ -29,38 +61,225 @@ pub struct MainGUI
     pub data_manager: Arc<Mutex<DataManager>>,
     pub cur_view: GUIViewType,
 
+    /// Close request or loads that wait for the user to decide what to do with unsaved edits.
+    pub pending_action: Option<PendingAction>,
+
+    /// The settings that are written to the config file.
+    pub settings: Settings,
+
+    /// The built-in and custom palettes. The one named by the theme setting is used.
+    pub palettes: Vec<Palette>,
+
+    /// Theme built from the chosen palette. Only rebuilt when the palette changes.
+    pub theme: Theme,
+
     // VIEWS
     pub list_view: ListView,
-    pub menu_view: MenuView
+    pub menu_view: MenuView,
+    pub settings_view: SettingsView,
+    pub command_palette: CommandPalette
 }
 
 impl MainGUI
 {
-    /// Constructs the iced applications, builds the views and displays the default view.
-    /// Returns the new MainGUI state and the initialization task.
-    fn new(data_manager: Arc<Mutex<DataManager>>) -> (Self, Task<GUIMessage>)
+    /// Constructs the iced applications, builds the views from the given [`Settings`] and
+    /// [`Palette`]s and displays the default view. The given config errors are shown in the
+    /// settings view. Returns the new MainGUI state and the initialization task.
+    fn new(data_manager: Arc<Mutex<DataManager>>, settings: Settings, palettes: Vec<Palette>,
+           config_errors: Vec<String>) -> (Self, Task<GUIMessage>)
     {
-        let instance = Self
+        let theme = gui_style::palette_theme(palette_by_name(&palettes, &settings.theme));
+        let mut instance = Self
             {
                 data_manager,
                 cur_view: GUIViewType::MenuView,
+                pending_action: None,
+                settings_view: SettingsView::new(&settings),
+                settings,
+                palettes,
+                theme,
 
                 // VIEWS
                 list_view: ListView::default(),
-                menu_view: MenuView{}
+                menu_view: MenuView::default(),
+                command_palette: CommandPalette::default()
             };
 
-        return (instance, asset_handler::init_assets_task());
+        if !config_errors.is_empty()
+        {
+            instance.settings_view.config_error = Some(config_errors.join("\n"));
+        }
+        instance.apply_settings();
+        instance.menu_view.check_recent_files(&instance.settings.recent_files);
+        if instance.settings.restore_filters
+        {
+            instance.list_view.restore_filters(&instance.settings.filters);
+        }
+
+        let mut tasks = vec![asset_handler::init_assets_task()];
+        if instance.settings.reopen_last_file
+            && let Some(path) = instance.settings.last_opened_file.clone()
+        {
+            instance.cur_view = GUIViewType::ListView;
+            tasks.push(instance.list_view.update(GUIMessage::FileSelected(path), &instance.data_manager));
+        }
+
+        (instance, Task::batch(tasks))
+    }
+
+    fn theme(&self) -> Theme
+    {
+        self.theme.clone()
+    }
+
+    /// The [`Palette`] chosen in the settings.
+    fn palette(&self) -> &Palette
+    {
+        palette_by_name(&self.palettes, &self.settings.theme)
+    }
+
+    /// Applies the settings to all views that depend on them.
+    fn apply_settings(&mut self)
+    {
+        let palette = self.palette().clone();
+        self.list_view.apply_settings(&self.settings, &palette);
+    }
+
+    fn subscription(&self) -> Subscription<GUIMessage>
+    {
+        Subscription::batch
+        ([
+            window::close_requests().map(GUIMessage::CloseRequested),
+            event::listen_with(global_event),
+            self.file_watcher()
+        ])
+    }
+
+    /// Watches the file the data was loaded from if the settings ask for it.
+    /// See [`file_watcher::watch`].
+    fn file_watcher(&self) -> Subscription<GUIMessage>
+    {
+        let source_path = self.data_manager.lock().unwrap().source_path.clone();
+        match source_path.filter(|_| self.settings.watch_file)
+        {
+            Some(path) =>
+                {
+                    Subscription::run_with_id(path.clone(), file_watcher::watch(path))
+                        .map(GUIMessage::WatchedFileChanged)
+                }
+            None => {Subscription::none()}
+        }
     }
 
     fn update(&mut self, message: GUIMessage) -> Task<GUIMessage>
     {
+        if let GUIMessage::LoadingUpdate(LoadingEvent::Finished(_)) = message
+        {
+            return self.finish_loading(message);
+        }
+        if !can_change_settings(&message)
+        {
+            return self.handle_message(message);
+        }
+
+        let previous_settings = self.settings.clone();
+        let previous_palette = self.palette().clone();
+        let task = self.handle_message(message);
+
+        // An imported palette can replace the one in use without changing its name
+        let palette_changed = *self.palette() != previous_palette;
+        if palette_changed
+        {
+            self.theme = gui_style::palette_theme(self.palette());
+        }
+
+        if self.settings != previous_settings
+        {
+            self.apply_settings();
+            self.save_settings();
+        }
+        else if palette_changed
+        {
+            self.apply_settings();
+        }
+        task
+    }
+
+    /// Handles the given message of a finished load and remembers the file the data
+    /// was loaded from as the last opened file.
+    fn finish_loading(&mut self, message: GUIMessage) -> Task<GUIMessage>
+    {
+        let previous_source = self.data_manager.lock().unwrap().source_path.clone();
+        let task = self.handle_message(message);
+
+        let source_path = self.data_manager.lock().unwrap().source_path.clone();
+        if let Some(path) = source_path.filter(|path| Some(path) != previous_source.as_ref())
+        {
+            self.settings.add_recent_file(&path);
+            self.menu_view.check_recent_files(&self.settings.recent_files);
+            self.save_settings();
+        }
+        task
+    }
+
+    fn handle_message(&mut self, message: GUIMessage) -> Task<GUIMessage>
+    {
+        // CLOSING AND UNSAVED EDITS
+        match message
+        {
+            GUIMessage::CloseRequested(id) => {return self.close_requested(id);}
+            GUIMessage::ConfirmPendingAction(save) => {return self.confirm_pending_action(save);}
+            GUIMessage::CancelPendingAction => {self.pending_action = None; return Task::none();}
+            GUIMessage::FileSelected(_) | GUIMessage::FileDropped(_)
+                if self.data_manager.lock().unwrap().dirty =>
+                {
+                    return self.hold_load(message);
+                }
+            _ => {}
+        }
+
+        self.route_message(message)
+    }
+
+    /// Passes the given message on to the command palette or the view that handles it.
+    fn route_message(&mut self, message: GUIMessage) -> Task<GUIMessage>
+    {
+
+        // COMMAND PALETTE AND SHORTCUTS
+        match message
+        {
+            GUIMessage::OpenCommandPalette | GUIMessage::CloseCommandPalette
+            | GUIMessage::CommandPaletteInputChanged(_) | GUIMessage::RunCommand(_)
+            | GUIMessage::RunSelectedCommand =>
+                {
+                    let (task, command) = self.command_palette.update(message);
+                    return match command
+                    {
+                        Some(command) => {Task::batch([task, self.handle_message(command)])}
+                        None => {task}
+                    };
+                }
+            GUIMessage::MoveCommandSelection(_) if self.command_palette.is_open =>
+                {
+                    return self.command_palette.update(message).0;
+                }
+            GUIMessage::GoBack => {return self.go_back();}
+            _ => {}
+        }
+
+        // BACKGROUND LOADING (keeps going when the list view is left)
+        if let GUIMessage::LoadingUpdate(_) | GUIMessage::WatchedFileChanged(_) = message
+        {
+            return self.list_view.update(message, &self.data_manager);
+        }
+
         // RETURN TO MENU
         if let GUIMessage::ReturnToView(view_name) = message
         {
             if view_name == MenuView::view_title()
             {
                 self.cur_view = GUIViewType::MenuView;
+                self.menu_view.check_recent_files(&self.settings.recent_files);
             }
         }
 
@@ -72,26 +291,211 @@ impl MainGUI
                 _ if view_name == ListView::view_title() =>
                     {self.cur_view = GUIViewType::ListView},
 
+                _ if view_name == SettingsView::view_title() =>
+                    {self.cur_view = GUIViewType::SettingsView},
+
                 _ => {}
             }
         }
 
+        // SWITCH TO THE VIEW THAT HANDLES THE MESSAGE (when sent by a shortcut or command)
+        match message
+        {
+            GUIMessage::SelectFile | GUIMessage::FileSelected(_) | GUIMessage::FileDropped(_) | GUIMessage::SaveFile | GUIMessage::SaveData
+            | GUIMessage::OpenFilterView(_) | GUIMessage::ToggleTableView
+            | GUIMessage::StartAddDay | GUIMessage::CancelLoading | GUIMessage::CopyFiltered(_)
+            | GUIMessage::ExportData(_) =>
+                {
+                    self.cur_view = GUIViewType::ListView;
+                }
+            GUIMessage::ImportPalette | GUIMessage::PickDefaultDirectory =>
+                {
+                    self.cur_view = GUIViewType::SettingsView;
+                }
+            _ => {}
+        }
+
         // UPDATE CURRENT VIEW
         match self.cur_view
         {
             GUIViewType::ListView => {self.list_view.update(message, &self.data_manager)}
-            GUIViewType::MenuView => {self.menu_view.update(message)}
+            GUIViewType::MenuView => {self.menu_view.update(message, &mut self.settings)}
+            GUIViewType::SettingsView => {self.settings_view.update(message, &mut self.settings, &mut self.palettes)}
             _ => {Task::none()}
         }
     }
 
-    fn view(&self) -> Element<GUIMessage>
+    /// Leaves whatever the user is currently in: the command palette, the unsaved edits confirmation,
+    /// an edit or the current view.
+    fn go_back(&mut self) -> Task<GUIMessage>
     {
-        match self.cur_view
+        if self.command_palette.is_open
+        {
+            self.command_palette.is_open = false;
+            return Task::none();
+        }
+
+        if self.pending_action.is_some()
+        {
+            self.pending_action = None;
+            return Task::none();
+        }
+
+        let message = match self.cur_view
+        {
+            GUIViewType::ListView => {self.list_view.go_back_message()}
+            GUIViewType::SettingsView => {GUIMessage::ReturnToView(MenuView::view_title())}
+            _ => {return Task::none();}
+        };
+        self.handle_message(message)
+    }
+
+    /// Writes the settings to the config file. Failures are shown in the settings view.
+    fn save_settings(&mut self)
+    {
+        self.settings_view.config_error = config_handler::save_settings(&self.settings).err();
+    }
+
+    /// Remembers the current filters and writes the settings before the window is closed.
+    fn save_settings_on_close(&mut self)
+    {
+        self.settings.filters = self.list_view.saved_filters();
+        self.save_settings();
+    }
+
+    /// Closes the window right away unless there are unsaved edits,
+    /// in which case the user is asked what to do with them first.
+    fn close_requested(&mut self, id: window::Id) -> Task<GUIMessage>
+    {
+        if self.data_manager.lock().unwrap().dirty
+        {
+            self.pending_action = Some(PendingAction::Close(id));
+            return Task::none();
+        }
+        self.save_settings_on_close();
+        window::close(id)
+    }
+
+    /// Holds back the given load message until the user decided what to do with the unsaved
+    /// edits it would discard. The window receives one message per dropped file, so the
+    /// messages of the same drop are collected.
+    fn hold_load(&mut self, message: GUIMessage) -> Task<GUIMessage>
+    {
+        match &mut self.pending_action
+        {
+            Some(PendingAction::Load(messages)) => {messages.push(message);}
+            Some(PendingAction::Close(_)) => {}
+            None => {self.pending_action = Some(PendingAction::Load(vec![message]));}
+        }
+        Task::none()
+    }
+
+    /// Carries out the pending action, saving the data beforehand if wanted.
+    /// Does nothing if saving fails so that the edits are not lost.
+    fn confirm_pending_action(&mut self, save: bool) -> Task<GUIMessage>
+    {
+        let Some(action) = self.pending_action.take() else {return Task::none()};
+
+        let saved = if save {self.data_manager.lock().unwrap().save_data()} else {Ok(())};
+        if let Err(e) = saved
+        {
+            self.list_view.editor.error = Some(e);
+            return Task::none();
+        }
+
+        match action
+        {
+            PendingAction::Close(id) =>
+                {
+                    self.save_settings_on_close();
+                    window::close(id)
+                }
+            PendingAction::Load(messages) =>
+                {
+                    Task::batch(messages.into_iter().map(|message| self.route_message(message)))
+                }
+        }
+    }
+
+    fn view(&self) -> Element<'_, GUIMessage>
+    {
+        if let Some(action) = &self.pending_action
+        {
+            return unsaved_edits_confirmation(action);
+        }
+
+        let view = match self.cur_view
         {
             GUIViewType::ListView => {self.list_view.view(&self.data_manager)}
-            GUIViewType::MenuView => {self.menu_view.view()}
+            GUIViewType::MenuView => {self.menu_view.view(&self.settings, self.palette())}
+            GUIViewType::SettingsView => {self.settings_view.view(&self.settings, &self.palettes)}
             _ => {Column::new().into()}
-        }
+        };
+        self.command_palette.view(view, self.palette())
     }
-}
\ No newline at end of file
+}
+
+/// Maps the events that are handled regardless of the current view to their messages:
+/// Key presses to the messages of the commands bound to them
+/// (see [`command_palette_control::shortcut`]) and files dropped onto the window.
+fn global_event(event: Event, status: event::Status, _: window::Id) -> Option<GUIMessage>
+{
+    match event
+    {
+        Event::Keyboard(keyboard::Event::KeyPressed {key, modifiers, ..}) =>
+            {
+                command_palette_control::shortcut(&key, modifiers, status == event::Status::Captured)
+            }
+        Event::Window(window::Event::FileDropped(path)) =>
+            {
+                Some(GUIMessage::FileDropped(path.to_string_lossy().into_owned()))
+            }
+        _ => {None}
+    }
+}
+
+/// Whether the given message can change the settings or the palettes.
+/// Commands are checked as well, as they can carry any of these messages.
+fn can_change_settings(message: &GUIMessage) -> bool
+{
+    matches!(message,
+        GUIMessage::SelectTheme(_) | GUIMessage::ImportPalette | GUIMessage::FontSizeInputChanged(_)
+        | GUIMessage::DateFormatInputChanged(_) | GUIMessage::PickDefaultDirectory
+        | GUIMessage::ClearDefaultDirectory | GUIMessage::ToggleRestoreFilters(_)
+        | GUIMessage::ToggleWatchFile(_) | GUIMessage::ToggleReportStatistics(_)
+        | GUIMessage::ClearLastOpenedFile | GUIMessage::ToggleReopenLastFile(_)
+        | GUIMessage::ClearRecentFiles | GUIMessage::RemoveRecentFile(_)
+        | GUIMessage::RunCommand(_) | GUIMessage::RunSelectedCommand)
+}
+
+/// Asks the user whether to save the unsaved edits before carrying out the given action.
+fn unsaved_edits_confirmation<'a>(action: &PendingAction) -> Element<'a, GUIMessage>
+{
+    let (save_title, question) = match action
+    {
+        PendingAction::Close(_) => {("Save and quit", "There are unsaved edits. Save them before quitting?")}
+        PendingAction::Load(_) => {("Save and load", "There are unsaved edits. Save them before loading another file?")}
+    };
+
+    let buttons = Row::new()
+        .spacing(20)
+        .push(button(save_title).on_press(GUIMessage::ConfirmPendingAction(true))
+            .padding(10).style(gui_style::ButtonStyle::style))
+        .push(button("Discard changes").on_press(GUIMessage::ConfirmPendingAction(false))
+            .padding(10).style(gui_style::DeleteButtonStyle::style))
+        .push(button("Cancel").on_press(GUIMessage::CancelPendingAction)
+            .padding(10).style(gui_style::ButtonStyle::style));
+
+    let column = Column::new()
+        .spacing(30)
+        .align_x(Alignment::Center)
+        .push(Text::new(question).size(22))
+        .push(buttons);
+
+    Container::new(column)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+}
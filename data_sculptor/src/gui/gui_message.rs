@@ -1,7 +1,11 @@
 //! Defines the [`GUIMessage`] enum
 
-use iced::window;
-use crate::core::filters::filter::{FilterID, FilterType};
+use iced::{Point, window};
+use iced::widget::scrollable::{AbsoluteOffset, Viewport};
+use crate::core::data_manager::LoadingEvent;
+use crate::core::filters::filter::{FilterID, FilterType, KeyFilterMode, KeySelection};
+use crate::file_io::exporter::ExportFormat;
+use crate::file_io::text_formats::TextFormat;
 
 /// Enum holding all types of messages to be received and handled by the gui application.
 /// Sorted by the view module they belong to.
@@ -11,18 +15,101 @@ pub enum GUIMessage
     // GENERAL
     ReturnToView(&'static str),
     OpenView(&'static str),
+    CloseRequested(window::Id),
+    ConfirmPendingAction(bool),
+    CancelPendingAction,
+    Undo,
+    Redo,
+    GoBack,
+    FocusSearch,
+
+    // COMMAND PALETTE
+    OpenCommandPalette,
+    CloseCommandPalette,
+    CommandPaletteInputChanged(String),
+    MoveCommandSelection(isize),
+    RunCommand(usize),
+    RunSelectedCommand,
+
+    // MENU VIEW
+    RemoveRecentFile(String),
 
     // LIST VIEW
     SelectFile,
     SaveFile,
     FileSelected(String),
+    FileDropped(String),
+    LoadingUpdate(LoadingEvent),
+    WatchedFileChanged(String),
+    CancelLoading,
     OpenFilterView(FilterType),
+    ToggleTableView,
+    ListScrolled(Viewport),
+    CopyFiltered(TextFormat),
+    ExportData(ExportFormat),
+
+    // DAY VIEW
+    OpenDay(String),
+    CloseDay,
+    ShowPreviousDay,
+    ShowNextDay,
+    CopyDayAsJson,
+    CopyValue(String),
+
+    // SEARCH
+    SearchInputChanged(String),
+    ClearSearch,
+    JumpToDay(String),
+
+    // EDITING
+    StartEditValue(String, String),
+    StartAddKey(String),
+    StartAddDay,
+    EditKeyInputChanged(String),
+    EditValueInputChanged(String),
+    ConfirmEdit,
+    CancelEdit,
+    DeleteKey(String, String),
+    DeleteDay(String),
+    SaveData,
+
+    // TABLE VIEW
+    SortTableBy(Option<String>),
+    StartColumnResize(String),
+    ColumnResizeMoved(Point),
+    EndColumnResize,
+    TableScrolled(AbsoluteOffset),
+    TableDatesScrolled(AbsoluteOffset),
 
     // FILTER VIEW
     ClickFilter(FilterID),
     FilterInputChanged(String),
+    AcceptCompletion(String),
     AddFilter,
+    UpdateFilter,
     DeleteFilter(FilterID),
+    MacroNameInputChanged(String),
+    MacroInputChanged(String),
+    DefineMacro,
+    ClickMacro(String),
+    DeleteMacro(String),
+    SetKeyFilterMode(KeyFilterMode),
+    ToggleChecklistKey(String),
+    AddKeySelection(KeySelection),
+
+    // SETTINGS VIEW
+    SelectTheme(String),
+    ImportPalette,
+    FontSizeInputChanged(String),
+    DateFormatInputChanged(String),
+    PickDefaultDirectory,
+    ClearDefaultDirectory,
+    ToggleRestoreFilters(bool),
+    ToggleWatchFile(bool),
+    ToggleReportStatistics(bool),
+    ToggleReopenLastFile(bool),
+    ClearRecentFiles,
+    ClearLastOpenedFile,
 
     LatestWindowId(Option<window::Id>),
 }
\ No newline at end of file
@@ -3,188 +3,255 @@
 use iced::{Border, Color, Shadow, Theme, Vector};
 use iced::Background;
 use iced::border::Radius;
+use iced::theme;
+use iced::theme::palette::{Extended, Pair};
 use iced::widget::{button, container};
+use crate::core::filters::expression_parser::HighlightKind;
+use crate::core::palette::Palette;
+
+// -----THEME-----
+
+/// Builds the iced [`Theme`] that widget styles read the colors of the given palette from.
+pub fn palette_theme(palette: &Palette) -> Theme
+{
+    let iced_palette = theme::Palette
+    {
+        background: palette.background,
+        text: palette.text,
+        primary: palette.primary,
+        success: palette.date,
+        danger: palette.danger
+    };
+
+    let palette = palette.clone();
+    Theme::custom_with_fn(palette.name.clone(), iced_palette, move |iced_palette|
+        {
+            let mut extended = Extended::generate(iced_palette);
+            extended.background.weak = Pair::new(palette.surface, palette.text);
+            extended.primary.base = Pair::new(palette.primary, palette.button_text);
+            extended.secondary.base = Pair::new(palette.secondary, palette.button_text);
+            extended.danger.base = Pair::new(palette.danger, palette.button_text);
+            extended
+        })
+}
 
 // -----BUTTONS-----
+// All button colors come from the extended palette of the theme, see [`palette_theme`].
+
 pub struct ButtonStyle;
 impl ButtonStyle
 {
-    pub fn style(_: &Theme, status: button::Status) -> button::Style
+    pub fn style(theme: &Theme, status: button::Status) -> button::Style
     {
+        let palette = theme.extended_palette();
         match status
         {
-            button::Status::Active => {ButtonStyle::active()}
-            button::Status::Hovered => {ButtonStyle::hovered()}
-            button::Status::Pressed => {ButtonStyle::pressed()}
+            button::Status::Active => {ButtonStyle::active(palette)}
+            button::Status::Hovered => {shaded(ButtonStyle::active(palette), 0.1, palette.is_dark)}
+            button::Status::Pressed => {shaded(ButtonStyle::active(palette), 0.2, palette.is_dark)}
             button::Status::Disabled => {Default::default()}
         }
     }
 
-    fn active() -> button::Style
+    fn active(palette: &Extended) -> button::Style
     {
         button::Style
         {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.6, 0.3))),
-            text_color: Color::WHITE,
+            background: Some(Background::Color(palette.primary.base.color)),
+            text_color: palette.primary.base.text,
             border: Border
             {
-                color: Color::from_rgb(0.1, 0.4, 0.25),
+                color: palette.primary.base.color,
                 width: 0.0,
                 radius: Radius::from(2),
             },
             shadow: Shadow
             {
-                color: Color::from_rgb(0.12, 0.15, 0.12),
+                color: shadow_color(),
                 offset: Vector::new(1.0, 1.0),
                 blur_radius: 8.0,
             },
             ..Default::default()
         }
-
-    }
-
-    fn hovered() -> button::Style
-    {
-        button::Style
-        {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.7, 0.35))),
-            ..ButtonStyle::active()
-        }
-    }
-
-    fn pressed() -> button::Style
-    {
-        button::Style
-        {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.8, 0.45))),
-            ..ButtonStyle::active()
-        }
     }
 }
 
 pub struct FilterButtonStyle;
 impl FilterButtonStyle
 {
-    pub fn style(_: &Theme, status: button::Status) -> button::Style
+    pub fn style(theme: &Theme, status: button::Status) -> button::Style
     {
+        let palette = theme.extended_palette();
         match status
         {
-            button::Status::Active => {FilterButtonStyle::active()}
-            button::Status::Hovered => {FilterButtonStyle::hovered()}
-            button::Status::Pressed => {FilterButtonStyle::pressed()}
+            button::Status::Active => {FilterButtonStyle::active(palette)}
+            button::Status::Hovered => {shaded(FilterButtonStyle::active(palette), 0.1, palette.is_dark)}
+            button::Status::Pressed => {shaded(FilterButtonStyle::active(palette), 0.2, palette.is_dark)}
             button::Status::Disabled => {Default::default()}
         }
     }
 
-    fn active() -> button::Style
+    fn active(palette: &Extended) -> button::Style
     {
         button::Style
         {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.4, 0.2))),
-            text_color: Color::WHITE,
+            background: Some(Background::Color(palette.secondary.base.color)),
+            text_color: palette.secondary.base.text,
             border: Border
             {
-                color: Color::from_rgb(0.1, 0.4, 0.25),
+                color: palette.secondary.base.color,
                 width: 0.0,
                 radius: Radius::from(2),
             },
             shadow: Shadow
             {
-                color: Color::from_rgb(0.08, 0.11, 0.08),
+                color: shadow_color(),
                 offset: Vector::new(2.0, 2.0),
                 blur_radius: 12.0,
             },
             ..Default::default()
         }
     }
-
-    fn hovered() -> button::Style
-    {
-        button::Style
-        {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.5, 0.25))),
-            ..FilterButtonStyle::active()
-        }
-    }
-
-    fn pressed() -> button::Style
-    {
-        button::Style
-        {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.6, 0.35))),
-            ..FilterButtonStyle::active()
-        }
-    }
 }
 
 pub struct DeleteButtonStyle;
 impl DeleteButtonStyle
 {
-    pub fn style(_: &Theme, status: button::Status) -> button::Style
+    pub fn style(theme: &Theme, status: button::Status) -> button::Style
     {
+        let palette = theme.extended_palette();
         match status
         {
-            button::Status::Active => {DeleteButtonStyle::active()}
-            button::Status::Hovered => {DeleteButtonStyle::hovered()}
-            button::Status::Pressed => {DeleteButtonStyle::pressed()}
+            button::Status::Active => {DeleteButtonStyle::active(palette)}
+            button::Status::Hovered => {shaded(DeleteButtonStyle::active(palette), 0.1, palette.is_dark)}
+            button::Status::Pressed => {shaded(DeleteButtonStyle::active(palette), 0.2, palette.is_dark)}
             button::Status::Disabled => {Default::default()}
         }
     }
 
-    fn active() -> button::Style
+    fn active(palette: &Extended) -> button::Style
     {
         button::Style
         {
-            background: Some(Background::Color(Color::from_rgb(0.6, 0.25, 0.25))),
-            text_color: Color::WHITE,
+            background: Some(Background::Color(palette.danger.base.color)),
+            text_color: palette.danger.base.text,
             border: Border
             {
-                color: Color::from_rgb(0.1, 0.4, 0.25),
+                color: palette.danger.base.color,
                 width: 0.0,
                 radius: Radius::from(2),
             },
             shadow: Shadow
             {
-                color: Color::from_rgb(0.12, 0.1, 0.06),
+                color: shadow_color(),
                 offset: Vector::new(2.0, 2.0),
                 blur_radius: 8.0,
             },
             ..Default::default()
         }
     }
+}
+
+/// Button that looks like plain text until it is hovered. Used for clickable data values.
+pub struct TextButtonStyle;
+impl TextButtonStyle
+{
+    pub fn style(theme: &Theme, status: button::Status) -> button::Style
+    {
+        let palette = theme.extended_palette();
+        match status
+        {
+            button::Status::Active => {TextButtonStyle::active(palette)}
+            button::Status::Hovered => {TextButtonStyle::hovered(palette)}
+            button::Status::Pressed => {TextButtonStyle::hovered(palette)}
+            button::Status::Disabled => {TextButtonStyle::active(palette)}
+        }
+    }
 
-    fn hovered() -> button::Style
+    fn active(palette: &Extended) -> button::Style
     {
         button::Style
         {
-            background: Some(Background::Color(Color::from_rgb(0.75, 0.25, 0.25))),
-            ..DeleteButtonStyle::active()
+            background: None,
+            text_color: palette.background.base.text,
+            ..Default::default()
         }
     }
 
-    fn pressed() -> button::Style
+    fn hovered(palette: &Extended) -> button::Style
     {
         button::Style
         {
-            background: Some(Background::Color(Color::from_rgb(0.8, 0.25, 0.25))),
-            ..DeleteButtonStyle::active()
+            background: Some(Background::Color(palette.background.weak.color)),
+            ..TextButtonStyle::active(palette)
         }
     }
 }
 
+/// Lightens the background of the given style on dark themes and darkens it on light ones.
+fn shaded(style: button::Style, amount: f32, is_dark: bool) -> button::Style
+{
+    let Some(Background::Color(color)) = style.background else {return style};
+    let target = if is_dark {Color::WHITE} else {Color::BLACK};
+
+    button::Style
+    {
+        background: Some(Background::Color(mix(color, target, amount))),
+        ..style
+    }
+}
+
+fn mix(a: Color, b: Color, amount: f32) -> Color
+{
+    Color
+    {
+        r: a.r + (b.r - a.r) * amount,
+        g: a.g + (b.g - a.g) * amount,
+        b: a.b + (b.b - a.b) * amount,
+        a: a.a
+    }
+}
+
+fn shadow_color() -> Color
+{
+    Color::from_rgba(0.0, 0.0, 0.0, 0.5)
+}
+
 // -----CONTAINERS-----
-pub fn container_bar_style(_: &Theme) -> container::Style
+pub fn container_bar_style(theme: &Theme) -> container::Style
 {
     container::Style
     {
-        background: Some(Background::Color(Color::from_rgb(0.2, 0.22, 0.23))),
+        background: Some(Background::Color(theme.extended_palette().background.weak.color)),
         border: Default::default(),
         text_color: None,
         shadow: Shadow
         {
-            color: Color::from_rgb(0.11, 0.11, 0.11),
+            color: shadow_color(),
             offset: Vector::new(0.0, 1.0),
             blur_radius: 10.0,
         },
     }
 }
+
+// -----TEXT-----
+/// Color of a piece of a filter expression in the filter input.
+pub fn filter_highlight_color(kind: HighlightKind, palette: &Palette) -> Color
+{
+    match kind
+    {
+        HighlightKind::Command => {palette.value}
+        HighlightKind::QuotedString => {palette.string}
+        HighlightKind::Operator => {palette.operator}
+        HighlightKind::Macro => {palette.date}
+        HighlightKind::Literal => {palette.operator}
+        HighlightKind::Parenthesis => {palette.text}
+        HighlightKind::MismatchedParenthesis | HighlightKind::Invalid => {palette.error}
+    }
+}
+
+/// Background of the parts of the data that were matched by a filter.
+pub fn match_highlight_background(palette: &Palette) -> Color
+{
+    Color {a: 0.35, ..palette.string}
+}
@@ -0,0 +1,104 @@
+//! Module implementing the undo/redo history of the GUI.
+//!
+//! Every change the user makes is recorded as the command that reverts it. Undoing applies
+//! that command, which in turn yields the command to redo the change, and so on.
+
+use std::collections::VecDeque;
+use crate::core::data_edit::DataEdit;
+use crate::core::filters::filter::FilterType;
+use crate::gui::views::filter::filter_view_control::FilterChange;
+
+/// A change that can be undone or redone.
+#[derive(Clone)]
+pub enum HistoryCommand
+{
+    /// A change to the filters of the [`FilterView`](crate::gui::views::filter::filter_view_control::FilterView)
+    /// of the given [`FilterType`].
+    Filter(FilterType, FilterChange),
+
+    /// A change to the data of the [`DataManager`](crate::core::data_manager::DataManager).
+    Data(DataEdit)
+}
+
+/// Bounded undo and redo stacks of commands.
+pub struct History<C>
+{
+    undo_stack: VecDeque<C>,
+    redo_stack: Vec<C>,
+
+    /// Maximum number of commands that can be undone. The oldest ones are dropped first.
+    capacity: usize
+}
+
+impl<C> History<C>
+{
+    pub fn new(capacity: usize) -> Self
+    {
+        Self
+        {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            capacity
+        }
+    }
+
+    /// Records the command reverting a new change made by the user.
+    /// Clears everything that could be redone, since it no longer follows from the current state.
+    pub fn record(&mut self, inverse: C)
+    {
+        self.redo_stack.clear();
+        self.push_undo(inverse);
+    }
+
+    /// Takes the command reverting the latest change.
+    /// The command that reverts applying it should be passed back through [`History::undone`].
+    pub fn undo(&mut self) -> Option<C>
+    {
+        self.undo_stack.pop_back()
+    }
+
+    /// Stores the command that redoes a change reverted through [`History::undo`].
+    pub fn undone(&mut self, redo: C)
+    {
+        self.redo_stack.push(redo);
+    }
+
+    /// Takes the command redoing the latest undone change.
+    /// The command that reverts applying it should be passed back through [`History::redone`].
+    pub fn redo(&mut self) -> Option<C>
+    {
+        self.redo_stack.pop()
+    }
+
+    /// Stores the command that reverts a change redone through [`History::redo`].
+    pub fn redone(&mut self, inverse: C)
+    {
+        self.push_undo(inverse);
+    }
+
+    pub fn can_undo(&self) -> bool
+    {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool
+    {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Only keeps the commands for which the given function returns true.
+    pub fn retain(&mut self, mut keep: impl FnMut(&C) -> bool)
+    {
+        self.undo_stack.retain(&mut keep);
+        self.redo_stack.retain(keep);
+    }
+
+    fn push_undo(&mut self, command: C)
+    {
+        self.undo_stack.push_back(command);
+        while self.undo_stack.len() > self.capacity
+        {
+            self.undo_stack.pop_front();
+        }
+    }
+}
@@ -4,4 +4,5 @@ pub mod gui_core;
 pub mod views;
 pub mod gui_util;
 pub mod gui_message;
+pub mod history;
 mod gui_style;
\ No newline at end of file
@@ -0,0 +1,303 @@
+//! Module implementing the control functions for the [`CommandPalette`]
+
+use iced::keyboard::{Key, Modifiers};
+use iced::keyboard::key::Named;
+use iced::Task;
+use iced::widget::text_input;
+use crate::core::filters::filter::{FilterType, KeyFilterMode, KeySelection};
+use crate::file_io::exporter::ExportFormat;
+use crate::file_io::text_formats::TextFormat;
+use crate::gui::gui_message::GUIMessage;
+use crate::gui::views::list::list_view_control::ListView;
+use crate::gui::views::menu::menu_view_control::MenuView;
+use crate::gui::views::settings::settings_view_control::SettingsView;
+
+/// Maximum number of commands shown at once.
+pub const MAX_SHOWN_COMMANDS: usize = 12;
+
+/// An action of the gui that can be run from the [`CommandPalette`] by its name.
+#[derive(Debug, Clone)]
+pub struct Command
+{
+    pub name: &'static str,
+    pub message: GUIMessage,
+    pub binding: Option<KeyBinding>
+}
+
+/// A key combination that runs a [`Command`] from anywhere in the gui.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBinding
+{
+    pub key: Key,
+
+    /// Whether Ctrl (Cmd on macOS) has to be held.
+    pub command: bool,
+    pub shift: bool
+}
+
+impl KeyBinding
+{
+    fn ctrl(character: &str) -> Self
+    {
+        KeyBinding {key: Key::Character(character.into()), command: true, shift: false}
+    }
+
+    fn ctrl_shift(character: &str) -> Self
+    {
+        KeyBinding {key: Key::Character(character.into()), command: true, shift: true}
+    }
+
+    fn named(key: Named) -> Self
+    {
+        KeyBinding {key: Key::Named(key), command: false, shift: false}
+    }
+
+    /// Whether the given key press triggers this binding. Characters are compared
+    /// case-insensitively, since holding shift changes them.
+    pub fn matches(&self, key: &Key, modifiers: Modifiers) -> bool
+    {
+        if self.command != modifiers.command() || self.shift != modifiers.shift()
+        {
+            return false;
+        }
+
+        match (&self.key, key)
+        {
+            (Key::Character(a), Key::Character(b)) => {a.to_lowercase() == b.to_lowercase()}
+            (a, b) => {a == b}
+        }
+    }
+
+    /// Human readable form like "Ctrl+Shift+S".
+    pub fn label(&self) -> String
+    {
+        let mut label = String::new();
+        if self.command
+        {
+            label += if cfg!(target_os = "macos") {"Cmd+"} else {"Ctrl+"};
+        }
+        if self.shift
+        {
+            label += "Shift+";
+        }
+
+        match &self.key
+        {
+            Key::Character(character) => {label += &character.to_uppercase();}
+            Key::Named(named) => {label += &format!("{:?}", named);}
+            Key::Unidentified => {}
+        }
+        label
+    }
+}
+
+impl Command
+{
+    fn new(name: &'static str, message: GUIMessage) -> Self
+    {
+        Command {name, message, binding: None}
+    }
+
+    fn bound(self, binding: KeyBinding) -> Self
+    {
+        Command {binding: Some(binding), ..self}
+    }
+}
+
+/// All commands in the order they are listed in when nothing is searched.
+pub fn commands() -> Vec<Command>
+{
+    vec!
+    [
+        // GENERAL
+        Command::new("Open file", GUIMessage::SelectFile).bound(KeyBinding::ctrl("o")),
+        Command::new("Save edits", GUIMessage::SaveData).bound(KeyBinding::ctrl("s")),
+        Command::new("Save filtered data as", GUIMessage::SaveFile).bound(KeyBinding::ctrl_shift("s")),
+        Command::new("Undo", GUIMessage::Undo).bound(KeyBinding::ctrl("z")),
+        Command::new("Redo", GUIMessage::Redo).bound(KeyBinding::ctrl_shift("z")),
+        Command::new("Go back", GUIMessage::GoBack).bound(KeyBinding::named(Named::Escape)),
+        Command::new("Focus search", GUIMessage::FocusSearch).bound(KeyBinding::ctrl("f")),
+        Command::new("Show command palette", GUIMessage::OpenCommandPalette).bound(KeyBinding::ctrl("p")),
+
+        // VIEWS
+        Command::new("Open list view", GUIMessage::OpenView(ListView::view_title())),
+        Command::new("Open settings", GUIMessage::OpenView(SettingsView::view_title())),
+        Command::new("Return to menu", GUIMessage::ReturnToView(MenuView::view_title())),
+        Command::new("Toggle table view", GUIMessage::ToggleTableView).bound(KeyBinding::ctrl("t")),
+        Command::new("Open date filters", GUIMessage::OpenFilterView(FilterType::Date))
+            .bound(KeyBinding::ctrl("1")),
+        Command::new("Open key filters", GUIMessage::OpenFilterView(FilterType::Key))
+            .bound(KeyBinding::ctrl("2")),
+        Command::new("Open value filters", GUIMessage::OpenFilterView(FilterType::Value))
+            .bound(KeyBinding::ctrl("3")),
+
+        // LIST VIEW
+        Command::new("Add day", GUIMessage::StartAddDay),
+        Command::new("Cancel loading", GUIMessage::CancelLoading),
+        Command::new("Copy filtered data as JSON", GUIMessage::CopyFiltered(TextFormat::Json)),
+        Command::new("Copy filtered data as CSV", GUIMessage::CopyFiltered(TextFormat::Csv)),
+        Command::new("Copy filtered data as Markdown table", GUIMessage::CopyFiltered(TextFormat::Markdown)),
+        Command::new("Copy filtered data as plain text", GUIMessage::CopyFiltered(TextFormat::PlainText)),
+        Command::new("Export Markdown report", GUIMessage::ExportData(ExportFormat::Markdown)),
+        Command::new("Export HTML report", GUIMessage::ExportData(ExportFormat::Html)),
+
+        // FILTER VIEW
+        Command::new("Add filter", GUIMessage::AddFilter).bound(KeyBinding::named(Named::Enter)),
+        Command::new("Update filter", GUIMessage::UpdateFilter),
+        Command::new("Define macro", GUIMessage::DefineMacro),
+        Command::new("Keep keys matching any key filter", GUIMessage::SetKeyFilterMode(KeyFilterMode::Any)),
+        Command::new("Keep keys matching all key filters", GUIMessage::SetKeyFilterMode(KeyFilterMode::All)),
+        Command::new("Include checked keys", GUIMessage::AddKeySelection(KeySelection::Include)),
+        Command::new("Exclude checked keys", GUIMessage::AddKeySelection(KeySelection::Exclude)),
+
+        // SETTINGS VIEW
+        Command::new("Import palette", GUIMessage::ImportPalette),
+        Command::new("Choose default directory", GUIMessage::PickDefaultDirectory),
+    ]
+}
+
+/// Maps a key press to the message of the [`Command`] bound to it.
+/// Key presses already handled by a widget (like typing into a text input) only trigger
+/// bindings that hold Ctrl, so that typing is never interrupted.
+/// Up and down move the selection of the [`CommandPalette`].
+pub fn shortcut(key: &Key, modifiers: Modifiers, captured: bool) -> Option<GUIMessage>
+{
+    if !captured && !modifiers.command()
+    {
+        match key
+        {
+            Key::Named(Named::ArrowUp) => {return Some(GUIMessage::MoveCommandSelection(-1));}
+            Key::Named(Named::ArrowDown) => {return Some(GUIMessage::MoveCommandSelection(1));}
+            _ => {}
+        }
+    }
+
+    commands().into_iter()
+        .filter_map(|command| Some((command.binding?, command.message)))
+        .find(|(binding, _)| (binding.command || !captured) && binding.matches(key, modifiers))
+        .map(|(_, message)| message)
+}
+
+/// Lets the user run any [`Command`] by typing a part of its name.
+pub struct CommandPalette
+{
+    pub is_open: bool,
+    pub(crate) query: String,
+
+    /// Index into the filtered commands.
+    pub(crate) selected: usize,
+    pub(crate) input_id: text_input::Id
+}
+
+impl Default for CommandPalette
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            is_open: false,
+            query: String::new(),
+            selected: 0,
+            input_id: text_input::Id::unique()
+        }
+    }
+}
+
+/// Implementation of the control functions for the command palette
+impl CommandPalette
+{
+    // UPDATE
+    /// Returns the message of the command the user chose to run, if any.
+    pub fn update(&mut self, message: GUIMessage) -> (Task<GUIMessage>, Option<GUIMessage>)
+    {
+        match message
+        {
+            GUIMessage::OpenCommandPalette => {return (self.open(), None);}
+            GUIMessage::CloseCommandPalette => {self.is_open = false;}
+            GUIMessage::CommandPaletteInputChanged(query) =>
+                {
+                    self.query = query;
+                    self.selected = 0;
+                }
+            GUIMessage::MoveCommandSelection(step) =>
+                {
+                    let count = self.filtered_commands().len();
+                    if count > 0
+                    {
+                        self.selected = (self.selected as isize + step).rem_euclid(count as isize) as usize;
+                    }
+                }
+            GUIMessage::RunCommand(index) => {return (Task::none(), self.run(index));}
+            GUIMessage::RunSelectedCommand => {return (Task::none(), self.run(self.selected));}
+            _ => {}
+        }
+
+        (Task::none(), None)
+    }
+
+    fn open(&mut self) -> Task<GUIMessage>
+    {
+        self.is_open = true;
+        self.query.clear();
+        self.selected = 0;
+        text_input::focus(self.input_id.clone())
+    }
+
+    /// Closes the palette and returns the message of the filtered command at the given index.
+    fn run(&mut self, index: usize) -> Option<GUIMessage>
+    {
+        let command = self.filtered_commands().into_iter().nth(index)?;
+        self.is_open = false;
+        Some(command.message)
+    }
+
+    /// The commands matching the query, best matches first.
+    pub(crate) fn filtered_commands(&self) -> Vec<Command>
+    {
+        filter_commands(commands(), &self.query)
+    }
+}
+
+/// Keeps the commands whose names fuzzily match the query and sorts them by how well they do.
+/// Commands that match equally well keep their order.
+pub fn filter_commands(commands: Vec<Command>, query: &str) -> Vec<Command>
+{
+    let mut scored: Vec<(i32, Command)> = commands.into_iter()
+        .filter_map(|command| Some((fuzzy_score(query, command.name)?, command)))
+        .collect();
+
+    scored.sort_by_key(|(score, _)| -score);
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+/// Scores how well the query matches the text. The query matches if all of its characters
+/// (ignoring case and whitespace) appear in the text in the same order.
+/// Characters that follow each other directly or start a word score higher.
+/// Returns None if the query does not match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32>
+{
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace())
+    {
+        let index = position + text[position..].iter().position(|c| *c == query_char)?;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index)
+        {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric()
+        {
+            score += 3;
+        }
+
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
@@ -0,0 +1,74 @@
+//! Module implementing the display functions for the [`CommandPalette`]
+
+use iced::{Alignment, Element, Length};
+use iced::widget::{center, mouse_area, opaque, stack, text_input, Button, Column, Container, Row, Space, Text};
+use crate::gui::gui_message::GUIMessage;
+use crate::core::palette::Palette;
+use crate::gui::gui_style;
+use crate::gui::views::command_palette::command_palette_control::{CommandPalette, MAX_SHOWN_COMMANDS};
+
+/// Implementation of the display functions for the command palette
+impl CommandPalette
+{
+    /// Shows the palette on top of the given view if it is open.
+    /// Clicking next to the palette closes it.
+    pub fn view<'a>(&'a self, base: Element<'a, GUIMessage>, palette: &Palette) -> Element<'a, GUIMessage>
+    {
+        if !self.is_open
+        {
+            return base;
+        }
+
+        let input = text_input("Type a command", &self.query)
+            .id(self.input_id.clone())
+            .on_input(GUIMessage::CommandPaletteInputChanged)
+            .on_submit(GUIMessage::RunSelectedCommand)
+            .padding(10)
+            .size(20);
+
+        let mut command_column = Column::new().spacing(4);
+        let commands = self.filtered_commands();
+        for (index, command) in commands.iter().enumerate().take(MAX_SHOWN_COMMANDS)
+        {
+            let binding = command.binding.as_ref().map(|binding| binding.label()).unwrap_or_default();
+            let row = Row::new()
+                .align_y(Alignment::Center)
+                .push(Text::new(command.name))
+                .push(Space::with_width(Length::Fill))
+                .push(Text::new(binding).size(14).color(palette.muted_text));
+
+            let style = if index == self.selected {gui_style::ButtonStyle::style}
+                else {gui_style::TextButtonStyle::style};
+
+            command_column = command_column.push
+            (
+                Button::new(row)
+                    .on_press(GUIMessage::RunCommand(index))
+                    .padding([5, 10])
+                    .width(Length::Fill)
+                    .style(style)
+            );
+        }
+
+        if commands.is_empty()
+        {
+            command_column = command_column.push(Text::new("No matching commands").color(palette.muted_text));
+        }
+
+        let palette_box = Container::new
+            (
+                Column::new()
+                    .spacing(10)
+                    .push(input)
+                    .push(command_column)
+            )
+            .padding(20)
+            .width(Length::Fixed(500.0))
+            .style(gui_style::container_bar_style);
+
+        let overlay = mouse_area(center(opaque(palette_box)).padding(40).align_y(Alignment::Start))
+            .on_press(GUIMessage::CloseCommandPalette);
+
+        stack![base, opaque(overlay)].into()
+    }
+}
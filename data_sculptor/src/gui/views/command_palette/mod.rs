@@ -0,0 +1,2 @@
+pub mod command_palette_control;
+mod command_palette_display;
@@ -0,0 +1,118 @@
+//! Module implementing the control functions for the [`DayView`]
+
+use iced::{clipboard, Task};
+use crate::core::data_containers::{DayDataParsed, EntryKey, DATE_FORMAT};
+use crate::file_io::data_writer;
+use crate::gui::gui_message::GUIMessage;
+use crate::core::palette::Palette;
+
+/// Shows every entry of a single day of the
+/// [`ListView`](crate::gui::views::list::list_view_control::ListView), including the ones
+/// hidden by the key filters, and lets the user step through the days.
+pub struct DayView
+{
+    /// Date of the shown day. The view is closed while there is none.
+    pub date: Option<String>,
+
+    /// Result of the last copy to the clipboard.
+    pub notice: Option<String>,
+
+    /// Format the dates are displayed in. See [`Settings`](crate::core::settings::Settings).
+    pub date_format: String,
+    pub palette: Palette
+}
+
+impl Default for DayView
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            date: None,
+            notice: None,
+            date_format: String::from(DATE_FORMAT),
+            palette: Palette::default()
+        }
+    }
+}
+
+/// Implementation of the control functions for the day view
+impl DayView
+{
+    // UPDATE
+    pub fn update(&mut self, message: GUIMessage, data: &[DayDataParsed]) -> Task<GUIMessage>
+    {
+        match message
+        {
+            GUIMessage::OpenDay(date) => {self.open(date)}
+            GUIMessage::CloseDay => {self.date = None; Task::none()}
+            GUIMessage::ShowPreviousDay => {self.step(data, -1)}
+            GUIMessage::ShowNextDay => {self.step(data, 1)}
+            GUIMessage::CopyDayAsJson => {self.copy_as_json(data)}
+            GUIMessage::CopyValue(key) => {self.copy_value(data, key)}
+            _ => {Task::none()}
+        }
+    }
+
+    pub fn is_open(&self) -> bool
+    {
+        self.date.is_some()
+    }
+
+    fn open(&mut self, date: String) -> Task<GUIMessage>
+    {
+        self.date = Some(date);
+        self.notice = None;
+        Task::none()
+    }
+
+    /// Index of the shown day in the given data. None if it does not exist (anymore).
+    pub fn day_index(&self, data: &[DayDataParsed]) -> Option<usize>
+    {
+        let date = self.date.as_ref()?;
+        data.iter().position(|day| &day.date.date_string == date)
+    }
+
+    /// Shows the day the given number of steps after the shown one in the given data.
+    /// Stays on the shown day when stepping past the first or last day.
+    fn step(&mut self, data: &[DayDataParsed], steps: isize) -> Task<GUIMessage>
+    {
+        let target = self.day_index(data).and_then(|index| index.checked_add_signed(steps));
+        if let Some(day) = target.and_then(|index| data.get(index))
+        {
+            self.date = Some(day.date.date_string.clone());
+            self.notice = None;
+        }
+        Task::none()
+    }
+
+    /// Writes the shown day to the clipboard in the format the data is loaded from.
+    fn copy_as_json(&mut self, data: &[DayDataParsed]) -> Task<GUIMessage>
+    {
+        let Some(index) = self.day_index(data) else {return Task::none()};
+
+        match data_writer::to_json(&data[index..=index])
+        {
+            Ok(json) =>
+                {
+                    self.notice = Some(String::from("Copied the day as JSON"));
+                    clipboard::write(json)
+                }
+            Err(e) =>
+                {
+                    self.notice = Some(format!("Could not copy the day: {}", e));
+                    Task::none()
+                }
+        }
+    }
+
+    /// Writes the value of the given key of the shown day to the clipboard.
+    fn copy_value(&mut self, data: &[DayDataParsed], key: String) -> Task<GUIMessage>
+    {
+        let Some(index) = self.day_index(data) else {return Task::none()};
+        let Some(value) = data[index].entries.get(&EntryKey {title: key.clone()}) else {return Task::none()};
+
+        self.notice = Some(format!("Copied the value of '{}'", key));
+        clipboard::write(value.string_value.clone())
+    }
+}
@@ -0,0 +1,134 @@
+//! Module implementing the display functions for the [`DayView`]
+
+use iced::{Alignment, Element, Length};
+use iced::widget::{button, Column, Container, Row, Scrollable, Space, Text};
+use iced::widget::text::Wrapping;
+use crate::core::data_containers::DayDataParsed;
+use crate::core::settings;
+use crate::gui::gui_message::GUIMessage;
+use crate::gui::gui_style;
+use crate::gui::views::day::day_view_control::DayView;
+
+const KEY_COLUMN_WIDTH: f32 = 250.0;
+
+/// Implementation of the display functions for the day view
+impl DayView
+{
+    /// Displays the shown day of the given data. `shown_entries` holds the indices of the
+    /// entries the list view shows, the others are marked as hidden by the filters.
+    /// It is None if the whole day is hidden by the filters.
+    pub fn view(&self, data: &[DayDataParsed], shown_entries: Option<&[usize]>) -> Element<'_, GUIMessage>
+    {
+        let day_index = self.day_index(data);
+        let has_previous = day_index.is_some_and(|index| index > 0);
+        let has_next = day_index.is_some_and(|index| index + 1 < data.len());
+
+        // TOP ROW
+        let top_row = Row::new()
+            .spacing(20)
+            .align_y(Alignment::Center)
+            .push(bar_button("Back to list", Some(GUIMessage::CloseDay)))
+            .push(Space::with_width(Length::Fill))
+            .push(bar_button("< Previous", has_previous.then_some(GUIMessage::ShowPreviousDay)))
+            .push(bar_button("Next >", has_next.then_some(GUIMessage::ShowNextDay)))
+            .push(Space::with_width(Length::Fill))
+            .push(bar_button("Copy day as JSON", day_index.map(|_| GUIMessage::CopyDayAsJson)));
+
+        let top_row_container = Container::new(top_row)
+            .padding(20)
+            .style(gui_style::container_bar_style);
+
+        let column = Column::new()
+            .push(Space::with_height(3))
+            .push(top_row_container);
+
+        let Some(day) = day_index.and_then(|index| data.get(index)) else
+        {
+            return column
+                .push(Space::with_height(20))
+                .push(Text::new("This day does not exist anymore.").color(self.palette.error))
+                .into();
+        };
+
+        // DAY
+        let mut day_column = Column::new()
+            .spacing(12)
+            .padding(20)
+            .push
+            (
+                Text::new(settings::format_date(&day.date, &self.date_format))
+                    .size(28)
+                    .color(self.palette.date)
+            );
+
+        let entry_count = match day.entries.len()
+        {
+            1 => {String::from("1 entry")}
+            count => {format!("{} entries", count)}
+        };
+        let filter_note = match shown_entries
+        {
+            None => {String::from(", the day is hidden by the filters")}
+            Some(shown) if shown.len() < day.entries.len() =>
+                {
+                    format!(", {} hidden by the key filters", day.entries.len() - shown.len())
+                }
+            Some(_) => {String::new()}
+        };
+        day_column = day_column.push(Text::new(entry_count + &filter_note).color(self.palette.muted_text));
+
+        if let Some(notice) = &self.notice
+        {
+            day_column = day_column.push(Text::new(notice.clone()).color(self.palette.muted_text));
+        }
+
+        // ENTRIES
+        for (entry_index, (key, value)) in day.entries.iter().enumerate()
+        {
+            let hidden = shown_entries.is_some_and(|shown| !shown.contains(&entry_index));
+            let key_color = if hidden {self.palette.muted_text} else {self.palette.text};
+
+            day_column = day_column.push
+            (
+                Row::new()
+                    .spacing(20)
+                    .push
+                    (
+                        Text::new(format!("{}:", key.title))
+                            .color(key_color)
+                            .width(Length::Fixed(KEY_COLUMN_WIDTH))
+                    )
+                    .push
+                    (
+                        Text::new(value.string_value.clone())
+                            .color(self.palette.value)
+                            .wrapping(Wrapping::WordOrGlyph)
+                            .width(Length::Fill)
+                    )
+                    .push
+                    (
+                        button(Text::new("Copy").size(14))
+                            .on_press(GUIMessage::CopyValue(key.title.clone()))
+                            .padding([3, 8])
+                            .style(gui_style::ButtonStyle::style)
+                    )
+            );
+        }
+
+        column
+            .push
+            (
+                Scrollable::new(Container::new(Container::new(day_column).max_width(1200)).center_x(Length::Fill))
+            )
+            .into()
+    }
+}
+
+fn bar_button(title: &str, on_press: Option<GUIMessage>) -> Element<'_, GUIMessage>
+{
+    button(title)
+        .on_press_maybe(on_press)
+        .padding(10)
+        .style(gui_style::ButtonStyle::style)
+        .into()
+}
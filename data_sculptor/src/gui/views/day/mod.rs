@@ -0,0 +1,2 @@
+pub mod day_view_control;
+mod day_view_display;
\ No newline at end of file
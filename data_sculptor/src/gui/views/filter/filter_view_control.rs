@@ -1,10 +1,51 @@
 //! Module implementing the control functions for the [`FilterView`]
 
 use iced::{Task};
+use iced::widget::text_input;
 use crate::gui::gui_message::GUIMessage;
-use crate::core::filters::filter::{FilterType, Filter, FilterID};
+use crate::core::palette::Palette;
+use crate::core::filters::filter::{FilterType, Filter, FilterID, KeyFilterMode, KeySelection};
 use crate::core::filters::{expression_parser};
-use indexmap::IndexMap;
+use crate::core::filters::expression_parser::{ExpressionError, FilterMacros};
+use crate::core::filters::filter_completion::Completion;
+use crate::gui::history::{History, HistoryCommand};
+use indexmap::{IndexMap, IndexSet};
+
+/// A change to the filters of a [`FilterView`]. See [`FilterView::apply_change`].
+#[derive(Clone)]
+pub enum FilterChange
+{
+    /// Inserts the filter at the given position.
+    Insert {index: usize, filter: Filter},
+
+    /// Removes the filter with the given id.
+    Remove {id: FilterID},
+
+    /// Replaces the filter with the given id with the given filter while keeping its position.
+    Replace {id: FilterID, filter: Filter},
+
+    /// Changes how the filters are combined. Only used by key filters.
+    SetKeyFilterMode {mode: KeyFilterMode},
+
+    /// Replaces the macro library together with the filters as they are resolved with it,
+    /// since their ids change with the definitions of the macros they use.
+    SetMacros {macros: FilterMacros, filters: IndexMap<FilterID, Filter>}
+}
+
+/// Preview of the filter that is currently being typed into a [`FilterView`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterPreview
+{
+    /// There is no input.
+    Empty,
+
+    /// The input is not a valid filter expression. Holds the reason.
+    Invalid(String),
+
+    /// The input is valid. Holds the number of days (or keys for key filters) that would
+    /// be kept if the filter was added and the number that is kept currently.
+    Valid {kept: usize, current: usize}
+}
 
 /// View for displaying and setting filters for the data list
 pub struct FilterView
@@ -12,6 +53,30 @@ pub struct FilterView
     pub filter_type: FilterType,
     pub filters: IndexMap<FilterID, Filter>, // index map to preserve order in list display
     pub(crate) input_value: String,
+
+    /// The filter that was clicked last and can be replaced with the input.
+    pub(crate) editing: Option<FilterID>,
+    pub preview: FilterPreview,
+    pub(crate) completions: Vec<Completion>,
+    pub(crate) input_id: text_input::Id,
+
+    // MACROS
+    pub macros: FilterMacros,
+    pub(crate) macro_name_input: String,
+    pub(crate) macro_input: String,
+    pub(crate) macro_error: Option<String>,
+
+    // KEY FILTERS
+    pub key_filter_mode: KeyFilterMode,
+
+    /// All keys of the loaded data, offered in the key checklist.
+    pub(crate) dataset_keys: Vec<String>,
+    pub(crate) checked_keys: IndexSet<String>,
+
+    pub palette: Palette,
+
+    /// Incremented every time the filters change.
+    pub(crate) revision: u64
 }
 
 impl From<FilterType> for FilterView
@@ -23,6 +88,19 @@ impl From<FilterType> for FilterView
             filter_type,
             filters: IndexMap::new(),
             input_value: String::new(),
+            editing: None,
+            preview: FilterPreview::Empty,
+            completions: Vec::new(),
+            input_id: text_input::Id::unique(),
+            macros: FilterMacros::new(),
+            macro_name_input: String::new(),
+            macro_input: String::new(),
+            macro_error: None,
+            key_filter_mode: KeyFilterMode::default(),
+            dataset_keys: Vec::new(),
+            checked_keys: IndexSet::new(),
+            palette: Palette::default(),
+            revision: 0
         }
     }
 }
@@ -31,58 +109,210 @@ impl From<FilterType> for FilterView
 impl FilterView
 {
     // UPDATE
-    pub fn update(&mut self, message: GUIMessage) -> Task<GUIMessage>
+    /// Changes made to the filters are recorded in the given [`History`].
+    pub fn update(&mut self, message: GUIMessage, history: &mut History<HistoryCommand>)
+        -> Task<GUIMessage>
     {
         match message
+        {
+            GUIMessage::AcceptCompletion(input) => {return self.accept_completion(input);}
+            GUIMessage::FocusSearch => {return text_input::focus(self.input_id.clone());}
+            _ => {}
+        }
+
+        let change = match message
         {
             GUIMessage::ClickFilter(filter_id) => {self.click_filter(filter_id)}
             GUIMessage::FilterInputChanged(input) => {self.update_input(input)}
             GUIMessage::AddFilter => {self.add_filter()}
-            GUIMessage::DeleteFilter(filter_id) => {self.delete_filter(filter_id)}
-            _ => {Task::none()}
+            GUIMessage::UpdateFilter => {self.update_filter()}
+            GUIMessage::DeleteFilter(filter_id) => {Some(FilterChange::Remove {id: filter_id})}
+            GUIMessage::SetKeyFilterMode(mode) =>
+                {
+                    (mode != self.key_filter_mode).then_some(FilterChange::SetKeyFilterMode {mode})
+                }
+            GUIMessage::ToggleChecklistKey(key) => {self.toggle_checklist_key(key)}
+            GUIMessage::AddKeySelection(selection) => {self.add_key_selection(selection)}
+
+            GUIMessage::MacroNameInputChanged(_) | GUIMessage::MacroInputChanged(_)
+            | GUIMessage::DefineMacro | GUIMessage::ClickMacro(_) | GUIMessage::DeleteMacro(_) =>
+                {
+                    self.update_macros(message)
+                }
+
+            _ => {None}
+        };
+
+        if let Some(inverse) = change.and_then(|change| self.apply_change(change))
+        {
+            history.record(HistoryCommand::Filter(self.filter_type.clone(), inverse));
         }
+
+        Task::none()
     }
 
-    fn click_filter(&mut self, filter_id: FilterID) -> Task<GUIMessage>
+    /// Applies the given [`FilterChange`] and returns the change that reverts it.
+    /// Returns None if the change could not be applied, which happens if the filter it refers
+    /// to does not exist or a replacement would duplicate another filter.
+    pub fn apply_change(&mut self, change: FilterChange) -> Option<FilterChange>
     {
-        self.input_value = self.filters.get(&filter_id).unwrap().title.clone();
-        Task::none()
+        let inverse = match change
+        {
+            FilterChange::Insert {index, filter} =>
+                {
+                    let id = FilterID::from(&filter.expression);
+                    if self.filters.contains_key(&id)
+                    {
+                        return None;
+                    }
+
+                    self.filters.shift_insert(index.min(self.filters.len()), id.clone(), filter);
+                    FilterChange::Remove {id}
+                }
+
+            FilterChange::Remove {id} =>
+                {
+                    let (index, _, filter) = self.filters.shift_remove_full(&id)?;
+                    if self.editing.as_ref() == Some(&id)
+                    {
+                        self.editing = None;
+                    }
+
+                    FilterChange::Insert {index, filter}
+                }
+
+            FilterChange::Replace {id, filter} =>
+                {
+                    let new_id = FilterID::from(&filter.expression);
+                    let index = self.filters.get_index_of(&id)?;
+                    if new_id != id && self.filters.contains_key(&new_id)
+                    {
+                        return None;
+                    }
+
+                    let (_, old_filter) = self.filters.shift_remove_index(index)?;
+                    self.filters.shift_insert(index, new_id.clone(), filter);
+                    if self.editing.as_ref() == Some(&id)
+                    {
+                        self.editing = Some(new_id.clone());
+                    }
+
+                    FilterChange::Replace {id: new_id, filter: old_filter}
+                }
+
+            FilterChange::SetKeyFilterMode {mode} =>
+                {
+                    let old_mode = std::mem::replace(&mut self.key_filter_mode, mode);
+                    FilterChange::SetKeyFilterMode {mode: old_mode}
+                }
+
+            FilterChange::SetMacros {macros, filters} =>
+                {
+                    let old_macros = std::mem::replace(&mut self.macros, macros);
+                    let old_filters = std::mem::replace(&mut self.filters, filters);
+                    self.editing = None;
+                    FilterChange::SetMacros {macros: old_macros, filters: old_filters}
+                }
+        };
+
+        self.revision += 1;
+        Some(inverse)
     }
 
-    fn delete_filter(&mut self, filter_id: FilterID) -> Task<GUIMessage>
+    /// Replaces the input with the given completion and continues typing at its end.
+    fn accept_completion(&mut self, input: String) -> Task<GUIMessage>
     {
-        self.filters.shift_remove(&filter_id);
-        Task::none()
+        self.update_input(input);
+        Task::batch
+        ([
+            text_input::focus(self.input_id.clone()),
+            text_input::move_cursor_to_end(self.input_id.clone())
+        ])
+    }
+
+    fn click_filter(&mut self, filter_id: FilterID) -> Option<FilterChange>
+    {
+        self.input_value = self.filters.get(&filter_id).unwrap().title.clone();
+        self.editing = Some(filter_id);
+        None
     }
 
-    fn update_input(&mut self, input: String) -> Task<GUIMessage>
+    fn update_input(&mut self, input: String) -> Option<FilterChange>
     {
         if input.len() < 500
         {
             self.input_value = input;
         }
 
-        Task::none()
+        None
     }
 
-    fn add_filter(&mut self) -> Task<GUIMessage>
+    fn add_filter(&mut self) -> Option<FilterChange>
     {
-        let parse_result = expression_parser::parse(&self.filter_type, self.input_value.as_str());
-        if let Some(filter_expression) = parse_result
+        let filter = self.parse_input().ok()?;
+        self.input_value.clear();
+
+        // Adding a filter that already exists only updates its title
+        let id = FilterID::from(&filter.expression);
+        if self.filters.contains_key(&id)
         {
-            self.filters.insert
-            (
-                FilterID::from(&filter_expression),
-                Filter
-                {
-                    title: self.input_value.clone(),
-                    expression: filter_expression,
-                    filter_type: self.filter_type.clone()
-                }
-            );
-            self.input_value.clear();
+            return Some(FilterChange::Replace {id, filter});
         }
+        Some(FilterChange::Insert {index: self.filters.len(), filter})
+    }
 
-        Task::none()
+    fn toggle_checklist_key(&mut self, key: String) -> Option<FilterChange>
+    {
+        if !self.checked_keys.shift_remove(&key)
+        {
+            self.checked_keys.insert(key);
+        }
+        None
+    }
+
+    /// Adds a filter that includes or excludes all keys checked in the key checklist.
+    fn add_key_selection(&mut self, selection: KeySelection) -> Option<FilterChange>
+    {
+        let keys: Vec<String> = self.checked_keys.drain(..).collect();
+        let filter = Filter::from_keys(&keys, selection)?;
+
+        let id = FilterID::from(&filter.expression);
+        if self.filters.contains_key(&id)
+        {
+            return None;
+        }
+        Some(FilterChange::Insert {index: self.filters.len(), filter})
+    }
+
+    /// Replaces the filter that was clicked last with the input.
+    fn update_filter(&mut self) -> Option<FilterChange>
+    {
+        let id = self.editing.clone()?;
+        let filter = self.parse_input().ok()?;
+        self.input_value.clear();
+        self.editing = None;
+
+        Some(FilterChange::Replace {id, filter})
+    }
+
+    pub(crate) fn parse_input(&self) -> Result<Filter, ExpressionError>
+    {
+        self.parse_filter(self.input_value.as_str())
+    }
+
+    /// Parses the given input into a [`Filter`] of this view, resolving its macros.
+    pub(crate) fn parse_filter(&self, input: &str) -> Result<Filter, ExpressionError>
+    {
+        let expression = expression_parser::parse_with_macros(&self.filter_type, input, &self.macros)?;
+        Ok
+        (
+            Filter
+            {
+                // Normalized so that filters written differently look the same
+                title: expression.to_string(),
+                expression,
+                filter_type: self.filter_type.clone()
+            }
+        )
     }
 }
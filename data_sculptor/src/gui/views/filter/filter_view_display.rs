@@ -1,10 +1,15 @@
 //! Module implementing the display functions for the [`FilterView`]
 
 use iced::{Alignment, Element, Length};
-use iced::widget::{Button, Column, Container, Row, Scrollable, Space, text_input};
+use iced::widget::{Button, Column, Container, rich_text, Row, Scrollable, Space, span, Text, text_input};
+use iced::widget::text::Span;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use crate::core::filters::expression_parser;
+use crate::core::filters::expression_parser::HighlightKind;
+use crate::core::filters::filter::{FilterType, KeyFilterMode, KeySelection};
 use crate::gui::gui_message::GUIMessage;
 use crate::gui::gui_style;
-use crate::gui::views::filter::filter_view_control::FilterView;
+use crate::gui::views::filter::filter_view_control::{FilterPreview, FilterView};
 use crate::gui::views::list::list_view_control::ListView;
 
 /// Implementation of the display functions for the list view
@@ -23,6 +28,7 @@ impl FilterView
             "Filter syntax description in README.md",
             &self.input_value
         )
+            .id(self.input_id.clone())
             .on_input(GUIMessage::FilterInputChanged)
             .on_submit(GUIMessage::AddFilter)
             .padding(10)
@@ -33,13 +39,36 @@ impl FilterView
             .padding(10)
             .style(gui_style::ButtonStyle::style);
 
-        let input_row = Row::new()
+        let mut input_row = Row::new()
             .align_y(Alignment::Center)
             .spacing(20)
             .push(text_input)
             .push(add_button);
 
-        let input_row_container = Container::new(input_row)
+        if self.editing.is_some()
+        {
+            input_row = input_row.push
+            (
+                Button::new("Update filter")
+                    .on_press(GUIMessage::UpdateFilter)
+                    .padding(10)
+                    .style(gui_style::ButtonStyle::style)
+            );
+        }
+
+        let input_column = Column::new()
+            .spacing(10)
+            .push(input_row)
+            .push(self.display_highlighted_input())
+            .push(self.display_completions())
+            .push(self.display_preview());
+
+        let input_row_container = Container::new(input_column)
+            .padding(20)
+            .style(gui_style::container_bar_style);
+
+        // MACROS
+        let macro_container = Container::new(self.display_macros())
             .padding(20)
             .style(gui_style::container_bar_style);
 
@@ -47,7 +76,7 @@ impl FilterView
         let filter_list = self.display_filter_list();
 
         // CONSTRUCT
-        Column::new()
+        let mut column = Column::new()
             .push
             (
                 Row::new()
@@ -55,11 +84,220 @@ impl FilterView
                     .padding(8)
             )
             .push(input_row_container)
+            .push(macro_container);
+
+        if self.filter_type == FilterType::Key
+        {
+            column = column.push
+            (
+                Container::new(self.display_key_options())
+                    .padding(20)
+                    .style(gui_style::container_bar_style)
+            );
+        }
+
+        column
             .push(Space::with_height(15))
             .push(filter_list)
             .into()
     }
 
+    /// Shows how the key filters are combined and the checklist of all keys in the data.
+    fn display_key_options(&self) -> Element<'_, GUIMessage>
+    {
+        // MODE
+        let mode_button = |label: &'static str, mode: KeyFilterMode|
+            {
+                let style = if self.key_filter_mode == mode {gui_style::ButtonStyle::style}
+                    else {gui_style::FilterButtonStyle::style};
+
+                Button::new(Text::new(label).size(14))
+                    .on_press(GUIMessage::SetKeyFilterMode(mode))
+                    .padding([5, 10])
+                    .style(style)
+            };
+
+        let mode_row = Row::new()
+            .align_y(Alignment::Center)
+            .spacing(10)
+            .push(Text::new("Show keys matching"))
+            .push(mode_button("any filter", KeyFilterMode::Any))
+            .push(mode_button("all filters", KeyFilterMode::All));
+
+        // CHECKLIST
+        let mut key_row = Row::new().spacing(10);
+        for key in &self.dataset_keys
+        {
+            let style = if self.checked_keys.contains(key) {gui_style::ButtonStyle::style}
+                else {gui_style::FilterButtonStyle::style};
+
+            key_row = key_row.push
+            (
+                Button::new(Text::new(key.as_str()).size(14))
+                    .on_press(GUIMessage::ToggleChecklistKey(key.clone()))
+                    .padding([5, 10])
+                    .style(style)
+            );
+        }
+
+        let selection_button = |label: &'static str, selection: KeySelection|
+            {
+                let mut button = Button::new(Text::new(label).size(14))
+                    .padding([5, 10])
+                    .style(gui_style::ButtonStyle::style);
+
+                if !self.checked_keys.is_empty()
+                {
+                    button = button.on_press(GUIMessage::AddKeySelection(selection));
+                }
+                button
+            };
+
+        let selection_row = Row::new()
+            .spacing(10)
+            .push(selection_button("Include checked keys", KeySelection::Include))
+            .push(selection_button("Exclude checked keys", KeySelection::Exclude));
+
+        Column::new()
+            .spacing(10)
+            .push(mode_row)
+            .push(Scrollable::new(key_row).direction(Direction::Horizontal(Scrollbar::default())))
+            .push(selection_row)
+            .into()
+    }
+
+    /// Shows the input with the pieces of the expression colored by their [`HighlightKind`].
+    fn display_highlighted_input(&self) -> Element<'_, GUIMessage>
+    {
+        let input = self.input_value.as_str();
+        let mut spans: Vec<Span<'_, GUIMessage>> = Vec::new();
+        let mut position = 0;
+
+        for highlight in expression_parser::highlight(input)
+        {
+            // Whitespace between the pieces
+            if position < highlight.range.start
+            {
+                spans.push(span(&input[position..highlight.range.start]));
+            }
+
+            let mismatched = matches!(highlight.kind,
+                HighlightKind::MismatchedParenthesis | HighlightKind::Invalid);
+
+            position = highlight.range.end;
+            spans.push
+            (
+                span(&input[highlight.range])
+                    .color(gui_style::filter_highlight_color(highlight.kind, &self.palette))
+                    .underline(mismatched)
+            );
+        }
+        spans.push(span(&input[position..]));
+
+        Container::new(rich_text(spans).size(18))
+            .padding([0, 10])
+            .into()
+    }
+
+    /// Lists the suggestions to complete the input. Clicking one replaces the input with it.
+    fn display_completions(&self) -> Element<'_, GUIMessage>
+    {
+        let mut row = Row::new().spacing(10);
+        for completion in &self.completions
+        {
+            row = row.push
+            (
+                Button::new(Text::new(completion.label.as_str()).size(14))
+                    .on_press(GUIMessage::AcceptCompletion(completion.input.clone()))
+                    .padding([3, 8])
+                    .style(gui_style::ButtonStyle::style)
+            );
+        }
+        row.into()
+    }
+
+    /// Shows the inputs to define macros and the macro library.
+    fn display_macros(&self) -> Element<'_, GUIMessage>
+    {
+        let input_row = Row::new()
+            .align_y(Alignment::Center)
+            .spacing(20)
+            .push
+            (
+                text_input("@macro_name", &self.macro_name_input)
+                    .on_input(GUIMessage::MacroNameInputChanged)
+                    .on_submit(GUIMessage::DefineMacro)
+                    .padding(10)
+                    .width(Length::Fixed(200.0))
+            )
+            .push
+            (
+                text_input("Filter the macro stands for", &self.macro_input)
+                    .on_input(GUIMessage::MacroInputChanged)
+                    .on_submit(GUIMessage::DefineMacro)
+                    .padding(10)
+            )
+            .push
+            (
+                Button::new("Define macro")
+                    .on_press(GUIMessage::DefineMacro)
+                    .padding(10)
+                    .style(gui_style::ButtonStyle::style)
+            );
+
+        let mut column = Column::new()
+            .spacing(10)
+            .push(input_row);
+
+        if let Some(error) = &self.macro_error
+        {
+            column = column.push(Text::new(error.as_str()).color(self.palette.error));
+        }
+
+        let mut macro_row = Row::new().spacing(10);
+        for (name, definition) in &self.macros
+        {
+            let macro_button = Button::new(Text::new(format!("@{} = {}", name, definition)).size(14))
+                .on_press(GUIMessage::ClickMacro(name.clone()))
+                .padding([5, 10])
+                .style(gui_style::FilterButtonStyle::style);
+
+            let delete_button = Button::new(Text::new("X").size(14))
+                .on_press(GUIMessage::DeleteMacro(name.clone()))
+                .padding([5, 10])
+                .style(gui_style::DeleteButtonStyle::style);
+
+            macro_row = macro_row.push(Row::new().push(macro_button).push(delete_button));
+        }
+
+        column.push(Scrollable::new(macro_row).direction(Direction::Horizontal(Scrollbar::default()))).into()
+    }
+
+    /// Shows whether the input is a valid filter and what adding it would change.
+    fn display_preview(&self) -> Element<'_, GUIMessage>
+    {
+        let unit = if self.filter_type == FilterType::Key {"keys"} else {"days"};
+
+        match &self.preview
+        {
+            FilterPreview::Empty => {Space::with_height(20).into()}
+
+            FilterPreview::Invalid(reason) =>
+                {
+                    Text::new(reason.as_str())
+                        .color(self.palette.error)
+                        .into()
+                }
+
+            FilterPreview::Valid {kept, current} =>
+                {
+                    Text::new(format!("Valid filter: keeps {} of currently {} {}", kept, current, unit))
+                        .color(self.palette.date)
+                        .into()
+                }
+        }
+    }
+
     pub fn display_filter_list(&self) -> Element<GUIMessage>
     {
         let mut filter_column = Column::new().spacing(20);
@@ -0,0 +1,121 @@
+//! Module implementing the management of the [`FilterMacros`] of the [`FilterView`]
+
+use indexmap::IndexMap;
+use crate::core::filters::expression_parser;
+use crate::core::filters::expression_parser::{ExpressionError, FilterMacros};
+use crate::core::filters::filter::{Filter, FilterID};
+use crate::gui::gui_message::GUIMessage;
+use crate::gui::views::filter::filter_view_control::{FilterChange, FilterView};
+
+/// Implementation of the macro functions for the filter view
+impl FilterView
+{
+    /// Handles all messages related to the macro library.
+    /// Returns the change to the macro library that should be applied, if any.
+    pub(crate) fn update_macros(&mut self, message: GUIMessage) -> Option<FilterChange>
+    {
+        match message
+        {
+            GUIMessage::MacroNameInputChanged(input) => {self.macro_name_input = input; None}
+            GUIMessage::MacroInputChanged(input) => {self.macro_input = input; None}
+            GUIMessage::DefineMacro => {self.define_macro()}
+            GUIMessage::ClickMacro(name) => {self.click_macro(name)}
+            GUIMessage::DeleteMacro(name) => {self.delete_macro(name)}
+            _ => {None}
+        }
+    }
+
+    fn click_macro(&mut self, name: String) -> Option<FilterChange>
+    {
+        self.macro_input = self.macros.get(&name).cloned().unwrap_or_default();
+        self.macro_name_input = name;
+        self.macro_error = None;
+        None
+    }
+
+    /// Adds the macro in the inputs to the library or replaces the one with the same name.
+    fn define_macro(&mut self) -> Option<FilterChange>
+    {
+        let name = self.macro_name_input.trim().trim_start_matches('@').to_string();
+        if !expression_parser::is_valid_macro_name(&name)
+        {
+            self.macro_error = Some(String::from("Macro names can only contain letters, digits and '_'"));
+            return None;
+        }
+
+        let mut macros = self.macros.clone();
+        macros.insert(name.clone(), self.macro_input.trim().to_string());
+
+        // Checking every macro of the new library also catches every cycle it would be part of
+        let change = match self.macro_change(macros)
+        {
+            Ok(change) => {change}
+            Err(e) =>
+                {
+                    self.macro_error = Some(match e
+                    {
+                        ExpressionError::InvalidMacro(invalid) if invalid == name =>
+                            {ExpressionError::InvalidSyntax.to_string()}
+                        e => {e.to_string()}
+                    });
+                    return None;
+                }
+        };
+
+        self.macro_name_input.clear();
+        self.macro_input.clear();
+        self.macro_error = None;
+        Some(change)
+    }
+
+    /// Removes the macro with the given name unless it is still referenced somewhere.
+    fn delete_macro(&mut self, name: String) -> Option<FilterChange>
+    {
+        let mut macros = self.macros.clone();
+        macros.shift_remove(&name);
+
+        match self.macro_change(macros)
+        {
+            Ok(change) => {self.macro_error = None; Some(change)}
+            Err(_) =>
+                {
+                    self.macro_error = Some(format!("'@{}' is still used by other filters or macros", name));
+                    None
+                }
+        }
+    }
+
+    /// Replaces the macro library without recording the change, see [`Self::macro_change`].
+    pub(crate) fn set_macros(&mut self, macros: FilterMacros) -> Result<(), ExpressionError>
+    {
+        let change = self.macro_change(macros)?;
+        self.apply_change(change);
+        Ok(())
+    }
+
+    /// Builds the change that replaces the macro library and parses all filters again,
+    /// so that they use the new definitions. Fails if any filter or macro no longer parses.
+    fn macro_change(&self, macros: FilterMacros) -> Result<FilterChange, ExpressionError>
+    {
+        for name in macros.keys()
+        {
+            expression_parser::parse_with_macros(&self.filter_type, &format!("@{}", name), &macros)?;
+        }
+
+        let mut filters: IndexMap<FilterID, Filter> = IndexMap::new();
+        for filter in self.filters.values()
+        {
+            let expression = expression_parser::parse_with_macros(&self.filter_type, &filter.title, &macros)?;
+
+            // Filters that became equal through the new definitions are merged
+            filters.entry(FilterID::from(&expression)).or_insert(Filter
+            {
+                title: filter.title.clone(),
+                expression,
+                filter_type: self.filter_type.clone()
+            });
+        }
+
+        Ok(FilterChange::SetMacros {macros, filters})
+    }
+}
@@ -1,2 +1,3 @@
 pub mod filter_view_control;
-mod filter_view_display;
\ No newline at end of file
+mod filter_view_display;
+mod filter_view_macros;
@@ -6,5 +6,6 @@ pub enum GUIViewType
 {
     ListView,
     FilterView,
-    MenuView
+    MenuView,
+    SettingsView
 }
\ No newline at end of file
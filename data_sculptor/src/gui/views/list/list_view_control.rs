@@ -1,13 +1,48 @@
 //! Module implementing the control functions for the [`ListView`]
 
-use std::sync::{Mutex};
-use iced::{Task};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use iced::{clipboard, Task};
+use iced::futures::SinkExt;
+use iced::task::Handle;
+use iced::widget::scrollable;
+use iced::widget::scrollable::{AbsoluteOffset, Viewport};
 use indexmap::IndexMap;
-use crate::core::data_manager::DataManager;
+use crate::core::data_containers::{DayDataParsed, DATE_FORMAT};
+use crate::core::data_manager::{DataManager, LoadedData, LoadingEvent};
 use crate::core::filters::filter::{Filter, FilterID, FilterType};
-use crate::file_io::{data_writer, file_dialogs};
+use crate::core::filters::filter_completion;
+use crate::core::filters::filter_matches;
+use crate::core::filters::filter_matches::FilterMatches;
+use crate::core::filters::filtered_index::FilteredIndex;
+use crate::core::search_index::SearchIndex;
+use crate::core::settings::{SavedFilterSet, SavedFilters, Settings};
+use crate::file_io::{data_loader, data_writer, exporter, file_dialogs, text_formats};
+use crate::file_io::exporter::{ExportContent, ExportFormat};
+use crate::file_io::text_formats::TextFormat;
 use crate::gui::gui_message::GUIMessage;
-use crate::gui::views::filter::filter_view_control::FilterView;
+use crate::core::palette::Palette;
+use crate::gui::history::{History, HistoryCommand};
+use crate::gui::views::day::day_view_control::DayView;
+use crate::gui::views::filter::filter_view_control::{FilterChange, FilterPreview, FilterView};
+use crate::gui::views::list::list_view_editing::EntryEditor;
+use crate::gui::views::list::list_view_search::ListSearch;
+use crate::gui::views::menu::menu_view_control::MenuView;
+use crate::gui::views::table::table_view_control::TableView;
+
+pub const DATE_ROW_HEIGHT: f32 = 40.0;
+pub const ENTRY_ROW_HEIGHT: f32 = 30.0;
+pub const DAY_SPACING: f32 = 10.0;
+
+/// Maximum number of characters of a value that are shown in its row of the list.
+pub const MAX_LIST_VALUE_CHARS: usize = 80;
+
+/// Maximum number of changes that can be undone.
+pub const HISTORY_CAPACITY: usize = 100;
+
+pub const UNSAVED_EDITS_NOTICE: &str = "The file changed on disk but was not reloaded because of unsaved edits";
 
 /// Lets the user load a data file and display it in a scrollable list.
 ///
@@ -17,8 +52,62 @@ pub struct ListView
     pub loaded_valid_file: bool,
     pub load_error_msg: String,
     pub loading_file: bool,
+
+    /// Fraction (0.0 to 1.0) of the file that is currently being loaded.
+    pub load_progress: f32,
+    load_cancelled: Arc<AtomicBool>,
+    load_handle: Option<Handle>,
+
+    /// Files that are being loaded. Several files are merged.
+    loading_paths: Vec<String>,
+
+    /// Whether the files that are being loaded were dropped onto the window.
+    loading_dropped: bool,
+
+    /// Whether the loaded file is being reloaded in the background because it changed on disk.
+    pub(crate) reloading: bool,
+
+    /// Note about the last background reload or copy to the clipboard.
+    pub notice: Option<String>,
+
     pub filter_views: [FilterView; 3],
-    pub(crate) opened_filter_view: Option<FilterType>
+    pub table_view: TableView,
+    pub day_view: DayView,
+    pub(crate) opened_filter_view: Option<FilterType>,
+    pub(crate) show_table: bool,
+    pub(crate) editor: EntryEditor,
+    pub(crate) history: History<HistoryCommand>,
+    pub(crate) search: ListSearch,
+
+    // SETTINGS
+    /// Format the dates are displayed in. See [`Settings`].
+    pub date_format: String,
+
+    /// Directory that file dialogs open in.
+    pub dialog_directory: Option<String>,
+
+    /// Whether exported reports include summary statistics.
+    pub report_statistics: bool,
+    pub palette: Palette,
+
+    // FILTERED INDEX
+    pub(crate) filtered_index: FilteredIndex,
+
+    /// The data generation and filter revisions the [`FilteredIndex`] was built from.
+    indexed_revision: Option<(u64, [u64; 3])>,
+
+    /// The index of the opened [`FilterView`], its input, the data generation and filter
+    /// revisions its preview was computed from.
+    previewed_revision: Option<(usize, String, u64, [u64; 3])>,
+
+    /// Offset of the top of each filtered day inside the list.
+    /// Holds one additional element at the end for the total height of the list.
+    pub(crate) day_offsets: Vec<f32>,
+
+    // SCROLLING
+    pub(crate) list_scroll_id: scrollable::Id,
+    pub list_scroll_y: f32,
+    pub(crate) list_viewport_height: f32
 }
 
 impl Default for ListView
@@ -30,13 +119,37 @@ impl Default for ListView
             loaded_valid_file: true,
             load_error_msg: "".to_string(),
             loading_file: false,
+            load_progress: 0.0,
+            load_cancelled: Arc::new(AtomicBool::new(false)),
+            load_handle: None,
+            loading_paths: Vec::new(),
+            loading_dropped: false,
+            reloading: false,
+            notice: None,
             filter_views:
             [
                 FilterView::from(FilterType::Date),
                 FilterView::from(FilterType::Key),
                 FilterView::from(FilterType::Value)
             ],
-            opened_filter_view: None
+            table_view: TableView::default(),
+            day_view: DayView::default(),
+            opened_filter_view: None,
+            show_table: false,
+            editor: EntryEditor::default(),
+            history: History::new(HISTORY_CAPACITY),
+            search: ListSearch::default(),
+            date_format: String::from(DATE_FORMAT),
+            dialog_directory: None,
+            report_statistics: true,
+            palette: Palette::default(),
+            filtered_index: FilteredIndex::default(),
+            indexed_revision: None,
+            previewed_revision: None,
+            day_offsets: vec![0.0],
+            list_scroll_id: scrollable::Id::unique(),
+            list_scroll_y: 0.0,
+            list_viewport_height: 0.0
         }
     }
 }
@@ -47,6 +160,34 @@ impl ListView
     // UPDATE
     pub fn update(&mut self, message: GUIMessage, dm: &Mutex<DataManager>) -> Task<GUIMessage>
     {
+        let task = self.handle_message(message, dm);
+        self.refresh_filtered_index(dm);
+        self.refresh_filter_preview(dm);
+        self.refresh_search(dm);
+        task
+    }
+
+    fn handle_message(&mut self, message: GUIMessage, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        // Loading happens in the background and has to be handled regardless of the opened view
+        if let GUIMessage::LoadingUpdate(event) = message
+        {
+            return self.loading_update(event, dm);
+        }
+
+        match message
+        {
+            GUIMessage::Undo => {return self.undo(dm);}
+            GUIMessage::Redo => {return self.redo(dm);}
+            GUIMessage::SelectFile => {return self.select_file();}
+            GUIMessage::FileDropped(path) => {return self.file_dropped(path);}
+            GUIMessage::WatchedFileChanged(path) => {return self.watched_file_changed(path, dm);}
+            GUIMessage::OpenFilterView(filter_type) => {return self.open_filter_view(filter_type);}
+            GUIMessage::CopyFiltered(format) => {return self.copy_filtered(format, dm);}
+            GUIMessage::ExportData(format) => {return self.export_data(format, dm);}
+            _ => {}
+        }
+
         if let Some(filter_view) = self.opened_filter_view.clone()
         {
             match message
@@ -54,7 +195,8 @@ impl ListView
                 GUIMessage::ReturnToView(view_name) => {self.return_to_view(view_name)}
                 _ =>
                     {
-                        self.get_filter_view_mut(&filter_view).update(message)
+                        let index = filter_view_index(&filter_view);
+                        self.filter_views[index].update(message, &mut self.history)
                     }
             }
         }
@@ -63,10 +205,44 @@ impl ListView
         {
             match message
             {
-                GUIMessage::SelectFile => {self.select_file()}
                 GUIMessage::SaveFile => {self.save_file(dm)}
-                GUIMessage::FileSelected(path) => {self.file_selected(path, dm)}
-                GUIMessage::OpenFilterView(filter_type) => {self.open_filter_view(filter_type)}
+                GUIMessage::FileSelected(path) => {self.file_selected(path)}
+                GUIMessage::CancelLoading => {self.cancel_loading()}
+                GUIMessage::ToggleTableView => {self.toggle_table_view()}
+                GUIMessage::ListScrolled(viewport) => {self.list_scrolled(viewport)}
+
+                GUIMessage::OpenDay(_) | GUIMessage::ShowPreviousDay | GUIMessage::ShowNextDay
+                | GUIMessage::CopyDayAsJson | GUIMessage::CopyValue(_) =>
+                    {
+                        self.day_view.update(message, &dm.lock().unwrap().data)
+                    }
+                GUIMessage::CloseDay => {self.close_day()}
+
+                GUIMessage::SearchInputChanged(_) | GUIMessage::ClearSearch
+                | GUIMessage::FocusSearch | GUIMessage::JumpToDay(_) =>
+                    {
+                        self.update_search(message, dm)
+                    }
+
+                GUIMessage::SortTableBy(_) | GUIMessage::StartColumnResize(_)
+                | GUIMessage::ColumnResizeMoved(_) | GUIMessage::EndColumnResize
+                | GUIMessage::TableScrolled(_) | GUIMessage::TableDatesScrolled(_) =>
+                    {
+                        let columns: Vec<String> = self.filtered_index.keys.iter()
+                            .map(|key| key.title.clone())
+                            .collect();
+                        self.table_view.update(message, &columns)
+                    }
+
+                GUIMessage::StartEditValue(_, _) | GUIMessage::StartAddKey(_)
+                | GUIMessage::StartAddDay | GUIMessage::EditKeyInputChanged(_)
+                | GUIMessage::EditValueInputChanged(_) | GUIMessage::ConfirmEdit
+                | GUIMessage::CancelEdit | GUIMessage::DeleteKey(_, _) | GUIMessage::DeleteDay(_)
+                | GUIMessage::SaveData =>
+                    {
+                        self.update_editing(message, dm)
+                    }
+
                 _ => {Task::none()}
             }
         }
@@ -74,34 +250,292 @@ impl ListView
 
     fn select_file(&mut self) -> Task<GUIMessage>
     {
-        if let Some(file_path) = file_dialogs::pick_file()
+        if let Some(file_path) = file_dialogs::pick_file(self.dialog_directory.as_deref())
         {
-            self.loading_file = true;
-
-            // For display reasons: Assume file is valid during loading, only change
-            // if it fails.
-            self.loaded_valid_file = true;
-
+            // The loading state is set once the file is actually loaded, see [`Self::load_files`],
+            // since loading may still wait for the user to decide what to do with unsaved edits.
             Task::perform(async move { file_path }, GUIMessage::FileSelected)
         } else { Task::none() }
     }
 
     fn save_file(&mut self, dm: &Mutex<DataManager>) -> Task<GUIMessage>
     {
-        if let Some(file_path) = file_dialogs::save_json_file()
+        if let Some(file_path) = file_dialogs::save_json_file(self.dialog_directory.as_deref())
         {
             data_writer::write_data_filtered(file_path, &dm.lock().unwrap().data, &self);
         }
         Task::none()
     }
 
-    fn file_selected(&mut self, path: String, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    fn file_selected(&mut self, path: String) -> Task<GUIMessage>
+    {
+        self.load_files(vec![path], false)
+    }
+
+    /// Loads the file dropped onto the window. The window receives one event per file,
+    /// all at once. Files dropped while the previous ones of the same drop are still loading
+    /// are merged with them, see [`DataManager::read_merged_data`].
+    fn file_dropped(&mut self, path: String) -> Task<GUIMessage>
     {
+        if !data_loader::is_supported_file(&path)
+        {
+            self.loaded_valid_file = false;
+            self.load_error_msg = format!("Unsupported file type: {}", path);
+            return Task::none();
+        }
+
+        let mut paths = if self.loading_file && self.loading_dropped
+        {
+            self.loading_paths.clone()
+        }
+        else {Vec::new()};
+
+        if !paths.contains(&path)
+        {
+            paths.push(path);
+        }
+        self.load_files(paths, true)
+    }
+
+    /// Reloads the loaded file in the background after it changed on disk.
+    /// The filters stay untouched. Unsaved edits are never overwritten, instead the user
+    /// is told that the file was not reloaded.
+    fn watched_file_changed(&mut self, path: String, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        let data_manager = dm.lock().unwrap();
+        if data_manager.source_path.as_ref() != Some(&path)
+        {
+            return Task::none();
+        }
+
+        // A file that is already being loaded is read in its latest state anyway
+        if self.loading_file && !self.reloading
+        {
+            return Task::none();
+        }
+
+        if data_manager.dirty
+        {
+            self.notice = Some(String::from(UNSAVED_EDITS_NOTICE));
+            return Task::none();
+        }
+        drop(data_manager);
+
+        let task = self.load_files(vec![path], false);
+        self.reloading = true;
+        task
+    }
+
+    /// Starts loading the files at the given paths in the background, merging them if
+    /// there are several. The currently loaded data stays in place until loading has finished.
+    fn load_files(&mut self, paths: Vec<String>, dropped: bool) -> Task<GUIMessage>
+    {
+        self.stop_loading();
+
+        self.loading_file = true;
+        self.loaded_valid_file = true;
+        self.load_progress = 0.0;
+        self.load_cancelled = Arc::new(AtomicBool::new(false));
+        self.loading_paths = paths.clone();
+        self.loading_dropped = dropped;
+        self.notice = None;
+
+        let (task, handle) = Task::run
+            (
+                loading_stream(paths, Arc::clone(&self.load_cancelled)),
+                GUIMessage::LoadingUpdate
+            )
+            .abortable();
+
+        self.load_handle = Some(handle);
+        task
+    }
+
+    fn loading_update(&mut self, event: LoadingEvent, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        match event
+        {
+            LoadingEvent::Progress(progress) => {self.load_progress = progress;}
+
+            LoadingEvent::Finished(result) if self.reloading =>
+                {
+                    self.loading_file = false;
+                    self.load_handle = None;
+                    self.reloading = false;
+                    return self.reload_finished(result, dm);
+                }
+
+            LoadingEvent::Finished(result) =>
+                {
+                    self.loading_file = false;
+                    self.load_handle = None;
+
+                    match result
+                    {
+                        Ok(LoadedData {data, search_index}) =>
+                            {
+                                let mut data_manager = dm.lock().unwrap();
+                                match self.loading_paths.as_slice()
+                                {
+                                    [path] => {data_manager.replace_data(data, path);}
+                                    _ => {data_manager.replace_data_merged(data);}
+                                }
+                                self.set_search_index(search_index, data_manager.generation);
+                                drop(data_manager);
+
+                                self.editor = EntryEditor::default();
+                                self.day_view.date = None;
+
+                                // Edits of the previous data can not be applied to the new data
+                                self.history.retain(|command| !matches!(command, HistoryCommand::Data(_)));
+                                self.loaded_valid_file = true;
+                                self.load_error_msg = match self.loading_paths.len()
+                                {
+                                    1 => {String::from("Loaded successfully")}
+                                    count => {format!("Merged {} files", count)}
+                                };
+                            }
+                        Err(e) =>
+                            {
+                                self.loaded_valid_file = false;
+                                self.load_error_msg = e;
+                            }
+                    }
+                }
+        }
+
+        Task::none()
+    }
+
+    /// Swaps in the reloaded data and scrolls the list back to the day that was at the top,
+    /// which may have moved if days were added before it. A file that could not be read
+    /// (e.g. because it was read while being written) keeps the current data.
+    fn reload_finished(&mut self, result: Result<LoadedData, String>, dm: &Mutex<DataManager>)
+        -> Task<GUIMessage>
+    {
+        let LoadedData {data, search_index} = match result
+        {
+            Ok(loaded) => {loaded}
+            Err(e) =>
+                {
+                    self.notice = Some(format!("Could not reload the file: {}", e));
+                    return Task::none();
+                }
+        };
+
+        let mut data_manager = dm.lock().unwrap();
+        if data_manager.dirty
+        {
+            self.notice = Some(String::from(UNSAVED_EDITS_NOTICE));
+            return Task::none();
+        }
+        if data == data_manager.data
+        {
+            return Task::none();
+        }
+        let Some(path) = data_manager.source_path.clone() else {return Task::none()};
+
+        let top_day = self.top_visible_day(&data_manager.data);
+        let new_days = data_manager.count_new_days(&data);
+        data_manager.replace_data(data, &path);
+        self.set_search_index(search_index, data_manager.generation);
+        drop(data_manager);
+
+        self.editor = EntryEditor::default();
+        self.history.retain(|command| !matches!(command, HistoryCommand::Data(_)));
+        self.notice = Some(match new_days
+        {
+            1 => {String::from("Reloaded 1 new day")}
+            count => {format!("Reloaded {} new days", count)}
+        });
+
+        let Some((date, offset_in_day)) = top_day else {return Task::none()};
+        self.refresh_filtered_index(dm);
+        let data = &dm.lock().unwrap().data;
+        match self.filtered_index.days.iter().position(|day| data[day.day_index].date.date_string == date)
+        {
+            Some(index) =>
+                {
+                    self.list_scroll_y = self.day_offsets[index] + offset_in_day;
+                    scrollable::scroll_to(self.list_scroll_id.clone(), AbsoluteOffset {x: 0.0, y: self.list_scroll_y})
+                }
+            None => {Task::none()}
+        }
+    }
+
+    /// Returns the date of the day at the top of the visible part of the list and how far
+    /// the list is scrolled into it.
+    fn top_visible_day(&self, data: &[DayDataParsed]) -> Option<(String, f32)>
+    {
+        let index = self.day_offsets[1..].partition_point(|&day_bottom| day_bottom <= self.list_scroll_y);
+        let day = self.filtered_index.days.get(index)?;
+        let date = data.get(day.day_index)?.date.date_string.clone();
+        Some((date, self.list_scroll_y - self.day_offsets[index]))
+    }
+
+    fn cancel_loading(&mut self) -> Task<GUIMessage>
+    {
+        self.stop_loading();
+        Task::none()
+    }
+
+    /// Stops loading the current file in the background. The loaded data stays untouched.
+    fn stop_loading(&mut self)
+    {
+        self.load_cancelled.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.load_handle.take()
+        {
+            handle.abort();
+        }
+
         self.loading_file = false;
+        self.reloading = false;
+    }
+
+    /// Writes the data that passes the filters to the clipboard in the given format.
+    /// The JSON has the same shape as the file written by "Save as".
+    fn copy_filtered(&mut self, format: TextFormat, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        let days = data_writer::filter_data(&dm.lock().unwrap().data, self);
+        match text_formats::format_days(&days, format)
+        {
+            Ok(text) =>
+                {
+                    self.notice = Some(match days.len()
+                    {
+                        1 => {format!("Copied 1 day as {}", format)}
+                        count => {format!("Copied {} days as {}", count, format)}
+                    });
+                    clipboard::write(text)
+                }
+            Err(e) =>
+                {
+                    self.notice = Some(format!("Could not copy the data: {}", e));
+                    Task::none()
+                }
+        }
+    }
+
+    /// Lets the user pick a file and exports the data that passes the filters to it
+    /// in the given format. The report is titled after the file the data was loaded from.
+    fn export_data(&mut self, format: ExportFormat, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        let exporter = format.exporter();
+        let Some(file_path) = file_dialogs::save_export_file(self.dialog_directory.as_deref(), exporter)
+            else {return Task::none()};
 
-        let mut unwrapped_dm = dm.lock().unwrap();
-        (self.loaded_valid_file, self.load_error_msg) = unwrapped_dm.load_data(path.as_str());
+        let data_manager = dm.lock().unwrap();
+        let title = data_manager.source_path.as_ref()
+            .and_then(|path| Path::new(path).file_stem())
+            .map(|stem| format!("{} report", stem.to_string_lossy()))
+            .unwrap_or(String::from("Data Sculptor report"));
+        let content = ExportContent::from_list_view(title, &data_manager.data, self, self.report_statistics);
 
+        self.notice = Some(match exporter::write_export(exporter, &content, &file_path)
+        {
+            Ok(_) => {format!("Exported {} days to {}", content.days.len(), file_path)}
+            Err(e) => {format!("Could not export the data: {}", e)}
+        });
         Task::none()
     }
 
@@ -111,6 +545,158 @@ impl ListView
         Task::none()
     }
 
+    fn toggle_table_view(&mut self) -> Task<GUIMessage>
+    {
+        self.show_table = !self.show_table;
+        Task::none()
+    }
+
+    /// Closes the [`DayView`] and scrolls the list back to where it was before the day was opened.
+    fn close_day(&mut self) -> Task<GUIMessage>
+    {
+        self.day_view.date = None;
+        scrollable::scroll_to(self.list_scroll_id.clone(), AbsoluteOffset {x: 0.0, y: self.list_scroll_y})
+    }
+
+    fn list_scrolled(&mut self, viewport: Viewport) -> Task<GUIMessage>
+    {
+        self.list_scroll_y = viewport.absolute_offset().y;
+        self.list_viewport_height = viewport.bounds().height;
+        Task::none()
+    }
+
+    /// Rebuilds the [`FilteredIndex`] and the layout of the list if the data or
+    /// any of the filters changed since it was last built.
+    pub fn refresh_filtered_index(&mut self, dm: &Mutex<DataManager>)
+    {
+        let data_manager = dm.lock().unwrap();
+        let filter_revisions = self.filter_views.each_ref().map(|view| view.revision);
+        let revision = Some((data_manager.generation, filter_revisions));
+        if self.indexed_revision == revision
+        {
+            return;
+        }
+
+        self.filtered_index = FilteredIndex::build
+            (
+                &data_manager.data,
+                self.get_filters(&FilterType::Date),
+                self.get_filters(&FilterType::Key),
+                self.get_filter_view(&FilterType::Key).key_filter_mode,
+                self.get_filters(&FilterType::Value)
+            );
+        self.indexed_revision = revision;
+
+        self.day_offsets = Vec::with_capacity(self.filtered_index.days.len() + 1);
+        let mut offset = 0.0;
+        for day in &self.filtered_index.days
+        {
+            self.day_offsets.push(offset);
+            offset += day_height(day.entry_indices.len());
+        }
+        self.day_offsets.push(offset);
+    }
+
+    /// Recomputes the [`FilterPreview`] and the completions of the opened [`FilterView`]
+    /// if its input, the data or any filters changed since they were last computed.
+    pub fn refresh_filter_preview(&mut self, dm: &Mutex<DataManager>)
+    {
+        let Some(filter_type) = self.opened_filter_view.clone() else {return};
+        let index = filter_view_index(&filter_type);
+
+        let data_manager = dm.lock().unwrap();
+        let filter_revisions = self.filter_views.each_ref().map(|view| view.revision);
+        let input = self.filter_views[index].input_value.clone();
+        let revision = Some((index, input, data_manager.generation, filter_revisions));
+        if self.previewed_revision == revision
+        {
+            return;
+        }
+
+        let keys = data_manager.all_keys();
+        let filter_view = &self.filter_views[index];
+        let completions = filter_completion::complete(&filter_type, &filter_view.input_value, &keys);
+
+        let preview = if filter_view.input_value.trim().is_empty()
+        {
+            FilterPreview::Empty
+        }
+        else
+        {
+            match filter_view.parse_input()
+            {
+                Ok(filter) => {self.preview_candidate(index, filter, &data_manager.data)}
+                Err(e) => {FilterPreview::Invalid(e.to_string())}
+            }
+        };
+
+        self.filter_views[index].preview = preview;
+        self.filter_views[index].completions = completions;
+        self.filter_views[index].dataset_keys = keys;
+        self.previewed_revision = revision;
+    }
+
+    /// Computes the [`FilterPreview`] of adding the given filter to the [`FilterView`]
+    /// at the given index. Only the filters of its type are evaluated, on the days that
+    /// currently pass all filters, since the filters of the other types stay the same.
+    fn preview_candidate(&self, index: usize, filter: Filter, data: &[DayDataParsed]) -> FilterPreview
+    {
+        let filter_view = &self.filter_views[index];
+        match filter_view.filter_type
+        {
+            FilterType::Key =>
+                {
+                    let mut candidate_filters = filter_view.filters.clone();
+                    candidate_filters.insert(FilterID::from(&filter.expression), filter);
+
+                    FilterPreview::Valid
+                    {
+                        kept: self.filtered_index.count_keys_passing(data, &candidate_filters, filter_view.key_filter_mode),
+                        current: self.filtered_index.keys.len()
+                    }
+                }
+            _ => FilterPreview::Valid
+            {
+                kept: self.filtered_index.count_days_passing(data, &filter),
+                current: self.filtered_index.days.len()
+            }
+        }
+    }
+
+    /// Returns the range of indices into the [`FilteredIndex`] of all days that are at least
+    /// partially inside the visible part of the list.
+    pub(crate) fn visible_day_range(&self) -> Range<usize>
+    {
+        let day_count = self.filtered_index.days.len();
+
+        // Render one screen above and below to hide the swapping of days while scrolling
+        let top = self.list_scroll_y - self.list_viewport_height;
+        let bottom = self.list_scroll_y + 2.0 * self.list_viewport_height.max(1000.0);
+
+        let start = self.day_offsets[1..].partition_point(|&day_bottom| day_bottom <= top);
+        let end = self.day_offsets[..day_count].partition_point(|&day_top| day_top < bottom);
+        start..end.max(start)
+    }
+
+    /// The message that leaves what the user is currently in: the opened filter view,
+    /// the opened day, the current edit or the list view itself.
+    pub fn go_back_message(&self) -> GUIMessage
+    {
+        if self.opened_filter_view.is_some()
+        {
+            return GUIMessage::ReturnToView(ListView::view_title());
+        }
+        if self.day_view.is_open()
+        {
+            return GUIMessage::CloseDay;
+        }
+        if self.editor.target.is_some()
+        {
+            return GUIMessage::CancelEdit;
+        }
+        GUIMessage::ReturnToView(MenuView::view_title())
+    }
+
     fn return_to_view(&mut self, view_name: &str) -> Task<GUIMessage>
     {
         if view_name != ListView::view_title()
@@ -122,33 +708,166 @@ impl ListView
         Task::none()
     }
 
-    pub fn get_filters(&self, filter_type: &FilterType) -> &IndexMap<FilterID, Filter>
+    /// Applies the parts of the given [`Settings`] that concern the list view
+    /// and the given [`Palette`] to it and all views it holds.
+    pub fn apply_settings(&mut self, settings: &Settings, palette: &Palette)
     {
-        &self.get_filter_view(filter_type).filters
+        self.date_format = settings.date_display_format.clone();
+        self.table_view.date_format = settings.date_display_format.clone();
+        self.day_view.date_format = settings.date_display_format.clone();
+        self.dialog_directory = settings.default_directory.clone();
+        self.report_statistics = settings.report_statistics;
+
+        self.palette = palette.clone();
+        self.table_view.palette = palette.clone();
+        self.day_view.palette = palette.clone();
+        for filter_view in &mut self.filter_views
+        {
+            filter_view.palette = palette.clone();
+        }
     }
 
-    pub fn get_filter_view(&self, filter_type: &FilterType) -> & FilterView
+    /// The filters and macros of all filter views, to be restored in a later run.
+    pub fn saved_filters(&self) -> SavedFilters
     {
-        match filter_type
+        let saved_set = |filter_type: &FilterType|
+            {
+                let filter_view = self.get_filter_view(filter_type);
+                SavedFilterSet
+                {
+                    filters: filter_view.filters.values().map(|filter| filter.title.clone()).collect(),
+                    macros: filter_view.macros.clone()
+                }
+            };
+
+        SavedFilters
         {
-            FilterType::Date => &self.filter_views[0],
-            FilterType::Key => &self.filter_views[1],
-            FilterType::Value => &self.filter_views[2],
+            date: saved_set(&FilterType::Date),
+            key: saved_set(&FilterType::Key),
+            value: saved_set(&FilterType::Value),
+            key_filter_mode: self.get_filter_view(&FilterType::Key).key_filter_mode
         }
     }
 
-    pub fn get_filter_view_mut(&mut self, filter_type: &FilterType) -> &mut FilterView
+    /// Restores the given saved filters and macros. Macros that do not parse anymore and
+    /// filters that do not parse with the restored macros are skipped.
+    /// Restoring is not recorded in the history.
+    pub fn restore_filters(&mut self, saved: &SavedFilters)
     {
-        match filter_type
+        let saved_sets = [(FilterType::Date, &saved.date), (FilterType::Key, &saved.key),
+            (FilterType::Value, &saved.value)];
+
+        for (filter_type, saved_set) in saved_sets
         {
-            FilterType::Date => &mut self.filter_views[0],
-            FilterType::Key => &mut self.filter_views[1],
-            FilterType::Value => &mut self.filter_views[2],
+            let filter_view = self.get_filter_view_mut(&filter_type);
+            if filter_view.set_macros(saved_set.macros.clone()).is_err()
+            {
+                continue;
+            }
+
+            for title in &saved_set.filters
+            {
+                if let Ok(filter) = filter_view.parse_filter(title)
+                {
+                    let index = filter_view.filters.len();
+                    filter_view.apply_change(FilterChange::Insert {index, filter});
+                }
+            }
         }
+
+        let key_filter_mode = saved.key_filter_mode;
+        self.get_filter_view_mut(&FilterType::Key)
+            .apply_change(FilterChange::SetKeyFilterMode {mode: key_filter_mode});
+    }
+
+    /// Finds the [`FilterMatches`] of the current filters in the given day.
+    pub(crate) fn find_matches(&self, day: &DayDataParsed) -> FilterMatches
+    {
+        filter_matches::find_matches
+            (
+                day,
+                self.get_filters(&FilterType::Date),
+                self.get_filters(&FilterType::Key),
+                self.get_filter_view(&FilterType::Key).key_filter_mode,
+                self.get_filters(&FilterType::Value)
+            )
+    }
+
+    pub fn get_filters(&self, filter_type: &FilterType) -> &IndexMap<FilterID, Filter>
+    {
+        &self.get_filter_view(filter_type).filters
+    }
+
+    pub fn get_filter_view(&self, filter_type: &FilterType) -> & FilterView
+    {
+        &self.filter_views[filter_view_index(filter_type)]
+    }
+
+    pub fn get_filter_view_mut(&mut self, filter_type: &FilterType) -> &mut FilterView
+    {
+        &mut self.filter_views[filter_view_index(filter_type)]
     }
 
     pub fn view_title() -> &'static str
     {
         "list_view"
     }
+}
+
+/// Reads the data files at the given paths on a background thread, builds the
+/// [`SearchIndex`] of the read data and streams their
+/// [`LoadingEvent`]s. Several files are merged into one list of days.
+/// Progress is only reported in steps of at least one percent.
+fn loading_stream(paths: Vec<String>, cancelled: Arc<AtomicBool>)
+    -> impl iced::futures::Stream<Item = LoadingEvent>
+{
+    iced::stream::channel(100, move |mut output| async move
+        {
+            let mut last_progress = 0.0;
+            let mut on_progress = |progress: f32|
+                {
+                    if progress - last_progress >= 0.01
+                    {
+                        last_progress = progress;
+                        let _ = output.try_send(LoadingEvent::Progress(progress));
+                    }
+                };
+
+            let result = match paths.as_slice()
+            {
+                [path] => {DataManager::read_data(path, &mut on_progress, &cancelled)}
+                _ => {DataManager::read_merged_data(&paths, &mut on_progress, &cancelled)}
+            };
+            let result = result.map(|data| LoadedData {search_index: SearchIndex::build(&data), data});
+            let _ = output.send(LoadingEvent::Finished(result)).await;
+        })
+}
+
+/// Index of the [`FilterView`] of the given [`FilterType`] in [`ListView::filter_views`].
+fn filter_view_index(filter_type: &FilterType) -> usize
+{
+    match filter_type
+    {
+        FilterType::Date => 0,
+        FilterType::Key => 1,
+        FilterType::Value => 2,
+    }
+}
+
+/// The height a day with the given amount of shown entries takes up in the list.
+pub fn day_height(entry_count: usize) -> f32
+{
+    DAY_SPACING + DATE_ROW_HEIGHT + entry_count as f32 * ENTRY_ROW_HEIGHT
+}
+
+/// Returns the part of the given value that fits into its single row of the list: its first
+/// line, cut after [`MAX_LIST_VALUE_CHARS`] characters. Returns None if the whole value fits.
+pub fn clip_list_value(value: &str) -> Option<&str>
+{
+    let line_end = value.find(['\n', '\r']).unwrap_or(value.len());
+    let end = value[..line_end].char_indices()
+        .nth(MAX_LIST_VALUE_CHARS)
+        .map_or(line_end, |(index, _)| index);
+
+    (end < value.len()).then(|| &value[..end])
 }
\ No newline at end of file
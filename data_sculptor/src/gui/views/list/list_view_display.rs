@@ -1,16 +1,29 @@
 //! Module implementing the display functions for the [`ListView`]
 
+use std::ops::Range;
 use std::sync::{Mutex};
-use iced::{Color, Element, Length};
-use iced::widget::{button, Column, Container, Row, Scrollable, Space, Text};
+use iced::{Alignment, Color, Element, Length, Theme};
+use iced::widget::{button, pick_list, progress_bar, rich_text, span, text_input, Column, Container, Row, Scrollable, Space, Text};
+use iced::widget::text::{Rich, Span, Wrapping};
+use crate::core::data_containers::{DateKey, DATE_FORMAT};
 use crate::core::data_manager::DataManager;
-use crate::core::filters::filter;
 use crate::core::filters::filter::{FilterType};
+use crate::core::filters::filter_matches;
+use crate::core::filters::filter_matches::FilterMatches;
+use crate::core::settings;
+use crate::file_io::exporter::ExportFormat;
+use crate::file_io::text_formats::TextFormat;
 use crate::gui::gui_message::GUIMessage;
+use crate::core::palette::Palette;
 use crate::gui::gui_style;
-use crate::gui::views::list::list_view_control::ListView;
+use crate::gui::views::list::list_view_control::{clip_list_value, DATE_ROW_HEIGHT, DAY_SPACING, ENTRY_ROW_HEIGHT, ListView};
+use crate::gui::views::list::list_view_editing::EditTarget;
 use crate::gui::views::menu::menu_view_control::MenuView;
 
+/// Maximum number of search results that are displayed at once.
+pub const MAX_SHOWN_SEARCH_RESULTS: usize = 200;
+const SEARCH_RESULTS_HEIGHT: f32 = 200.0;
+
 /// Implementation of the display functions for the list view
 impl ListView
 {
@@ -22,6 +35,12 @@ impl ListView
             return self.get_filter_view(filter_view).view();
         }
 
+        // SHOW DAY VIEW IF A DAY IS OPENED
+        if self.day_view.is_open()
+        {
+            return self.display_day(data_manager);
+        }
+
         //TOP ROW
         let top_row: Element<GUIMessage> = Row::new()
             .push
@@ -34,6 +53,7 @@ impl ListView
             .padding(8).into();
 
         //SECOND ROW
+        let is_dirty = data_manager.lock().unwrap().dirty;
         let second_row: Element<GUIMessage> = Row::new()
             .push
             (
@@ -61,6 +81,27 @@ impl ListView
                 Space::with_width(Length::FillPortion(10))
             )
             .push
+            (
+                button(if self.show_table {"List view"} else {"Table view"})
+                    .on_press(GUIMessage::ToggleTableView)
+                    .padding(10)
+                    .style(gui_style::ButtonStyle::style)
+            )
+            .push
+            (
+                button("Add day")
+                    .on_press(GUIMessage::StartAddDay)
+                    .padding(10)
+                    .style(gui_style::ButtonStyle::style)
+            )
+            .push
+            (
+                button(if is_dirty {"Save*"} else {"Save"})
+                    .on_press_maybe(is_dirty.then_some(GUIMessage::SaveData))
+                    .padding(10)
+                    .style(gui_style::ButtonStyle::style)
+            )
+            .push
             (
                 button("Save as")
                     .on_press(GUIMessage::SaveFile)
@@ -68,6 +109,18 @@ impl ListView
                     .style(gui_style::ButtonStyle::style)
             )
             .push
+            (
+                pick_list(TextFormat::ALL, None::<TextFormat>, GUIMessage::CopyFiltered)
+                    .placeholder("Copy as")
+                    .padding(10)
+            )
+            .push
+            (
+                pick_list(ExportFormat::ALL, None::<ExportFormat>, GUIMessage::ExportData)
+                    .placeholder("Export as")
+                    .padding(10)
+            )
+            .push
             (
                 button("Select file")
                     .on_press(GUIMessage::SelectFile)
@@ -85,13 +138,16 @@ impl ListView
             .style(gui_style::container_bar_style);
 
         let msg_container = self.build_message_container();
-        let data_list_display = self.display_list(data_manager);
+        let search_panel = self.search_panel(data_manager);
+        let data_list_display = if self.show_table {self.display_table(data_manager)}
+            else {self.display_list(data_manager)};
 
         Column::new()
             .push(Space::with_height(3))
             .push(top_row)
             .push(Space::with_height(3))
             .push(second_row_container)
+            .push(search_panel)
             .push(Space::with_height(20))
             .push(msg_container)
             .push(data_list_display)
@@ -106,80 +162,320 @@ impl ListView
     /// 2. If the *key* filter does not match at least one key filter, only the *key* is skipped
     ///
     /// 3. If the *value* filter does not match, the *whole day* is skipped
-    fn display_list(&self, data_manager: &Mutex<DataManager>) -> Element<GUIMessage>
+    ///
+    /// The text matched by the filters is highlighted.
+    ///
+    /// The filtering itself is cached in the [`FilteredIndex`]. Only the days inside the
+    /// visible part of the list are built, the rest is replaced by empty space of the same height.
+    /// To make that height predictable, every row has a fixed height and values are not wrapped.
+    /// Values that do not fit into their row are clipped with an ellipsis and a button that
+    /// opens the day in the [`DayView`](crate::gui::views::day::day_view_control::DayView),
+    /// which shows them in full.
+    fn display_list(&self, data_manager: &Mutex<DataManager>) -> Element<'_, GUIMessage>
     {
-        let date_color = Color::new(0.4, 0.8, 0.5, 1.0);
-        let value_color = Color::new(0.6, 0.8, 1.0, 1.0);
+        let date_color = self.palette.date;
+        let value_color = self.palette.value;
 
-        let mut column: Column<GUIMessage> = Column::new().spacing(10);
+        let visible_range = self.visible_day_range();
+        let top_space = self.day_offsets[visible_range.start];
+        let bottom_space = self.day_offsets[self.filtered_index.days.len()]
+            - self.day_offsets[visible_range.end];
 
-        let date_filters = self.get_filters(&FilterType::Date);
-        let key_filters = self.get_filters(&FilterType::Key);
-        let value_filters = self.get_filters(&FilterType::Value);
+        let mut column: Column<GUIMessage> = Column::new()
+            .push(Space::with_height(Length::Fixed(top_space)));
 
-        for day in &data_manager.lock().unwrap().data
+        let data = &data_manager.lock().unwrap().data;
+        for filtered_day in &self.filtered_index.days[visible_range]
         {
-            if !filter::filter_day(&day, date_filters, value_filters)
-            {
-                continue; // skip filtered days (based on date and value filters)
-            }
+            let Some(day) = data.get(filtered_day.day_index) else {continue};
+
+            let date = &day.date.date_string;
+            let matches = self.find_matches(day);
 
             // DATE
-            let date_text = Text::new(day.date.date_string.clone())
+            let date_text = date_text(&day.date, &self.date_format, &matches, &self.palette)
                 .size(20)
                 .color(date_color);
 
             column = column
-                .push(Space::with_height(Length::Fixed(10.0)))
-                .push(date_text);
+                .push(Space::with_height(Length::Fixed(DAY_SPACING)))
+                .push
+                (
+                    Container::new(self.date_row(date, date_text))
+                        .height(Length::Fixed(DATE_ROW_HEIGHT))
+                        .align_y(Alignment::End)
+                );
 
             // ENTRIES
-            let mut entries_column = Column::new().spacing(10);
-            for (key, value) in &day.entries
+            for &entry_index in &filtered_day.entry_indices
             {
-                if !filter::filter_key(day, key, key_filters)
-                {
-                    continue; // do not show keys that are filtered out
-                }
+                let Some((key, value)) = day.entries.get_index(entry_index) else {continue};
+
+                let key_text = Row::new()
+                    .push(Space::with_width(Length::Fixed(40.0)))
+                    .push(highlighted_text(&key.title, &matches.key_ranges(key), &self.palette))
+                    .push(Text::new(":"));
+
+                let value_element: Element<GUIMessage> =
+                    if self.is_editing_value(date, &key.title)
+                    {
+                        text_input("Value", &self.editor.value_input)
+                            .on_input(GUIMessage::EditValueInputChanged)
+                            .on_submit(GUIMessage::ConfirmEdit)
+                            .padding(2)
+                            .width(Length::Fixed(400.0))
+                            .into()
+                    }
+                    else
+                    {
+                        let clipped = clip_list_value(&value.string_value);
+                        let shown_value = clipped.unwrap_or(&value.string_value);
+                        let ranges: Vec<Range<usize>> = matches.value_ranges(key).into_iter()
+                            .filter(|range| range.start < shown_value.len())
+                            .map(|range| range.start..range.end.min(shown_value.len()))
+                            .collect();
+
+                        let value_text = highlighted_text(shown_value, &ranges, &self.palette)
+                            .color(value_color)
+                            .wrapping(Wrapping::None);
+                        let closing_quote = if clipped.is_some() {"…\""} else {"\""};
+
+                        let value_button = button
+                            (
+                                Row::new()
+                                    .push(Text::new("\"").color(value_color))
+                                    .push(value_text)
+                                    .push(Text::new(closing_quote).color(value_color))
+                            )
+                            .on_press(GUIMessage::StartEditValue(date.clone(), key.title.clone()))
+                            .padding([2, 5])
+                            .style(gui_style::TextButtonStyle::style);
 
-                let key_text = Text::new(format!("        {}:", key.title));
-                let value_text = Text::new(format!("   \"{}\"", value.string_value))
-                    .color(value_color);
-                let value_container = Container::new(value_text); // For wrapping
+                        match clipped
+                        {
+                            Some(_) =>
+                                {
+                                    Row::new()
+                                        .spacing(10)
+                                        .align_y(Alignment::Center)
+                                        .push(value_button)
+                                        .push(small_button("Show all", GUIMessage::OpenDay(date.clone()),
+                                            gui_style::ButtonStyle::style))
+                                        .into()
+                                }
+                            None => {value_button.into()}
+                        }
+                    };
 
-                entries_column = entries_column.push
+                let delete_button = button(Text::new("X").size(12))
+                    .on_press(GUIMessage::DeleteKey(date.clone(), key.title.clone()))
+                    .padding([2, 6])
+                    .style(gui_style::DeleteButtonStyle::style);
+
+                column = column.push
                 (
                     Row::new()
                         .push(key_text)
-                        .push(value_container)
+                        .push(value_element)
+                        .push(delete_button)
+                        .spacing(10)
+                        .height(Length::Fixed(ENTRY_ROW_HEIGHT))
+                        .align_y(Alignment::Center)
                 );
             }
-            column = column.push(entries_column);
         }
 
+        column = column.push(Space::with_height(Length::Fixed(bottom_space)));
 
         Scrollable::new
             (
                 Container::new(column)
                     .center_x(Length::Fill)
             )
+            .id(self.list_scroll_id.clone())
+            .on_scroll(GUIMessage::ListScrolled)
             .into()
     }
 
+    /// Displays the day opened in the [`DayView`] with the entries the list shows for it.
+    fn display_day(&self, data_manager: &Mutex<DataManager>) -> Element<'_, GUIMessage>
+    {
+        let data = &data_manager.lock().unwrap().data;
+        let day_index = self.day_view.day_index(data);
+        let shown_entries = self.filtered_index.days.iter()
+            .find(|day| Some(day.day_index) == day_index)
+            .map(|day| day.entry_indices.as_slice());
+
+        self.day_view.view(data, shown_entries)
+    }
+
+    /// Builds the search input and, while something is searched, the values that were found.
+    /// Clicking a result scrolls the list to its day.
+    fn search_panel(&self, data_manager: &Mutex<DataManager>) -> Element<'_, GUIMessage>
+    {
+        let search_row = Row::new()
+            .spacing(20)
+            .align_y(Alignment::Center)
+            .push
+            (
+                text_input("Search all values (\"phrase\", prefix*)", &self.search.input)
+                    .id(self.search.input_id.clone())
+                    .on_input(GUIMessage::SearchInputChanged)
+                    .padding(10)
+                    .width(Length::Fixed(500.0))
+            )
+            .push(small_button("Clear", GUIMessage::ClearSearch, gui_style::ButtonStyle::style));
+
+        let mut column: Column<GUIMessage> = Column::new()
+            .spacing(10)
+            .padding(20)
+            .push(search_row);
+
+        if self.search.input.trim().is_empty()
+        {
+            return column.into();
+        }
+
+        let results = &self.search.results;
+        let summary = match results.len()
+        {
+            0 => {String::from("No results")}
+            1 => {String::from("1 result")}
+            count if count > MAX_SHOWN_SEARCH_RESULTS =>
+                {
+                    format!("{} results, showing the first {}", count, MAX_SHOWN_SEARCH_RESULTS)
+                }
+            count => {format!("{} results", count)}
+        };
+        column = column.push(Text::new(summary).color(self.palette.muted_text));
+
+        if let Some(notice) = &self.search.notice
+        {
+            column = column.push(Text::new(notice.clone()).color(self.palette.error));
+        }
+
+        let data = &data_manager.lock().unwrap().data;
+        let mut result_column: Column<GUIMessage> = Column::new();
+        for hit in results.iter().take(MAX_SHOWN_SEARCH_RESULTS)
+        {
+            let Some(day) = data.get(hit.day_index) else {continue};
+            let Some((key, value)) = day.entries.get_index(hit.entry_index) else {continue};
+
+            let result_row = Row::new()
+                .spacing(10)
+                .push(date_text(&day.date, &self.date_format, &FilterMatches::default(), &self.palette)
+                    .color(self.palette.date))
+                .push(Text::new(format!("{}:", key.title)))
+                .push(highlighted_text(&value.string_value, &hit.ranges, &self.palette)
+                    .color(self.palette.value)
+                    .wrapping(Wrapping::None));
+
+            result_column = result_column.push
+            (
+                button(result_row)
+                    .on_press(GUIMessage::JumpToDay(day.date.date_string.clone()))
+                    .padding([2, 5])
+                    .style(gui_style::TextButtonStyle::style)
+            );
+        }
+
+        column
+            .push
+            (
+                Scrollable::new(result_column)
+                    .height(Length::Fixed(SEARCH_RESULTS_HEIGHT))
+                    .width(Length::Fill)
+            )
+            .into()
+    }
+
+    /// Builds the row holding the date of a day and the actions to edit it. Clicking the date
+    /// opens the day in the [`DayView`](crate::gui::views::day::day_view_control::DayView).
+    /// Also holds the inputs for a new key-value pair while one is being added to the day.
+    fn date_row<'a>(&self, date: &str, date_text: Rich<'a, GUIMessage>) -> Element<'a, GUIMessage>
+    {
+        let date_button = button(date_text)
+            .on_press(GUIMessage::OpenDay(String::from(date)))
+            .padding([2, 5])
+            .style(gui_style::TextButtonStyle::style);
+
+        let row = Row::new()
+            .push(date_button)
+            .spacing(10)
+            .align_y(Alignment::Center);
+
+        if self.is_adding_key(date)
+        {
+            return row
+                .push
+                (
+                    text_input("Key", &self.editor.key_input)
+                        .on_input(GUIMessage::EditKeyInputChanged)
+                        .on_submit(GUIMessage::ConfirmEdit)
+                        .padding(2)
+                        .width(Length::Fixed(200.0))
+                )
+                .push
+                (
+                    text_input("Value", &self.editor.value_input)
+                        .on_input(GUIMessage::EditValueInputChanged)
+                        .on_submit(GUIMessage::ConfirmEdit)
+                        .padding(2)
+                        .width(Length::Fixed(300.0))
+                )
+                .push(small_button("Add", GUIMessage::ConfirmEdit, gui_style::ButtonStyle::style))
+                .push(small_button("Cancel", GUIMessage::CancelEdit, gui_style::ButtonStyle::style))
+                .into();
+        }
+
+        row
+            .push(small_button("+ Key", GUIMessage::StartAddKey(String::from(date)),
+                               gui_style::ButtonStyle::style))
+            .push(small_button("Delete day", GUIMessage::DeleteDay(String::from(date)),
+                               gui_style::DeleteButtonStyle::style))
+            .into()
+    }
+
+    /// Displays the data in the given [`DataManager`] filtered by all [`FilterView`]s
+    /// in the [`TableView`]. Only keys that match the key filters are shown as columns.
+    fn display_table(&self, data_manager: &Mutex<DataManager>) -> Element<'_, GUIMessage>
+    {
+        let data = &data_manager.lock().unwrap().data;
+        let days = self.filtered_index.resolve(data);
+
+        self.table_view.view(days, &self.filtered_index.keys, |day| self.find_matches(day))
+    }
+
     /// Builds the message container with the correct error message based on the state
     /// of the given [`ListView`].
     pub fn build_message_container(&self) -> Element<GUIMessage>
     {
         let mut msg_column: Column<GUIMessage> = Column::new().spacing(20);
 
+        if let Some(EditTarget::NewDay) = self.editor.target
+        {
+            msg_column = msg_column.push(self.new_day_row());
+        }
+
+        if let Some(error) = &self.editor.error
+        {
+            msg_column = msg_column.push(Text::new(error.clone()).color(self.palette.error));
+        }
+
         if !self.loaded_valid_file
         {
-            msg_column = file_load_error(msg_column, self.load_error_msg.clone());
+            msg_column = file_load_error(msg_column, self.load_error_msg.clone(), self.palette.error);
         }
 
-        else if self.loading_file
+        // Reloading happens unnoticed in the background
+        else if self.loading_file && !self.reloading
         {
-            msg_column = loading_message(msg_column);
+            msg_column = loading_message(msg_column, self.load_progress);
+        }
+
+        if let Some(notice) = &self.notice
+        {
+            msg_column = msg_column.push(Text::new(notice.clone()).color(self.palette.muted_text));
         }
 
         Container::new(msg_column)
@@ -188,11 +484,68 @@ impl ListView
     }
 }
 
-fn file_load_error(msg_column: Column<GUIMessage>, error_msg: String)
-                   -> Column<GUIMessage>
+impl ListView
+{
+    /// Builds the row holding the inputs to add a new day.
+    fn new_day_row(&self) -> Element<'_, GUIMessage>
+    {
+        Row::new()
+            .align_y(Alignment::Center)
+            .spacing(20)
+            .push
+            (
+                text_input("YYYY-MM-DD", &self.editor.key_input)
+                    .on_input(GUIMessage::EditKeyInputChanged)
+                    .on_submit(GUIMessage::ConfirmEdit)
+                    .padding(10)
+                    .width(Length::Fixed(200.0))
+            )
+            .push(small_button("Add day", GUIMessage::ConfirmEdit, gui_style::ButtonStyle::style))
+            .push(small_button("Cancel", GUIMessage::CancelEdit, gui_style::ButtonStyle::style))
+            .into()
+    }
+}
+
+/// Builds a text of the given string with the given byte ranges highlighted.
+/// See [`filter_matches::split_by_ranges`].
+pub(crate) fn highlighted_text<'a>(text: &str, ranges: &[Range<usize>], palette: &Palette) -> Rich<'a, GUIMessage>
 {
-    let error_color = Color::new(1.0, 0.2, 0.2, 1.0);
+    let spans: Vec<Span<'a, GUIMessage>> = filter_matches::split_by_ranges(text, ranges).into_iter()
+        .map(|(piece, matched)|
+            {
+                let piece_span = span(piece.to_string());
+                if matched {piece_span.background(gui_style::match_highlight_background(palette))} else {piece_span}
+            })
+        .collect();
 
+    rich_text(spans)
+}
+
+/// Builds a text of the given date in the given display format. Its matches are only
+/// highlighted if the format is the one the matches were found in.
+pub(crate) fn date_text<'a>(date: &DateKey, format: &str, matches: &FilterMatches, palette: &Palette)
+    -> Rich<'a, GUIMessage>
+{
+    if format == DATE_FORMAT
+    {
+        return highlighted_text(&date.date_string, &matches.date_ranges(), palette);
+    }
+    highlighted_text(&settings::format_date(date, format), &[], palette)
+}
+
+fn small_button<'a>(title: &'a str, on_press: GUIMessage,
+                    style: fn(&Theme, button::Status) -> button::Style) -> Element<'a, GUIMessage>
+{
+    button(Text::new(title).size(14))
+        .on_press(on_press)
+        .padding([3, 8])
+        .style(style)
+        .into()
+}
+
+fn file_load_error(msg_column: Column<GUIMessage>, error_msg: String, error_color: Color)
+                   -> Column<GUIMessage>
+{
     msg_column
         .push
         (
@@ -203,12 +556,26 @@ fn file_load_error(msg_column: Column<GUIMessage>, error_msg: String)
         .push(Text::new(error_msg).size(15).color(error_color))
 }
 
-fn loading_message(msg_column: Column<GUIMessage>) -> Column<GUIMessage>
+fn loading_message(msg_column: Column<GUIMessage>, progress: f32) -> Column<GUIMessage>
 {
+    let progress_row = Row::new()
+        .align_y(Alignment::Center)
+        .spacing(20)
+        .push(progress_bar(0.0..=1.0, progress).width(Length::Fixed(300.0)))
+        .push(Text::new(format!("{:.0}%", progress * 100.0)))
+        .push
+        (
+            button("Cancel")
+                .on_press(GUIMessage::CancelLoading)
+                .padding(10)
+                .style(gui_style::DeleteButtonStyle::style)
+        );
+
     msg_column
         .push
         (
             Text::new("Loading..")
                 .size(25)
         )
+        .push(progress_row)
 }
\ No newline at end of file
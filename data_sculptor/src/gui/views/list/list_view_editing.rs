@@ -0,0 +1,148 @@
+//! Module implementing the editing functions for the [`ListView`]
+
+use std::sync::Mutex;
+use iced::Task;
+use indexmap::IndexMap;
+use crate::core::data_edit::DataEdit;
+use crate::core::data_manager::DataManager;
+use crate::gui::gui_message::GUIMessage;
+use crate::gui::history::HistoryCommand;
+use crate::gui::views::list::list_view_control::ListView;
+
+/// The part of the data that is currently being edited in the [`ListView`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditTarget
+{
+    /// The value of an existing key.
+    Value {date: String, key: String},
+
+    /// A new key-value pair for the day with the given date.
+    NewKey {date: String},
+
+    /// A new day.
+    NewDay
+}
+
+/// Holds the state of the edit that is currently in progress.
+#[derive(Default)]
+pub struct EntryEditor
+{
+    pub target: Option<EditTarget>,
+    pub key_input: String,
+    pub value_input: String,
+
+    /// Message of the last edit that could not be applied.
+    pub error: Option<String>
+}
+
+/// Implementation of the editing functions for the list view
+impl ListView
+{
+    /// Handles all messages related to editing the data.
+    pub(crate) fn update_editing(&mut self, message: GUIMessage, dm: &Mutex<DataManager>)
+        -> Task<GUIMessage>
+    {
+        match message
+        {
+            GUIMessage::StartEditValue(date, key) =>
+                {
+                    let value = dm.lock().unwrap().get_value(&date, &key).unwrap_or_default();
+                    self.start_edit(EditTarget::Value {date, key}, value)
+                }
+            GUIMessage::StartAddKey(date) => {self.start_edit(EditTarget::NewKey {date}, String::new())}
+            GUIMessage::StartAddDay => {self.start_edit(EditTarget::NewDay, String::new())}
+            GUIMessage::EditKeyInputChanged(input) => {self.editor.key_input = input; Task::none()}
+            GUIMessage::EditValueInputChanged(input) => {self.editor.value_input = input; Task::none()}
+            GUIMessage::ConfirmEdit => {self.confirm_edit(dm)}
+            GUIMessage::CancelEdit => {self.editor = EntryEditor::default(); Task::none()}
+            GUIMessage::DeleteKey(date, key) => {self.apply_edit(DataEdit::RemoveKey {date, key}, dm)}
+            GUIMessage::DeleteDay(date) => {self.apply_edit(DataEdit::RemoveDay {date}, dm)}
+            GUIMessage::SaveData => {self.save_data(dm)}
+            _ => {Task::none()}
+        }
+    }
+
+    fn start_edit(&mut self, target: EditTarget, value: String) -> Task<GUIMessage>
+    {
+        self.editor = EntryEditor
+        {
+            target: Some(target),
+            key_input: String::new(),
+            value_input: value,
+            error: None
+        };
+        Task::none()
+    }
+
+    /// Turns the current inputs into a [`DataEdit`] for the current [`EditTarget`] and applies it.
+    fn confirm_edit(&mut self, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        let Some(target) = self.editor.target.clone() else {return Task::none()};
+
+        let edit = match target
+        {
+            EditTarget::Value {date, key} =>
+                {
+                    DataEdit::SetValue {date, key, value: self.editor.value_input.clone()}
+                }
+
+            EditTarget::NewKey {date} =>
+                {
+                    DataEdit::InsertKey
+                    {
+                        date,
+                        key: self.editor.key_input.clone(),
+                        value: self.editor.value_input.clone(),
+                        index: usize::MAX
+                    }
+                }
+
+            EditTarget::NewDay =>
+                {
+                    let date = self.editor.key_input.trim().to_string();
+                    DataEdit::InsertDay {date, entries: IndexMap::new()}
+                }
+        };
+
+        self.apply_edit(edit, dm)
+    }
+
+    /// Applies the given edit to the data. Ends the current edit if it succeeded,
+    /// otherwise keeps it open and shows the error.
+    fn apply_edit(&mut self, edit: DataEdit, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        match self.apply_data_edit(edit, dm)
+        {
+            Ok(inverse) =>
+                {
+                    self.history.record(HistoryCommand::Data(inverse));
+                    self.editor = EntryEditor::default();
+                }
+            Err(e) => {self.editor.error = Some(e.to_string());}
+        }
+        Task::none()
+    }
+
+    /// Writes the data back to the file it was loaded from.
+    fn save_data(&mut self, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        match dm.lock().unwrap().save_data()
+        {
+            Ok(_) => {self.editor.error = None;}
+            Err(e) => {self.editor.error = Some(e);}
+        }
+        Task::none()
+    }
+
+    /// Whether the value of the given key of the given day is currently being edited.
+    pub(crate) fn is_editing_value(&self, date: &str, key: &str) -> bool
+    {
+        matches!(&self.editor.target, Some(EditTarget::Value {date: d, key: k}) if d == date && k == key)
+    }
+
+    /// Whether a new key is currently being added to the given day.
+    pub(crate) fn is_adding_key(&self, date: &str) -> bool
+    {
+        matches!(&self.editor.target, Some(EditTarget::NewKey {date: d}) if d == date)
+    }
+}
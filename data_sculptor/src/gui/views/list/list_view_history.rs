@@ -0,0 +1,61 @@
+//! Module implementing the undo and redo functions for the [`ListView`]
+
+use std::sync::Mutex;
+use iced::Task;
+use crate::core::data_manager::DataManager;
+use crate::gui::gui_message::GUIMessage;
+use crate::gui::history::HistoryCommand;
+use crate::gui::views::list::list_view_control::ListView;
+use crate::gui::views::list::list_view_editing::EntryEditor;
+
+/// Implementation of the undo and redo functions for the list view
+impl ListView
+{
+    /// Reverts the latest recorded change.
+    pub(crate) fn undo(&mut self, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        let Some(command) = self.history.undo() else {return Task::none()};
+
+        if let Some(redo) = self.apply_command(command, dm)
+        {
+            self.history.undone(redo);
+        }
+        Task::none()
+    }
+
+    /// Repeats the latest undone change.
+    pub(crate) fn redo(&mut self, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        let Some(command) = self.history.redo() else {return Task::none()};
+
+        if let Some(inverse) = self.apply_command(command, dm)
+        {
+            self.history.redone(inverse);
+        }
+        Task::none()
+    }
+
+    /// Applies the given [`HistoryCommand`] and returns the command that reverts it.
+    /// Returns None if it could no longer be applied.
+    fn apply_command(&mut self, command: HistoryCommand, dm: &Mutex<DataManager>)
+        -> Option<HistoryCommand>
+    {
+        match command
+        {
+            HistoryCommand::Filter(filter_type, change) =>
+                {
+                    let inverse = self.get_filter_view_mut(&filter_type).apply_change(change)?;
+                    Some(HistoryCommand::Filter(filter_type, inverse))
+                }
+
+            HistoryCommand::Data(edit) =>
+                {
+                    // An open editor could refer to data that the edit removes
+                    self.editor = EntryEditor::default();
+
+                    let inverse = self.apply_data_edit(edit, dm).ok()?;
+                    Some(HistoryCommand::Data(inverse))
+                }
+        }
+    }
+}
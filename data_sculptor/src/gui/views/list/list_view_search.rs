@@ -0,0 +1,138 @@
+//! Module implementing the full-text search of the [`ListView`]
+
+use std::sync::Mutex;
+use iced::Task;
+use iced::widget::{scrollable, text_input};
+use iced::widget::scrollable::AbsoluteOffset;
+use crate::core::data_edit::{DataEdit, EditError};
+use crate::core::data_manager::DataManager;
+use crate::core::search_index::{SearchHit, SearchIndex, SearchQuery};
+use crate::gui::gui_message::GUIMessage;
+use crate::gui::views::list::list_view_control::ListView;
+
+/// Holds the state of the search over all values of the data.
+/// See [`SearchQuery`] for the syntax of the input.
+pub struct ListSearch
+{
+    pub input: String,
+    pub input_id: text_input::Id,
+
+    /// Values that contain the input, sorted by date.
+    pub results: Vec<SearchHit>,
+
+    /// Shown when a result can not be jumped to.
+    pub notice: Option<String>,
+
+    index: SearchIndex,
+
+    /// The data generation the [`SearchIndex`] was built from.
+    indexed_generation: Option<u64>,
+
+    /// The input and data generation the results were found for.
+    searched_revision: Option<(String, u64)>
+}
+
+impl Default for ListSearch
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            input: String::new(),
+            input_id: text_input::Id::unique(),
+            results: Vec::new(),
+            notice: None,
+            index: SearchIndex::default(),
+            indexed_generation: None,
+            searched_revision: None
+        }
+    }
+}
+
+/// Implementation of the search functions for the list view
+impl ListView
+{
+    /// Handles all messages related to the search.
+    pub(crate) fn update_search(&mut self, message: GUIMessage, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        match message
+        {
+            GUIMessage::SearchInputChanged(input) => {self.search.input = input; self.search.notice = None;}
+            GUIMessage::ClearSearch => {self.search.input.clear(); self.search.notice = None;}
+            GUIMessage::FocusSearch => {return text_input::focus(self.search.input_id.clone());}
+            GUIMessage::JumpToDay(date) => {return self.jump_to_day(date, dm);}
+            _ => {}
+        }
+        Task::none()
+    }
+
+    /// Searches the [`SearchIndex`] again whenever the data or the input changed.
+    /// The index is built along with loaded data and updated by [`Self::apply_data_edit`],
+    /// it is only rebuilt here if the data changed in any other way.
+    pub fn refresh_search(&mut self, dm: &Mutex<DataManager>)
+    {
+        let data_manager = dm.lock().unwrap();
+        if self.search.indexed_generation != Some(data_manager.generation)
+        {
+            self.search.index = SearchIndex::build(&data_manager.data);
+            self.search.indexed_generation = Some(data_manager.generation);
+        }
+
+        let revision = Some((self.search.input.clone(), data_manager.generation));
+        if self.search.searched_revision == revision
+        {
+            return;
+        }
+
+        let query = SearchQuery::parse(&self.search.input);
+        self.search.results = self.search.index.search(&query, &data_manager.data);
+        self.search.searched_revision = revision;
+    }
+
+    /// Uses the given [`SearchIndex`] built from the data of the given generation.
+    pub(crate) fn set_search_index(&mut self, index: SearchIndex, generation: u64)
+    {
+        self.search.index = index;
+        self.search.indexed_generation = Some(generation);
+    }
+
+    /// Applies the given [`DataEdit`] to the data and updates the [`SearchIndex`] for the
+    /// changed day, instead of rebuilding it. Returns the edit that reverts the applied one.
+    pub(crate) fn apply_data_edit(&mut self, edit: DataEdit, dm: &Mutex<DataManager>) -> Result<DataEdit, EditError>
+    {
+        let mut data_manager = dm.lock().unwrap();
+        let indexed = self.search.indexed_generation == Some(data_manager.generation);
+        let date = edit.date().to_string();
+        let old_day = data_manager.find_day(&date).map(|index| (index, data_manager.data[index].clone()));
+
+        let inverse = data_manager.apply_edit(edit)?;
+
+        if indexed
+        {
+            let new_day = data_manager.find_day(&date).map(|index| (index, &data_manager.data[index]));
+            self.search.index.update_day(old_day.as_ref().map(|(index, day)| (*index, day)), new_day);
+            self.search.indexed_generation = Some(data_manager.generation);
+        }
+        Ok(inverse)
+    }
+
+    /// Scrolls the list to the day with the given date. Days that are hidden by the filters
+    /// can not be jumped to.
+    fn jump_to_day(&mut self, date: String, dm: &Mutex<DataManager>) -> Task<GUIMessage>
+    {
+        let data = &dm.lock().unwrap().data;
+        let position = self.filtered_index.days.iter()
+            .position(|day| data[day.day_index].date.date_string == date);
+
+        let Some(index) = position else
+        {
+            self.search.notice = Some(format!("{} is hidden by the filters", date));
+            return Task::none();
+        };
+
+        self.show_table = false;
+        self.search.notice = None;
+        let y = self.day_offsets[index];
+        scrollable::scroll_to(self.list_scroll_id.clone(), AbsoluteOffset {x: 0.0, y})
+    }
+}
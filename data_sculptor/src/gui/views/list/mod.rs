@@ -1,4 +1,7 @@
 //! Module containing the list view and its utility modules.
 
 pub mod list_view_control;
-mod list_view_display;
\ No newline at end of file
+pub(crate) mod list_view_display;
+pub mod list_view_editing;
+pub mod list_view_history;
+pub mod list_view_search;
\ No newline at end of file
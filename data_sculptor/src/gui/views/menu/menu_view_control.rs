@@ -1,26 +1,49 @@
 //! Module implementing the control functions for the [`MenuView`]
 
+use std::collections::HashSet;
+use std::path::Path;
 use iced::{Task};
+use crate::core::settings::Settings;
 use crate::gui::gui_message::GUIMessage;
 
 /// The main menu of the data_sculptor application.
+#[derive(Default)]
 pub struct MenuView
 {
-
+    /// The recent files that did not exist when they were last checked.
+    /// See [`MenuView::check_recent_files`].
+    pub missing_files: HashSet<String>
 }
 
 /// Implementation of the control functions for the menu view
 impl MenuView
 {
     // UPDATE
-    pub fn update(&mut self, message: GUIMessage) -> Task<GUIMessage>
+    /// Changes to the recent files are applied to the given [`Settings`].
+    pub fn update(&mut self, message: GUIMessage, settings: &mut Settings) -> Task<GUIMessage>
     {
         match message
         {
+            GUIMessage::RemoveRecentFile(path) =>
+                {
+                    settings.recent_files.retain(|recent| *recent != path);
+                    self.missing_files.remove(&path);
+                    Task::none()
+                }
             _ => {Task::none()}
         }
     }
 
+    /// Checks which of the given recent files no longer exist. Called when the menu is opened
+    /// or the recent files change, so that the filesystem is not checked on every render.
+    pub fn check_recent_files(&mut self, recent_files: &[String])
+    {
+        self.missing_files = recent_files.iter()
+            .filter(|path| !Path::new(path).exists())
+            .cloned()
+            .collect();
+    }
+
     pub fn view_title() -> &'static str
     {
         "menu_view"
@@ -1,16 +1,22 @@
 //! Module implementing the display functions for the [`MenuView`]
 
-use iced::{Element, Length};
-use iced::widget::{button, Column, Container, Row, Space, Text};
+use std::collections::HashSet;
+use std::path::Path;
+use iced::{Alignment, Element, Length};
+use iced::widget::{button, Column, Container, Row, Scrollable, Space, Text};
+use crate::core::settings::Settings;
 use crate::gui::gui_message::GUIMessage;
+use crate::core::palette::Palette;
 use crate::gui::gui_style;
 use crate::gui::views::list::list_view_control::ListView;
 use crate::gui::views::menu::menu_view_control::MenuView;
+use crate::gui::views::settings::settings_view_control::SettingsView;
 
 /// Implementation of the display functions for the menu view
 impl MenuView
 {
-    pub fn view(&self) -> Element<GUIMessage>
+    /// Shows the recent files of the given [`Settings`] below the menu buttons.
+    pub fn view<'a>(&'a self, settings: &'a Settings, palette: &Palette) -> Element<'a, GUIMessage>
     {
         let top_row: Element<GUIMessage> = Row::new()
             .push
@@ -24,7 +30,14 @@ impl MenuView
             .spacing(50).into();
 
         let second_row: Element<GUIMessage> = Row::new()
-            .push(empty_menu_button())
+            .push
+            (
+                menu_button
+                    (
+                        String::from("  Settings"),
+                        GUIMessage::OpenView(SettingsView::view_title())
+                    )
+            )
             .spacing(50).into();
 
         let buttons_column = Column::new()
@@ -42,14 +55,81 @@ impl MenuView
             .padding(20)
             .style(gui_style::container_bar_style);
 
-        Column::new()
+        let mut column = Column::new()
             .push(Space::with_height(Length::FillPortion(3)))
-            .push(buttons_container)
+            .push(buttons_container);
+
+        if !settings.recent_files.is_empty()
+        {
+            column = column
+                .push(Space::with_height(30))
+                .push(recent_files(&settings.recent_files, &self.missing_files, palette));
+        }
+
+        column
             .push(Space::with_height(Length::FillPortion(4)))
             .into()
     }
 }
 
+/// Lists the given recent files. Clicking one loads it in the list view.
+/// The given missing files are marked as such.
+fn recent_files<'a>(paths: &'a [String], missing_files: &HashSet<String>, palette: &Palette) -> Element<'a, GUIMessage>
+{
+    let mut file_column = Column::new()
+        .spacing(5)
+        .push(Text::new("Recent files").size(22));
+
+    for path in paths
+    {
+        let file_name = Path::new(path).file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or(path.clone());
+
+        let mut file_text = Column::new()
+            .push(Text::new(file_name).size(18))
+            .push(Text::new(path.as_str()).size(12).color(palette.muted_text));
+
+        if missing_files.contains(path)
+        {
+            file_text = file_text.push(Text::new("Missing").size(12).color(palette.error));
+        }
+
+        let row = Row::new()
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .push
+            (
+                button(file_text)
+                    .on_press(GUIMessage::FileSelected(path.clone()))
+                    .padding([3, 10])
+                    .width(Length::Fill)
+                    .style(gui_style::TextButtonStyle::style)
+            )
+            .push
+            (
+                button(Text::new("X").size(12))
+                    .on_press(GUIMessage::RemoveRecentFile(path.clone()))
+                    .padding([2, 6])
+                    .style(gui_style::DeleteButtonStyle::style)
+            );
+
+        file_column = file_column.push(row);
+    }
+
+    let container = Container::new(Scrollable::new(file_column))
+        .padding(20)
+        .width(Length::Fixed(600.0))
+        .max_height(400)
+        .style(gui_style::container_bar_style);
+
+    Row::new()
+        .push(Space::with_width(Length::FillPortion(1)))
+        .push(container)
+        .push(Space::with_width(Length::FillPortion(1)))
+        .into()
+}
+
 pub fn menu_button(title: String, on_press: GUIMessage) -> Element<'static, GUIMessage>
 {
     button(Text::new(title).size(32))
@@ -59,9 +139,4 @@ pub fn menu_button(title: String, on_press: GUIMessage) -> Element<'static, GUIM
         .height(Length::Fixed(64.0))
         .style(gui_style::ButtonStyle::style)
         .into()
-}
-
-pub fn empty_menu_button()-> Element<'static, GUIMessage>
-{
-    menu_button(String::new(), GUIMessage::OpenView(""))
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 //! Module holding all gui views supported by data_sculptor.
+pub mod command_palette;
+pub mod day;
 pub mod filter;
 pub mod list;
 pub mod gui_view_type;
-pub mod menu;
\ No newline at end of file
+pub mod menu;
+pub mod settings;
+pub mod table;
\ No newline at end of file
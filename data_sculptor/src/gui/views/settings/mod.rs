@@ -0,0 +1,2 @@
+pub mod settings_view_control;
+mod settings_view_display;
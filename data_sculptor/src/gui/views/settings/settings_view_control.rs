@@ -0,0 +1,138 @@
+//! Module implementing the control functions for the [`SettingsView`]
+
+use iced::{Task};
+use crate::core::settings;
+use crate::core::settings::{Settings, MAX_FONT_SIZE, MIN_FONT_SIZE};
+use crate::file_io::{config_handler, file_dialogs};
+use crate::gui::gui_message::GUIMessage;
+use crate::core::palette::Palette;
+
+/// Lets the user change the [`Settings`] that are kept between runs.
+pub struct SettingsView
+{
+    pub(crate) font_size_input: String,
+    pub(crate) date_format_input: String,
+
+    /// Why the current font size or date format input can not be used.
+    pub(crate) input_error: Option<String>,
+
+    /// Why the config file could not be read or written.
+    pub config_error: Option<String>
+}
+
+/// Implementation of the control functions for the settings view
+impl SettingsView
+{
+    pub fn new(settings: &Settings) -> Self
+    {
+        Self
+        {
+            font_size_input: settings.font_size.to_string(),
+            date_format_input: settings.date_display_format.clone(),
+            input_error: None,
+            config_error: None
+        }
+    }
+
+    // UPDATE
+    /// Changes made by the user are applied to the given [`Settings`].
+    /// Imported palettes are added to the given palettes.
+    pub fn update(&mut self, message: GUIMessage, settings: &mut Settings, palettes: &mut Vec<Palette>)
+        -> Task<GUIMessage>
+    {
+        match message
+        {
+            GUIMessage::SelectTheme(theme) => {settings.theme = theme;}
+            GUIMessage::ImportPalette => {self.import_palette(settings, palettes);}
+            GUIMessage::FontSizeInputChanged(input) => {self.font_size_changed(input, settings);}
+            GUIMessage::DateFormatInputChanged(input) => {self.date_format_changed(input, settings);}
+            GUIMessage::PickDefaultDirectory =>
+                {
+                    if let Some(directory) = file_dialogs::pick_folder(settings.default_directory.as_deref())
+                    {
+                        settings.default_directory = Some(directory);
+                    }
+                }
+            GUIMessage::ClearDefaultDirectory => {settings.default_directory = None;}
+            GUIMessage::ToggleRestoreFilters(restore) => {settings.restore_filters = restore;}
+            GUIMessage::ToggleWatchFile(watch) => {settings.watch_file = watch;}
+            GUIMessage::ToggleReportStatistics(statistics) => {settings.report_statistics = statistics;}
+            GUIMessage::ClearLastOpenedFile => {settings.last_opened_file = None;}
+            GUIMessage::ToggleReopenLastFile(reopen) => {settings.reopen_last_file = reopen;}
+            GUIMessage::ClearRecentFiles => {settings.recent_files.clear();}
+            _ => {}
+        }
+
+        Task::none()
+    }
+
+    /// Lets the user pick a palette file, copies it to the palettes directory and selects it.
+    /// An imported palette replaces a custom palette of the same name.
+    fn import_palette(&mut self, settings: &mut Settings, palettes: &mut Vec<Palette>)
+    {
+        let Some(path) = file_dialogs::pick_file(settings.default_directory.as_deref()) else {return};
+
+        let imported = config_handler::read_palette(path.as_ref())
+            .and_then(|palette| config_handler::save_palette(&palette).map(|_| palette));
+
+        match imported
+        {
+            Ok(palette) =>
+                {
+                    palettes.retain(|existing| existing.name != palette.name);
+                    settings.theme = palette.name.clone();
+                    palettes.push(palette);
+                    self.config_error = None;
+                }
+            Err(e) => {self.config_error = Some(e);}
+        }
+    }
+
+    fn font_size_changed(&mut self, input: String, settings: &mut Settings)
+    {
+        match input.trim().parse::<u16>()
+        {
+            Ok(size) if (MIN_FONT_SIZE..=MAX_FONT_SIZE).contains(&size) =>
+                {
+                    settings.font_size = size;
+                    self.input_error = None;
+                }
+            _ =>
+                {
+                    self.input_error = Some(format!("The font size has to be a number from {} to {}",
+                                                    MIN_FONT_SIZE, MAX_FONT_SIZE));
+                }
+        }
+
+        self.font_size_input = input;
+    }
+
+    fn date_format_changed(&mut self, input: String, settings: &mut Settings)
+    {
+        if settings::is_valid_date_format(&input)
+        {
+            settings.date_display_format = input.clone();
+            self.input_error = None;
+        }
+        else
+        {
+            self.input_error = Some(String::from("The date format is not valid"));
+        }
+
+        self.date_format_input = input;
+    }
+
+    pub fn view_title() -> &'static str
+    {
+        "settings_view"
+    }
+}
+
+/// The palette with the given name. Falls back to the first palette for unknown names.
+/// Expects at least one palette.
+pub fn palette_by_name<'a>(palettes: &'a [Palette], name: &str) -> &'a Palette
+{
+    palettes.iter()
+        .find(|palette| palette.name == name)
+        .unwrap_or(&palettes[0])
+}
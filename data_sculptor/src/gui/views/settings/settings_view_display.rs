@@ -0,0 +1,206 @@
+//! Module implementing the display functions for the [`SettingsView`]
+
+use chrono::NaiveDate;
+use iced::{Alignment, Element, Length};
+use iced::widget::{checkbox, pick_list, text_input, Button, Column, Container, Row, Space, Text};
+use crate::core::settings::Settings;
+use crate::gui::gui_message::GUIMessage;
+use crate::core::palette::Palette;
+use crate::gui::gui_style;
+use crate::gui::views::menu::menu_view_control::MenuView;
+use crate::gui::views::settings::settings_view_control::{palette_by_name, SettingsView};
+
+const LABEL_WIDTH: f32 = 220.0;
+
+/// Implementation of the display functions for the settings view
+impl SettingsView
+{
+    /// Offers the given palettes to choose from.
+    pub fn view<'a>(&'a self, settings: &'a Settings, palettes: &[Palette]) -> Element<'a, GUIMessage>
+    {
+        let palette = palette_by_name(palettes, &settings.theme);
+        let error_color = palette.error;
+        let note_color = palette.muted_text;
+
+        // RETURN
+        let return_button = Button::new("Return to menu")
+            .on_press(GUIMessage::ReturnToView(MenuView::view_title()))
+            .padding(10)
+            .style(gui_style::ButtonStyle::style);
+
+        // THEME
+        let palette_names: Vec<String> = palettes.iter().map(|palette| palette.name.clone()).collect();
+        let theme_row = setting_row
+            (
+                "Theme",
+                Row::new()
+                    .spacing(20)
+                    .align_y(Alignment::Center)
+                    .push(pick_list(palette_names, Some(palette.name.clone()), GUIMessage::SelectTheme))
+                    .push(small_button("Import palette", GUIMessage::ImportPalette))
+                    .into()
+            );
+
+        // FONT SIZE
+        let font_size_row = setting_row
+            (
+                "Font size",
+                Row::new()
+                    .spacing(20)
+                    .align_y(Alignment::Center)
+                    .push
+                    (
+                        text_input("Font size", &self.font_size_input)
+                            .on_input(GUIMessage::FontSizeInputChanged)
+                            .padding(5)
+                            .width(Length::Fixed(80.0))
+                    )
+                    .push(Text::new("Applies after a restart").color(note_color))
+                    .into()
+            );
+
+        // DATE FORMAT
+        let date_example = NaiveDate::from_ymd_opt(2024, 1, 31)
+            .map(|date| date.format(&settings.date_display_format).to_string())
+            .unwrap_or_default();
+
+        let date_format_row = setting_row
+            (
+                "Date display format",
+                Row::new()
+                    .spacing(20)
+                    .align_y(Alignment::Center)
+                    .push
+                    (
+                        text_input("%Y-%m-%d", &self.date_format_input)
+                            .on_input(GUIMessage::DateFormatInputChanged)
+                            .padding(5)
+                            .width(Length::Fixed(200.0))
+                    )
+                    .push(Text::new(format!("e.g. {}", date_example)).color(note_color))
+                    .into()
+            );
+
+        // DEFAULT DIRECTORY
+        let directory = settings.default_directory.clone().unwrap_or(String::from("None"));
+        let directory_row = setting_row
+            (
+                "Default directory",
+                Row::new()
+                    .spacing(20)
+                    .align_y(Alignment::Center)
+                    .push(Text::new(directory))
+                    .push(small_button("Choose", GUIMessage::PickDefaultDirectory))
+                    .push(small_button("Clear", GUIMessage::ClearDefaultDirectory))
+                    .into()
+            );
+
+        // LAST OPENED FILE
+        let last_file = settings.last_opened_file.clone().unwrap_or(String::from("None"));
+        let last_file_row = setting_row
+            (
+                "Last opened file",
+                Column::new()
+                    .spacing(10)
+                    .push
+                    (
+                        Row::new()
+                            .spacing(20)
+                            .align_y(Alignment::Center)
+                            .push(Text::new(last_file))
+                            .push(small_button("Clear", GUIMessage::ClearLastOpenedFile))
+                    )
+                    .push
+                    (
+                        checkbox("Reopen the last opened file on startup", settings.reopen_last_file)
+                            .on_toggle(GUIMessage::ToggleReopenLastFile)
+                    )
+                    .push
+                    (
+                        checkbox("Reload the loaded file when it changes on disk", settings.watch_file)
+                            .on_toggle(GUIMessage::ToggleWatchFile)
+                    )
+                    .into()
+            );
+
+        // RECENT FILES
+        let recent_files_row = setting_row
+            (
+                "Recent files",
+                Row::new()
+                    .spacing(20)
+                    .align_y(Alignment::Center)
+                    .push(Text::new(format!("{} remembered", settings.recent_files.len())))
+                    .push(small_button("Clear", GUIMessage::ClearRecentFiles))
+                    .into()
+            );
+
+        // FILTERS
+        let filters_row = setting_row
+            (
+                "Filters",
+                checkbox("Restore the filters of the last run on startup", settings.restore_filters)
+                    .on_toggle(GUIMessage::ToggleRestoreFilters)
+                    .into()
+            );
+
+        // REPORTS
+        let reports_row = setting_row
+            (
+                "Reports",
+                checkbox("Include summary statistics of every key", settings.report_statistics)
+                    .on_toggle(GUIMessage::ToggleReportStatistics)
+                    .into()
+            );
+
+        let mut settings_column = Column::new()
+            .spacing(20)
+            .push(theme_row)
+            .push(font_size_row)
+            .push(date_format_row)
+            .push(directory_row)
+            .push(last_file_row)
+            .push(recent_files_row)
+            .push(filters_row)
+            .push(reports_row);
+
+        for error in [&self.input_error, &self.config_error].into_iter().flatten()
+        {
+            settings_column = settings_column.push(Text::new(error.clone()).color(error_color));
+        }
+
+        Column::new()
+            .push
+            (
+                Row::new()
+                    .push(return_button)
+                    .padding(8)
+            )
+            .push
+            (
+                Container::new(settings_column)
+                    .padding(20)
+                    .width(Length::Fill)
+                    .style(gui_style::container_bar_style)
+            )
+            .into()
+    }
+}
+
+fn setting_row<'a>(label: &'a str, content: Element<'a, GUIMessage>) -> Element<'a, GUIMessage>
+{
+    Row::new()
+        .align_y(Alignment::Center)
+        .push(Text::new(label).size(20).width(Length::Fixed(LABEL_WIDTH)))
+        .push(content)
+        .push(Space::with_width(Length::Fill))
+        .into()
+}
+
+fn small_button(title: &str, on_press: GUIMessage) -> Button<'_, GUIMessage>
+{
+    Button::new(Text::new(title).size(14))
+        .on_press(on_press)
+        .padding([3, 8])
+        .style(gui_style::ButtonStyle::style)
+}
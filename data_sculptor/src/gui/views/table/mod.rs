@@ -0,0 +1,2 @@
+pub mod table_view_control;
+mod table_view_display;
\ No newline at end of file
@@ -0,0 +1,155 @@
+//! Module implementing the control functions for the [`TableView`]
+
+use std::collections::HashMap;
+use iced::{Point, Task};
+use iced::widget::scrollable;
+use iced::widget::scrollable::AbsoluteOffset;
+use crate::core::data_containers::DATE_FORMAT;
+use crate::gui::gui_message::GUIMessage;
+use crate::core::palette::Palette;
+
+pub const DATE_COLUMN_WIDTH: f32 = 130.0;
+pub const DEFAULT_COLUMN_WIDTH: f32 = 180.0;
+pub const MIN_COLUMN_WIDTH: f32 = 60.0;
+pub const ROW_HEIGHT: f32 = 32.0;
+
+/// Displays the data of the [`ListView`](crate::gui::views::list::list_view_control::ListView)
+/// as a grid with days as rows and keys as columns.
+/// The date column and the header row stay in place while the body is scrolled.
+pub struct TableView
+{
+    /// Title of the key to sort the rows by. Rows are sorted by date if it is `None`.
+    pub sort_key: Option<String>,
+    pub sort_ascending: bool,
+    pub column_widths: HashMap<String, f32>,
+
+    /// Format the dates are displayed in. See [`Settings`](crate::core::settings::Settings).
+    pub date_format: String,
+    pub palette: Palette,
+
+    /// Title of the key whose column is currently being resized by the user.
+    pub(crate) resizing_column: Option<String>,
+    pub(crate) scroll_offset: AbsoluteOffset,
+
+    pub(crate) header_scroll_id: scrollable::Id,
+    pub(crate) date_scroll_id: scrollable::Id,
+    pub(crate) body_scroll_id: scrollable::Id
+}
+
+impl Default for TableView
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            sort_key: None,
+            sort_ascending: true,
+            column_widths: HashMap::new(),
+            date_format: String::from(DATE_FORMAT),
+            palette: Palette::default(),
+            resizing_column: None,
+            scroll_offset: AbsoluteOffset::default(),
+            header_scroll_id: scrollable::Id::unique(),
+            date_scroll_id: scrollable::Id::unique(),
+            body_scroll_id: scrollable::Id::unique()
+        }
+    }
+}
+
+/// Implementation of the control functions for the table view
+impl TableView
+{
+    // UPDATE
+    pub fn update(&mut self, message: GUIMessage, columns: &[String]) -> Task<GUIMessage>
+    {
+        match message
+        {
+            GUIMessage::SortTableBy(key) => {self.sort_by(key)}
+            GUIMessage::StartColumnResize(key) => {self.start_column_resize(key)}
+            GUIMessage::ColumnResizeMoved(position) => {self.resize_column(position, columns)}
+            GUIMessage::EndColumnResize => {self.end_column_resize()}
+            GUIMessage::TableScrolled(offset) => {self.table_scrolled(offset)}
+            GUIMessage::TableDatesScrolled(offset) => {self.dates_scrolled(offset)}
+            _ => {Task::none()}
+        }
+    }
+
+    /// Sorts by the given key. Clicking the key that is already sorted by flips the direction.
+    /// `None` sorts by date.
+    fn sort_by(&mut self, key: Option<String>) -> Task<GUIMessage>
+    {
+        if self.sort_key == key
+        {
+            self.sort_ascending = !self.sort_ascending;
+        }
+        else
+        {
+            self.sort_key = key;
+            self.sort_ascending = true;
+        }
+
+        Task::none()
+    }
+
+    fn start_column_resize(&mut self, key: String) -> Task<GUIMessage>
+    {
+        self.resizing_column = Some(key);
+        Task::none()
+    }
+
+    /// Resizes the column that is currently being dragged so that its right edge
+    /// follows the given cursor position (relative to the table).
+    fn resize_column(&mut self, position: Point, columns: &[String]) -> Task<GUIMessage>
+    {
+        let Some(resizing) = &self.resizing_column else {return Task::none()};
+
+        let mut left_edge = DATE_COLUMN_WIDTH - self.scroll_offset.x;
+        for column in columns
+        {
+            if column == resizing
+            {
+                let width = (position.x - left_edge).max(MIN_COLUMN_WIDTH);
+                self.column_widths.insert(column.clone(), width);
+                break;
+            }
+            left_edge += self.column_width(column);
+        }
+
+        Task::none()
+    }
+
+    fn end_column_resize(&mut self) -> Task<GUIMessage>
+    {
+        self.resizing_column = None;
+        Task::none()
+    }
+
+    /// Keeps the sticky header row and date column aligned with the scrolled body.
+    fn table_scrolled(&mut self, offset: AbsoluteOffset) -> Task<GUIMessage>
+    {
+        self.scroll_offset = offset;
+
+        Task::batch
+        ([
+            scrollable::scroll_to(self.header_scroll_id.clone(), AbsoluteOffset{x: offset.x, y: 0.0}),
+            scrollable::scroll_to(self.date_scroll_id.clone(), AbsoluteOffset{x: 0.0, y: offset.y})
+        ])
+    }
+
+    /// Keeps the body aligned with the date column if the latter is scrolled directly.
+    fn dates_scrolled(&mut self, offset: AbsoluteOffset) -> Task<GUIMessage>
+    {
+        if offset.y == self.scroll_offset.y
+        {
+            return Task::none();
+        }
+
+        self.scroll_offset.y = offset.y;
+        scrollable::scroll_to(self.body_scroll_id.clone(), self.scroll_offset)
+    }
+
+    pub fn column_width(&self, key: &str) -> f32
+    {
+        *self.column_widths.get(key).unwrap_or(&DEFAULT_COLUMN_WIDTH)
+    }
+}
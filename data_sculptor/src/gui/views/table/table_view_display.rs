@@ -0,0 +1,184 @@
+//! Module implementing the display functions for the [`TableView`]
+
+use iced::{Element, Length};
+use iced::widget::{button, mouse_area, Column, Container, Row, Scrollable, Space, Text};
+use iced::widget::scrollable::{Direction, Scrollbar};
+use iced::widget::text::{Rich, Wrapping};
+use iced::mouse::Interaction;
+use crate::core::data_containers::{DayDataParsed, EntryKey};
+use crate::core::data_sorting;
+use crate::core::filters::filter_matches::FilterMatches;
+use crate::gui::gui_message::GUIMessage;
+use crate::gui::gui_style;
+use crate::gui::views::list::list_view_display::{date_text, highlighted_text};
+use crate::gui::views::table::table_view_control::{DATE_COLUMN_WIDTH, ROW_HEIGHT, TableView};
+
+const RESIZE_HANDLE_WIDTH: f32 = 6.0;
+
+/// Implementation of the display functions for the table view
+impl TableView
+{
+    /// Displays the given days as rows and the given keys as columns.
+    /// The days are expected to already be filtered. The text of each day that is part of
+    /// the [`FilterMatches`] found by the given function is highlighted.
+    pub fn view<'a>(&self, mut days: Vec<&DayDataParsed>, columns: &[EntryKey],
+                    find_matches: impl Fn(&DayDataParsed) -> FilterMatches) -> Element<'a, GUIMessage>
+    {
+        let date_color = self.palette.date;
+        let value_color = self.palette.value;
+
+        if let Some(sort_key) = &self.sort_key
+        {
+            let key = EntryKey {title: sort_key.clone()};
+            data_sorting::sort_by_key(&mut days, &key, self.sort_ascending);
+        }
+        else if !self.sort_ascending
+        {
+            days.reverse();
+        }
+
+        // HEADER
+        let mut header_row = Row::new();
+        for column in columns
+        {
+            header_row = header_row.push(self.header_cell(&column.title));
+        }
+
+        let date_header = button(Text::new(self.header_title("Date", &None)))
+            .on_press(GUIMessage::SortTableBy(None))
+            .width(Length::Fixed(DATE_COLUMN_WIDTH))
+            .height(Length::Fixed(ROW_HEIGHT))
+            .style(gui_style::FilterButtonStyle::style);
+
+        let header = Row::new()
+            .push(date_header)
+            .push
+            (
+                Scrollable::with_direction(header_row, hidden_scrollbar(false))
+                    .id(self.header_scroll_id.clone())
+            );
+
+        // BODY
+        let mut date_column = Column::new();
+        let mut body_column = Column::new();
+        for day in days
+        {
+            let matches = find_matches(day);
+            date_column = date_column.push
+            (
+                cell(date_text(&day.date, &self.date_format, &matches, &self.palette).color(date_color),
+                     DATE_COLUMN_WIDTH)
+            );
+
+            let mut row = Row::new();
+            for column in columns
+            {
+                let value = match day.entries.get(column)
+                {
+                    Some(value) => value.string_value.as_str(),
+                    None => ""
+                };
+
+                row = row.push
+                (
+                    cell(highlighted_text(value, &matches.value_ranges(column), &self.palette).color(value_color),
+                         self.column_width(&column.title))
+                );
+            }
+            body_column = body_column.push(row);
+        }
+
+        let dates = Scrollable::with_direction(date_column, hidden_scrollbar(true))
+            .id(self.date_scroll_id.clone())
+            .on_scroll(|viewport| GUIMessage::TableDatesScrolled(viewport.absolute_offset()))
+            .height(Length::Fill);
+
+        let body = Scrollable::with_direction
+            (
+                body_column,
+                Direction::Both {vertical: Scrollbar::new(), horizontal: Scrollbar::new()}
+            )
+            .id(self.body_scroll_id.clone())
+            .on_scroll(|viewport| GUIMessage::TableScrolled(viewport.absolute_offset()))
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let table = Column::new()
+            .push(header)
+            .push(Row::new().push(dates).push(body));
+
+        // RESIZING
+        let mut table_area = mouse_area(table);
+        if self.resizing_column.is_some()
+        {
+            table_area = table_area
+                .on_move(GUIMessage::ColumnResizeMoved)
+                .on_release(GUIMessage::EndColumnResize)
+                .interaction(Interaction::ResizingHorizontally);
+        }
+
+        table_area.into()
+    }
+
+    /// Builds the header cell of the given key consisting of a sort button and a drag
+    /// handle to resize the column.
+    fn header_cell<'a>(&self, key: &str) -> Element<'a, GUIMessage>
+    {
+        let width = self.column_width(key);
+        let sort_key = Some(String::from(key));
+
+        let sort_button = button
+            (
+                Text::new(self.header_title(key, &sort_key)).wrapping(Wrapping::None)
+            )
+            .on_press(GUIMessage::SortTableBy(sort_key))
+            .clip(true)
+            .width(Length::Fixed(width - RESIZE_HANDLE_WIDTH))
+            .height(Length::Fixed(ROW_HEIGHT))
+            .style(gui_style::FilterButtonStyle::style);
+
+        let resize_handle = mouse_area
+            (
+                Container::new(Space::new(RESIZE_HANDLE_WIDTH, ROW_HEIGHT))
+                    .style(gui_style::container_bar_style)
+            )
+            .on_press(GUIMessage::StartColumnResize(String::from(key)))
+            .interaction(Interaction::ResizingHorizontally);
+
+        Row::new()
+            .push(sort_button)
+            .push(resize_handle)
+            .into()
+    }
+
+    /// The title of a header cell with an arrow indicating the sorting direction
+    /// if the table is sorted by the corresponding column.
+    fn header_title(&self, title: &str, sort_key: &Option<String>) -> String
+    {
+        if &self.sort_key != sort_key
+        {
+            return String::from(title);
+        }
+
+        let arrow = if self.sort_ascending {"▲"} else {"▼"};
+        format!("{} {}", title, arrow)
+    }
+}
+
+fn cell<'a>(text: Rich<'a, GUIMessage>, width: f32) -> Element<'a, GUIMessage>
+{
+    Container::new(text.wrapping(Wrapping::None))
+        .width(Length::Fixed(width))
+        .height(Length::Fixed(ROW_HEIGHT))
+        .padding([5, 10])
+        .clip(true)
+        .into()
+}
+
+/// A scrollbar that takes up no space. Used for the sticky parts of the table which are only
+/// scrolled along with the body.
+fn hidden_scrollbar(vertical: bool) -> Direction
+{
+    let scrollbar = Scrollbar::new().width(0).scroller_width(0);
+    if vertical {Direction::Vertical(scrollbar)} else {Direction::Horizontal(scrollbar)}
+}
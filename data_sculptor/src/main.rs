@@ -6,7 +6,7 @@ use std::sync::{Arc, Mutex};
 /// and distributes it among the submodules (i.e. GUI) that it constructs.
 fn main()
 {
-    let data_manager = DataManager { data: Vec::new()};
+    let data_manager = DataManager::default();
     let arc_data_manager: Arc<Mutex<DataManager>> = Arc::new(Mutex::from(data_manager));
 
     //GUI
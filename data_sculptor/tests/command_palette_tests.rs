@@ -0,0 +1,56 @@
+mod command_palette_tests
+{
+    use iced::keyboard::{Key, Modifiers};
+    use iced::keyboard::key::Named;
+    use data_sculptor::core::filters::filter::FilterType;
+    use data_sculptor::gui::gui_message::GUIMessage;
+    use data_sculptor::gui::views::command_palette::command_palette_control::{commands, filter_commands, fuzzy_score, shortcut};
+
+    fn names(query: &str) -> Vec<&'static str>
+    {
+        filter_commands(commands(), query).iter().map(|command| command.name).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score()
+    {
+        assert!(fuzzy_score("", "Open file").is_some());
+        assert!(fuzzy_score("opfi", "Open file").is_some());
+        assert!(fuzzy_score("OPEN FILE", "Open file").is_some());
+        assert!(fuzzy_score("fo", "Open file").is_none());
+        assert!(fuzzy_score("openx", "Open file").is_none());
+
+        // Consecutive characters and word starts score higher
+        assert!(fuzzy_score("file", "Open file") > fuzzy_score("file", "Find longest item entry"));
+        assert!(fuzzy_score("of", "Open file") > fuzzy_score("of", "Proof"));
+    }
+
+    #[test]
+    fn test_filter_commands()
+    {
+        assert_eq!(names("").len(), commands().len());
+        assert_eq!(names("key filt")[0], "Open key filters");
+        assert_eq!(names("undo"), ["Undo"]);
+        assert!(names("zzzz").is_empty());
+    }
+
+    #[test]
+    fn test_shortcuts()
+    {
+        let ctrl = Modifiers::CTRL;
+        let ctrl_shift = Modifiers::CTRL | Modifiers::SHIFT;
+        let character = |c: &str| Key::Character(c.into());
+
+        assert!(matches!(shortcut(&character("p"), ctrl, false), Some(GUIMessage::OpenCommandPalette)));
+        assert!(matches!(shortcut(&character("2"), ctrl, false), Some(GUIMessage::OpenFilterView(FilterType::Key))));
+        assert!(matches!(shortcut(&character("Z"), ctrl_shift, false), Some(GUIMessage::Redo)));
+        assert!(matches!(shortcut(&character("z"), ctrl, false), Some(GUIMessage::Undo)));
+        assert!(shortcut(&character("p"), Modifiers::empty(), false).is_none());
+
+        // Keys handled by a widget only trigger shortcuts that hold ctrl
+        assert!(matches!(shortcut(&character("s"), ctrl, true), Some(GUIMessage::SaveData)));
+        assert!(matches!(shortcut(&Key::Named(Named::Escape), Modifiers::empty(), false), Some(GUIMessage::GoBack)));
+        assert!(shortcut(&Key::Named(Named::Escape), Modifiers::empty(), true).is_none());
+        assert!(shortcut(&Key::Named(Named::Enter), Modifiers::empty(), true).is_none());
+    }
+}
@@ -0,0 +1,77 @@
+mod data_edit_tests
+{
+    use indexmap::IndexMap;
+    use data_sculptor::core::data_containers::{DayDataUnparsed, parse_and_sort_by_date};
+    use data_sculptor::core::data_edit::{DataEdit, EditError};
+    use data_sculptor::core::data_manager::DataManager;
+
+    fn data_manager() -> DataManager
+    {
+        let mut days: Vec<DayDataUnparsed> = Vec::new();
+        for date in ["2024-01-01", "2024-01-03"]
+        {
+            let mut entries: IndexMap<String, String> = IndexMap::new();
+            entries.insert(String::from("speed"), String::from("5"));
+            entries.insert(String::from("note"), String::from("something"));
+            days.push(DayDataUnparsed{date: String::from(date), entries});
+        }
+
+        let mut data_manager = DataManager::default();
+        data_manager.replace_data(parse_and_sort_by_date(days).unwrap(), "test.json");
+        data_manager
+    }
+
+    #[test]
+    fn test_edit_and_revert()
+    {
+        let mut dm = data_manager();
+        let edits =
+            [
+                DataEdit::SetValue{date: "2024-01-01".into(), key: "speed".into(), value: "7".into()},
+                DataEdit::InsertKey{date: "2024-01-01".into(), key: "new".into(), value: "x".into(), index: 0},
+                DataEdit::RemoveKey{date: "2024-01-03".into(), key: "note".into()},
+                DataEdit::InsertDay{date: "2024-01-02".into(), entries: IndexMap::new()},
+                DataEdit::RemoveDay{date: "2024-01-03".into()}
+            ];
+
+        let original = dm.data.clone();
+        let mut inverses: Vec<DataEdit> = Vec::new();
+        for edit in edits
+        {
+            inverses.push(dm.apply_edit(edit).unwrap());
+        }
+
+        assert!(dm.dirty);
+        assert_eq!(dm.data.len(), 2);
+        assert_eq!(dm.data[1].date.date_string, "2024-01-02");
+        assert_eq!(dm.get_value("2024-01-01", "speed"), Some(String::from("7")));
+
+        for inverse in inverses.into_iter().rev()
+        {
+            dm.apply_edit(inverse).unwrap();
+        }
+
+        assert_eq!(dm.data.len(), original.len());
+        for (day, original_day) in dm.data.iter().zip(&original)
+        {
+            assert_eq!(day.date.date_string, original_day.date.date_string);
+            assert_eq!(day.entries, original_day.entries);
+        }
+    }
+
+    #[test]
+    fn test_invalid_edits()
+    {
+        let mut dm = data_manager();
+
+        assert_eq!(dm.apply_edit(DataEdit::RemoveDay{date: "2000-01-01".into()}),
+                   Err(EditError::UnknownDate(String::from("2000-01-01"))));
+
+        assert_eq!(dm.apply_edit(DataEdit::InsertKey
+                   {date: "2024-01-01".into(), key: "note".into(), value: "".into(), index: 0}),
+                   Err(EditError::DuplicateKey(String::from("note"))));
+
+        assert!(dm.apply_edit(DataEdit::InsertDay{date: "not a date".into(), entries: IndexMap::new()}).is_err());
+        assert!(!dm.dirty);
+    }
+}
@@ -0,0 +1,77 @@
+mod data_merge_tests
+{
+    use std::fs;
+    use std::sync::atomic::AtomicBool;
+    use data_sculptor::core::data_containers::{EntryKey, unparse};
+    use data_sculptor::core::data_manager::DataManager;
+    use data_sculptor::file_io::data_loader::is_supported_file;
+
+    fn write_temp(name: &str, content: &str) -> String
+    {
+        let path = std::env::temp_dir().join(format!("data_sculptor_merge_test_{}.json", name));
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_read_merged_data()
+    {
+        let first = write_temp("first", "{\"2024-01-02\": {\"a\": \"1\", \"b\": \"2\"}, \"2024-01-03\": {\"a\": \"3\"}}");
+        let second = write_temp("second", "{\"2024-01-02\": {\"b\": \"new\", \"c\": \"4\"}, \"2024-01-01\": {\"a\": \"5\"}}");
+
+        let mut progress = Vec::new();
+        let data = DataManager::read_merged_data(&[first.clone(), second.clone()],
+                                                 &mut |p| progress.push(p), &AtomicBool::new(false));
+        let _ = fs::remove_file(&first);
+        let _ = fs::remove_file(&second);
+        let data = data.unwrap();
+
+        let dates: Vec<&str> = data.iter().map(|day| day.date.date_string.as_str()).collect();
+        assert_eq!(dates, ["2024-01-01", "2024-01-02", "2024-01-03"]);
+
+        let merged_day = unparse(&data[1]);
+        assert_eq!(merged_day.entries.get("a").map(String::as_str), Some("1"));
+        assert_eq!(merged_day.entries.get("b").map(String::as_str), Some("new"));
+        assert_eq!(merged_day.entries.get("c").map(String::as_str), Some("4"));
+        assert!(data[1].entries.contains_key(&EntryKey{title: String::from("c")}));
+
+        assert!(progress.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(progress.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn test_merge_errors()
+    {
+        let valid = write_temp("valid", "{\"2024-01-02\": {\"a\": \"1\"}}");
+        let invalid = write_temp("invalid", "{\"2024-01-02\": ");
+
+        let result = DataManager::read_merged_data(&[valid.clone(), invalid.clone()],
+                                                   &mut |_| {}, &AtomicBool::new(false));
+        let _ = fs::remove_file(&valid);
+        let _ = fs::remove_file(&invalid);
+
+        assert!(result.unwrap_err().starts_with(&invalid));
+    }
+
+    #[test]
+    fn test_supported_files()
+    {
+        assert!(is_supported_file("data.json"));
+        assert!(is_supported_file("/some/dir/DATA.JSON"));
+        assert!(!is_supported_file("data.csv"));
+        assert!(!is_supported_file("json"));
+    }
+
+    #[test]
+    fn test_replace_data_merged()
+    {
+        let mut data_manager = DataManager::default();
+        data_manager.replace_data(Vec::new(), "test.json");
+        let generation = data_manager.generation;
+
+        data_manager.replace_data_merged(Vec::new());
+        assert_eq!(data_manager.source_path, None);
+        assert!(data_manager.generation > generation);
+        assert!(data_manager.save_data().is_err());
+    }
+}
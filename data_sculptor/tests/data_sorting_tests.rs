@@ -0,0 +1,103 @@
+mod sort_by_key_tests
+{
+    use std::cmp::Ordering;
+    use indexmap::IndexMap;
+    use data_sculptor::core::data_containers::{DayDataParsed, DayDataUnparsed, EntryKey, EntryValue, parse};
+    use data_sculptor::core::data_sorting::{compare_values, sort_by_key};
+
+    fn day(date: &str, entries: &[(&str, &str)]) -> DayDataParsed
+    {
+        let mut map: IndexMap<String, String> = IndexMap::new();
+        for (key, value) in entries
+        {
+            map.insert(key.to_string(), value.to_string());
+        }
+        parse(DayDataUnparsed{date: date.to_string(), entries: map}).unwrap()
+    }
+
+    fn dates(days: &[&DayDataParsed]) -> Vec<String>
+    {
+        days.iter().map(|day| day.date.date_string.clone()).collect()
+    }
+
+    #[test]
+    fn test_sorts_numerically()
+    {
+        let a = day("2024-01-01", &[("speed", "10")]);
+        let b = day("2024-01-02", &[("speed", "9.5")]);
+        let c = day("2024-01-03", &[("speed", "100")]);
+        let mut days = vec![&a, &b, &c];
+
+        sort_by_key(&mut days, &EntryKey{title: String::from("speed")}, true);
+        assert_eq!(dates(&days), ["2024-01-02", "2024-01-01", "2024-01-03"]);
+
+        sort_by_key(&mut days, &EntryKey{title: String::from("speed")}, false);
+        assert_eq!(dates(&days), ["2024-01-03", "2024-01-01", "2024-01-02"]);
+    }
+
+    #[test]
+    fn test_falls_back_to_strings()
+    {
+        let a = day("2024-01-01", &[("food", "tomato")]);
+        let b = day("2024-01-02", &[("food", "apple")]);
+        let c = day("2024-01-03", &[("food", "12")]);
+        let mut days = vec![&a, &b, &c];
+
+        sort_by_key(&mut days, &EntryKey{title: String::from("food")}, true);
+        assert_eq!(dates(&days), ["2024-01-03", "2024-01-02", "2024-01-01"]);
+    }
+
+    #[test]
+    fn test_mixed_numbers_and_text()
+    {
+        let values = ["2", "10", "1a", "abc", "-3", "10", "b", " 7 "];
+        let days: Vec<DayDataParsed> = values.iter().enumerate()
+            .map(|(i, value)| day(&format!("2024-01-{:02}", i + 1), &[("mixed", value)]))
+            .collect();
+        let key = EntryKey{title: String::from("mixed")};
+        let sorted_values = |days: &[&DayDataParsed]| -> Vec<String>
+            {
+                days.iter().map(|day| day.entries[&key].string_value.clone()).collect()
+            };
+
+        // Numbers first, then text, regardless of the order the days start in
+        let mut days_refs: Vec<&DayDataParsed> = days.iter().collect();
+        sort_by_key(&mut days_refs, &key, true);
+        assert_eq!(sorted_values(&days_refs), ["-3", "2", " 7 ", "10", "10", "1a", "abc", "b"]);
+
+        days_refs.reverse();
+        sort_by_key(&mut days_refs, &key, true);
+        assert_eq!(sorted_values(&days_refs), ["-3", "2", " 7 ", "10", "10", "1a", "abc", "b"]);
+
+        sort_by_key(&mut days_refs, &key, false);
+        assert_eq!(sorted_values(&days_refs), ["b", "abc", "1a", "10", "10", " 7 ", "2", "-3"]);
+    }
+
+    #[test]
+    fn test_mixed_order_is_consistent()
+    {
+        let a = EntryValue{string_value: String::from("2")};
+        let b = EntryValue{string_value: String::from("10")};
+        let c = EntryValue{string_value: String::from("1a")};
+
+        // Used to be "2" < "10" < "1a" < "2"
+        assert_eq!(compare_values(&a, &b), Ordering::Less);
+        assert_eq!(compare_values(&b, &c), Ordering::Less);
+        assert_eq!(compare_values(&a, &c), Ordering::Less);
+    }
+
+    #[test]
+    fn test_missing_values_last()
+    {
+        let a = day("2024-01-01", &[]);
+        let b = day("2024-01-02", &[("speed", "3")]);
+        let c = day("2024-01-03", &[("speed", "5")]);
+        let mut days = vec![&a, &b, &c];
+
+        sort_by_key(&mut days, &EntryKey{title: String::from("speed")}, true);
+        assert_eq!(dates(&days), ["2024-01-02", "2024-01-03", "2024-01-01"]);
+
+        sort_by_key(&mut days, &EntryKey{title: String::from("speed")}, false);
+        assert_eq!(dates(&days), ["2024-01-03", "2024-01-02", "2024-01-01"]);
+    }
+}
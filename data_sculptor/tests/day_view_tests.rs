@@ -0,0 +1,83 @@
+mod day_view_tests
+{
+    use indexmap::IndexMap;
+    use data_sculptor::core::data_containers::{DayDataParsed, DayDataUnparsed, parse_and_sort_by_date};
+    use data_sculptor::file_io::data_writer;
+    use data_sculptor::gui::gui_message::GUIMessage;
+    use data_sculptor::gui::views::day::day_view_control::DayView;
+    use data_sculptor::gui::views::list::list_view_control::{clip_list_value, MAX_LIST_VALUE_CHARS};
+
+    fn data() -> Vec<DayDataParsed>
+    {
+        let days: Vec<DayDataUnparsed> = ["2024-01-03", "2024-01-01", "2024-01-02"].into_iter()
+            .map(|date|
+                {
+                    let mut entries: IndexMap<String, String> = IndexMap::new();
+                    entries.insert(String::from("b"), String::from("2"));
+                    entries.insert(String::from("a"), format!("value of {}", date));
+                    DayDataUnparsed{date: String::from(date), entries}
+                })
+            .collect();
+        parse_and_sort_by_date(days).unwrap()
+    }
+
+    #[test]
+    fn test_navigation()
+    {
+        let data = data();
+        let mut day_view = DayView::default();
+        assert!(!day_view.is_open());
+
+        let _ = day_view.update(GUIMessage::OpenDay(String::from("2024-01-02")), &data);
+        assert_eq!(day_view.day_index(&data), Some(1));
+
+        let _ = day_view.update(GUIMessage::ShowNextDay, &data);
+        assert_eq!(day_view.date.as_deref(), Some("2024-01-03"));
+
+        // Stays on the last day
+        let _ = day_view.update(GUIMessage::ShowNextDay, &data);
+        assert_eq!(day_view.date.as_deref(), Some("2024-01-03"));
+
+        let _ = day_view.update(GUIMessage::ShowPreviousDay, &data);
+        let _ = day_view.update(GUIMessage::ShowPreviousDay, &data);
+        let _ = day_view.update(GUIMessage::ShowPreviousDay, &data);
+        assert_eq!(day_view.date.as_deref(), Some("2024-01-01"));
+
+        let _ = day_view.update(GUIMessage::CloseDay, &data);
+        assert!(!day_view.is_open());
+    }
+
+    #[test]
+    fn test_missing_day()
+    {
+        let data = data();
+        let mut day_view = DayView::default();
+
+        let _ = day_view.update(GUIMessage::OpenDay(String::from("2023-12-31")), &data);
+        assert!(day_view.is_open());
+        assert_eq!(day_view.day_index(&data), None);
+
+        let _ = day_view.update(GUIMessage::ShowNextDay, &data);
+        assert_eq!(day_view.date.as_deref(), Some("2023-12-31"));
+    }
+
+    #[test]
+    fn test_day_to_json()
+    {
+        let data = data();
+        let json = data_writer::to_json(&data[1..=1]).unwrap();
+        assert_eq!(json, "{\n  \"2024-01-02\": {\n    \"b\": \"2\",\n    \"a\": \"value of 2024-01-02\"\n  }\n}");
+    }
+
+    #[test]
+    fn test_clip_list_value()
+    {
+        assert_eq!(clip_list_value("short value"), None);
+        assert_eq!(clip_list_value(&"a".repeat(MAX_LIST_VALUE_CHARS)), None);
+
+        let long = "ä".repeat(MAX_LIST_VALUE_CHARS + 1);
+        assert_eq!(clip_list_value(&long), Some("ä".repeat(MAX_LIST_VALUE_CHARS).as_str()));
+        assert_eq!(clip_list_value("first line\nsecond line"), Some("first line"));
+        assert_eq!(clip_list_value("first line\r\n"), Some("first line"));
+    }
+}
@@ -0,0 +1,111 @@
+mod exporter_tests
+{
+    use indexmap::IndexMap;
+    use data_sculptor::file_io::exporter::{ExportContent, ExportFormat, FilterGroup};
+    use data_sculptor::file_io::report_exporter::{escape_html, format_number, key_statistics, markdown_code, markdown_text, NumericStatistics};
+    use data_sculptor::file_io::text_formats::DayEntries;
+
+    fn content(statistics: bool) -> ExportContent
+    {
+        let mut days: DayEntries = IndexMap::new();
+        for (date, entries) in [
+            ("2024-01-01", vec![("steps", "9000"), ("mood", "good")]),
+            ("2024-01-02", vec![("steps", " 4500.5 "), ("mood", "<tired> | sleepy")]),
+            ("2024-01-03", vec![("mood", "good")])
+        ]
+        {
+            let entries = entries.into_iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+            days.insert(date.to_string(), entries);
+        }
+
+        ExportContent
+        {
+            title: String::from("Diary report"),
+            filters: vec![FilterGroup
+            {
+                name: String::from("Date filters"),
+                expressions: vec![String::from("date > 2023-12-31")]
+            }],
+            days,
+            statistics
+        }
+    }
+
+    #[test]
+    fn test_key_statistics()
+    {
+        let statistics = key_statistics(&content(true).days);
+        assert_eq!(statistics.len(), 2);
+
+        assert_eq!(statistics[0].key, "steps");
+        assert_eq!(statistics[0].count, 2);
+        assert_eq!(statistics[0].numeric, Some(NumericStatistics {min: 4500.5, max: 9000.0, mean: 6750.25, sum: 13500.5}));
+
+        assert_eq!(statistics[1].key, "mood");
+        assert_eq!(statistics[1].count, 3);
+        assert_eq!(statistics[1].distinct_values, 2);
+        assert_eq!(statistics[1].numeric, None);
+
+        assert_eq!(format_number(6750.25), "6750.25");
+        assert_eq!(format_number(2.0 / 3.0), "0.67");
+        assert_eq!(format_number(9000.0), "9000");
+        assert_eq!(format_number(-0.001), "0");
+    }
+
+    #[test]
+    fn test_markdown_report()
+    {
+        let report = ExportFormat::Markdown.exporter().export(&content(true)).unwrap();
+        assert!(report.starts_with("# Diary report\n\n3 days from 2024-01-01 to 2024-01-03\n"));
+        assert!(report.contains("## Filters\n\n- Date filters: `date > 2023-12-31`\n"));
+        assert!(report.contains("| steps | 2 | 2 | 4500.5 | 9000 | 6750.25 | 13500.5 |"));
+        assert!(report.contains("## 2024-01-02\n\n| Key | Value |\n| --- | --- |\n| steps |  4500.5  |\n| mood | <tired> \\| sleepy |"));
+
+        let without_statistics = ExportFormat::Markdown.exporter().export(&content(false)).unwrap();
+        assert!(!without_statistics.contains("## Summary"));
+    }
+
+    #[test]
+    fn test_markdown_escaping()
+    {
+        let mut content = content(false);
+        content.title = String::from("*Diary* #1 <draft>");
+        content.filters[0].expressions.push(String::from("{contains \"a`b``c\"}"));
+        let date = content.days.shift_remove("2024-01-03").unwrap();
+        content.days.insert(String::from("2024_01_03"), date);
+
+        let report = ExportFormat::Markdown.exporter().export(&content).unwrap();
+        assert!(report.starts_with("# \\*Diary\\* \\#1 \\<draft\\>\n"));
+        assert!(report.contains("`date > 2023-12-31`, ```{contains \"a`b``c\"}```\n"));
+        assert!(report.contains("## 2024\\_01\\_03\n"));
+
+        assert_eq!(markdown_code("`a`"), "`` `a` ``");
+        assert_eq!(markdown_code("plain"), "`plain`");
+        assert_eq!(markdown_text("a\nb | c"), "a b \\| c");
+    }
+
+    #[test]
+    fn test_html_report()
+    {
+        let report = ExportFormat::Html.exporter().export(&content(true)).unwrap();
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains("<title>Diary report</title>"));
+        assert!(report.contains("<li>Date filters: <code>date &gt; 2023-12-31</code></li>"));
+        assert!(report.contains("<h2>Summary</h2>"));
+        assert!(report.contains("<h2>2024-01-03</h2>"));
+        assert!(report.contains("<tr><td>mood</td><td>&lt;tired&gt; | sleepy</td></tr>"));
+        assert_eq!(report.matches("<section>").count(), 3);
+
+        assert_eq!(escape_html("a & \"b\" 'c'"), "a &amp; &quot;b&quot; &#39;c&#39;");
+    }
+
+    #[test]
+    fn test_json_export()
+    {
+        let content = content(true);
+        let json = ExportFormat::Json.exporter().export(&content).unwrap();
+        let parsed: DayEntries = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, content.days);
+        assert_eq!(ExportFormat::Json.exporter().extension(), "json");
+    }
+}
@@ -0,0 +1,85 @@
+mod expression_parser_tests
+{
+    use data_sculptor::core::filters::expression_parser::{parse, parse_with_macros, ExpressionError, FilterMacros};
+    use data_sculptor::core::filters::filter::FilterType;
+
+    fn error(input: &str) -> ExpressionError
+    {
+        let input = input.replace("{a}", "{contains \"a\"}").replace("{b}", "{contains \"b\"}");
+        parse_with_macros(&FilterType::Key, &input, &FilterMacros::new()).unwrap_err()
+    }
+
+    fn same(a: &str, b: &str)
+    {
+        assert_eq!(parse(&FilterType::Key, a).unwrap(), parse(&FilterType::Key, b).unwrap(), "{} | {}", a, b);
+    }
+
+    #[test]
+    fn test_rejects_ill_formed_sequences()
+    {
+        assert_eq!(error("{a} not and {b}"), ExpressionError::MisplacedNot);
+        assert_eq!(error("{a} {b}"), ExpressionError::MissingOperator);
+        assert_eq!(error("{a} and"), ExpressionError::MissingOperand);
+        assert_eq!(error("and {a}"), ExpressionError::MissingOperand);
+        assert_eq!(error("{a} xand {b}"), ExpressionError::UnknownOperator(String::from("xand")));
+        assert_eq!(error("({a} and {b}"), ExpressionError::UnmatchedParenthesis);
+        assert_eq!(error("{a})"), ExpressionError::UnmatchedParenthesis);
+        assert_eq!(error("()"), ExpressionError::MissingOperand);
+        assert_eq!(error("{a} & {b}"), ExpressionError::InvalidSyntax);
+        assert_eq!(error("{numop \"a\"}"), ExpressionError::InvalidCommand(String::from("numop \"a\"")));
+    }
+
+    #[test]
+    fn test_precedence()
+    {
+        same("not {contains \"a\"} and {contains \"b\"}", "(not {contains \"a\"}) and {contains \"b\"}");
+        same("{contains \"a\"} or {contains \"b\"} and {contains \"c\"}",
+             "{contains \"a\"} or ({contains \"b\"} and {contains \"c\"})");
+        same("{contains \"a\"} xor {contains \"b\"} or {contains \"c\"}",
+             "({contains \"a\"} xor {contains \"b\"}) or {contains \"c\"}");
+        same("{contains \"a\"} and not {contains \"b\"} nand {contains \"c\"}",
+             "({contains \"a\"} and (not {contains \"b\"})) nand {contains \"c\"}");
+        same("not not {contains \"a\"}", "not (not {contains \"a\"})");
+    }
+
+    #[test]
+    fn test_aliases_and_literals()
+    {
+        same("!{contains \"a\"} && {contains \"b\"} || {contains \"c\"}",
+             "not {contains \"a\"} and {contains \"b\"} or {contains \"c\"}");
+        same("{contains \"a\"}&&{contains \"b\"}", "{contains \"a\"} and {contains \"b\"}");
+        assert_eq!(parse(&FilterType::Key, "true and not false").unwrap().to_string(), "true and not false");
+
+        // Single commands can still be written without {}
+        same("contains \"a\"", "{contains \"a\"}");
+    }
+
+    #[test]
+    fn test_escapes()
+    {
+        let canonical = |filter_type: FilterType, input: &str| parse(&filter_type, input).map(|e| e.to_string());
+
+        for input in ["{contains \"say \\\"hi\\\"\"}", "{contains \"a\\}b\"}", "{contains \"back\\\\slash\"}",
+            "{kv-contains \"\\\"\" \"{\\}\"}"]
+        {
+            let filter_type = if input.starts_with("{kv") {FilterType::Value} else {FilterType::Key};
+            assert_eq!(canonical(filter_type, input).as_deref(), Some(input));
+        }
+
+        // Backslashes in front of other characters are kept as they are
+        assert_eq!(canonical(FilterType::Key, "{contains \"C:\\path\"}").as_deref(), Some("{contains \"C:\\\\path\"}"));
+        assert_eq!(canonical(FilterType::Key, "contains \"a\\b\"").as_deref(), Some("{contains \"a\\\\b\"}"));
+        assert!(parse(&FilterType::Key, "{contains \"a\\\"}").is_none());
+    }
+
+    #[test]
+    fn test_relaxed_whitespace()
+    {
+        same("{contains   \"x\"}", "{contains \"x\"}");
+        same("{ numop\t\">\"   \"3\" }", "{numop \">\" \"3\"}");
+        same("contains\"x\"", "{contains \"x\"}");
+        assert_eq!(parse(&FilterType::Date, "{date   before  \"2024-01-01\"}").unwrap().to_string(),
+                   "{date before \"2024-01-01\"}");
+        assert!(parse(&FilterType::Key, "{contains \"x\" y}").is_none());
+    }
+}
@@ -0,0 +1,71 @@
+mod expression_simplifier_tests
+{
+    use data_sculptor::core::filters::expression_parser::parse;
+    use data_sculptor::core::filters::expression_simplifier::simplify;
+    use data_sculptor::core::filters::filter::{FilterID, FilterType};
+
+    /// Parses the input with {a}, {b}, ... as shorthand for {contains "a"}, {contains "b"}, ...
+    fn simplified(input: &str) -> String
+    {
+        simplify(&parse(&FilterType::Key, &expand(input)).unwrap()).to_string()
+    }
+
+    fn expand(input: &str) -> String
+    {
+        let mut expanded = String::from(input);
+        for name in ["a", "b", "c"]
+        {
+            expanded = expanded.replace(&format!("{{{}}}", name), &format!("{{contains \"{}\"}}", name));
+        }
+        expanded
+    }
+
+    fn id(input: &str) -> FilterID
+    {
+        FilterID::from(&parse(&FilterType::Key, &expand(input)).unwrap())
+    }
+
+    #[test]
+    fn test_double_negation()
+    {
+        assert_eq!(simplified("not (not {a})"), expand("{a}"));
+        assert_eq!(simplified("not (not (not {a}))"), expand("not {a}"));
+    }
+
+    #[test]
+    fn test_de_morgan()
+    {
+        assert_eq!(simplified("{a} nand {b}"), expand("not {a} or not {b}"));
+        assert_eq!(simplified("{a} nor {b}"), expand("not {a} and not {b}"));
+        assert_eq!(simplified("not ({a} or not {b})"), expand("not {a} and {b}"));
+        assert_eq!(simplified("{a} xnor {b}"), expand("not ({a} xor {b})"));
+    }
+
+    #[test]
+    fn test_flatten_and_sort()
+    {
+        assert_eq!(simplified("{c} and ({b} and {a})"), expand("{a} and {b} and {c}"));
+        assert_eq!(simplified("{b} or {a} or {b}"), expand("{a} or {b}"));
+        assert_eq!(simplified("{b} xor ({a} xor {b})"), expand("{a}"));
+        assert_eq!(simplified("({b} or {a}) and {c}"), expand("({a} or {b}) and {c}"));
+    }
+
+    #[test]
+    fn test_constant_folding()
+    {
+        assert_eq!(simplified("{a} and not {a}"), "false");
+        assert_eq!(simplified("{a} or not {a}"), "true");
+        assert_eq!(simplified("{a} xor {a}"), "false");
+        assert_eq!(simplified("{a} xor not {a} xor {b}"), expand("not {b}"));
+        assert_eq!(simplified("({a} or not {a}) and {b}"), expand("{b}"));
+        assert_eq!(simplified("({a} and not {a}) or {b}"), expand("{b}"));
+    }
+
+    #[test]
+    fn test_filter_ids()
+    {
+        assert_eq!(id("{a} and {b}"), id("{b} and {a}"));
+        assert_eq!(id("{a} nand {b}"), id("not ({b} and {a})"));
+        assert_ne!(id("{a} and {b}"), id("{a} or {b}"));
+    }
+}
@@ -0,0 +1,156 @@
+mod file_watcher_tests
+{
+    use std::fs;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Mutex;
+    use data_sculptor::core::data_manager::{DataManager, LoadedData, LoadingEvent};
+    use data_sculptor::core::filters::filter::FilterType;
+    use data_sculptor::core::search_index::SearchIndex;
+    use data_sculptor::file_io::data_writer;
+    use data_sculptor::file_io::file_watcher::file_stamp;
+    use data_sculptor::gui::gui_message::GUIMessage;
+    use data_sculptor::gui::views::list::list_view_control::{day_height, ListView, UNSAVED_EDITS_NOTICE};
+
+    const DAYS: &str = "{\"2024-01-02\": {\"a\": \"2\"}, \"2024-01-03\": {\"a\": \"3\"}}";
+    const DAYS_WITH_NEW_DAY: &str = "{\"2024-01-01\": {\"a\": \"1\", \"b\": \"1\"}, \
+        \"2024-01-02\": {\"a\": \"2\"}, \"2024-01-03\": {\"a\": \"3\"}}";
+
+    fn temp_path(name: &str) -> String
+    {
+        let path = std::env::temp_dir().join(format!("data_sculptor_watcher_test_{}.json", name));
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_file_stamp()
+    {
+        let path = temp_path("stamp");
+        let _ = fs::remove_file(&path);
+        assert_eq!(file_stamp(&path), None);
+
+        fs::write(&path, "{}").unwrap();
+        let stamp = file_stamp(&path);
+        assert!(stamp.is_some());
+        assert_eq!(file_stamp(&path), stamp);
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"\n").unwrap();
+        drop(file);
+        let appended = file_stamp(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_ne!(appended, stamp);
+        assert_eq!(appended.unwrap().len, 3);
+    }
+
+    #[test]
+    fn test_count_new_days()
+    {
+        let path = temp_path("count");
+        fs::write(&path, "{\"2024-01-01\": {\"a\": \"1\"}, \"2024-01-02\": {\"a\": \"2\"}}").unwrap();
+        let mut dm = DataManager::default();
+        dm.load_data(&path);
+
+        fs::write(&path, "{\"2024-01-01\": {\"a\": \"1\"}, \"2024-01-02\": {\"a\": \"changed\"}, \
+            \"2024-01-03\": {\"a\": \"3\"}, \"2023-12-31\": {\"b\": \"4\"}}").unwrap();
+        let reloaded = DataManager::read_data(&path, &mut |_| {}, &AtomicBool::new(false));
+        let _ = fs::remove_file(&path);
+        let reloaded = reloaded.unwrap();
+
+        assert_eq!(dm.count_new_days(&reloaded), 2);
+        assert_eq!(dm.count_new_days(&dm.data), 0);
+        assert_eq!(DataManager::default().count_new_days(&reloaded), 4);
+    }
+
+    /// Loads the given content from a file of the given name into a new data manager and list view.
+    fn load(name: &str, content: &str) -> (String, Mutex<DataManager>, ListView)
+    {
+        let path = temp_path(name);
+        fs::write(&path, content).unwrap();
+        let mut dm = DataManager::default();
+        dm.load_data(&path);
+        (path, Mutex::new(dm), ListView::default())
+    }
+
+    /// Writes the given content to the watched file and passes the reload through the list view
+    /// as the background loading would.
+    fn reload(list_view: &mut ListView, dm: &Mutex<DataManager>, path: &str, content: &str)
+    {
+        fs::write(path, content).unwrap();
+        let _ = list_view.update(GUIMessage::WatchedFileChanged(String::from(path)), dm);
+        finish_reload(list_view, dm, path);
+    }
+
+    /// Passes the file read in its current state to the list view as the finished background load.
+    fn finish_reload(list_view: &mut ListView, dm: &Mutex<DataManager>, path: &str)
+    {
+        let data = DataManager::read_data(path, &mut |_| {}, &AtomicBool::new(false)).unwrap();
+        let search_index = SearchIndex::build(&data);
+        let finished = LoadingEvent::Finished(Ok(LoadedData {data, search_index}));
+        let _ = list_view.update(GUIMessage::LoadingUpdate(finished), dm);
+    }
+
+    #[test]
+    fn test_reload_skipped_with_unsaved_edits()
+    {
+        let (path, dm, mut list_view) = load("unsaved", DAYS);
+        dm.lock().unwrap().dirty = true;
+        let _ = list_view.update(GUIMessage::WatchedFileChanged(path.clone()), &dm);
+        assert_eq!(list_view.notice.as_deref(), Some(UNSAVED_EDITS_NOTICE));
+        assert!(!list_view.loading_file);
+
+        // Edits made while the file was being reloaded are kept as well
+        dm.lock().unwrap().dirty = false;
+        list_view.notice = None;
+        fs::write(&path, DAYS_WITH_NEW_DAY).unwrap();
+        let _ = list_view.update(GUIMessage::WatchedFileChanged(path.clone()), &dm);
+        dm.lock().unwrap().dirty = true;
+        finish_reload(&mut list_view, &dm, &path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(list_view.notice.as_deref(), Some(UNSAVED_EDITS_NOTICE));
+        assert_eq!(dm.lock().unwrap().data.len(), 2);
+    }
+
+    #[test]
+    fn test_reload_keeps_filters()
+    {
+        let (path, dm, mut list_view) = load("filters", DAYS);
+        for message in [
+            GUIMessage::OpenFilterView(FilterType::Value),
+            GUIMessage::FilterInputChanged(String::from("{kv-numop \"a\" \"<\" \"3\"}")),
+            GUIMessage::AddFilter
+        ]
+        {
+            let _ = list_view.update(message, &dm);
+        }
+        let titles = |list_view: &ListView| -> Vec<String>
+            {
+                list_view.get_filters(&FilterType::Value).values().map(|filter| filter.title.clone()).collect()
+            };
+        let filters = titles(&list_view);
+
+        reload(&mut list_view, &dm, &path, DAYS_WITH_NEW_DAY);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(titles(&list_view), filters);
+        let filtered = data_writer::filter_data(&dm.lock().unwrap().data, &list_view);
+        assert_eq!(filtered.keys().collect::<Vec<_>>(), ["2024-01-01", "2024-01-02"]);
+        assert_eq!(list_view.notice.as_deref(), Some("Reloaded 1 new day"));
+    }
+
+    #[test]
+    fn test_reload_restores_scroll_position()
+    {
+        let (path, dm, mut list_view) = load("scroll", DAYS);
+
+        // Scrolled into the second day, the new day is added in front of the first one
+        list_view.list_scroll_y = day_height(1) + 5.0;
+        reload(&mut list_view, &dm, &path, DAYS_WITH_NEW_DAY);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(list_view.list_scroll_y, day_height(2) + day_height(1) + 5.0);
+    }
+}
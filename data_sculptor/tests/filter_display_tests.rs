@@ -0,0 +1,64 @@
+mod filter_display_tests
+{
+    use data_sculptor::core::filters::expression_parser::parse;
+    use data_sculptor::core::filters::filter::FilterType;
+
+    fn display(filter_type: FilterType, input: &str) -> String
+    {
+        parse(&filter_type, input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_commands()
+    {
+        assert_eq!(display(FilterType::Key, "contains \"a b\""), "{contains \"a b\"}");
+        assert_eq!(display(FilterType::Key, "{numop \">\" \"2.50\"}"), "{numop \">\" \"2.5\"}");
+        assert_eq!(display(FilterType::Value, "{kv-contains \"k\" \"v\"}"), "{kv-contains \"k\" \"v\"}");
+        assert_eq!(display(FilterType::Value, "{kv-numop \"k\" \"<\" \"-3\"}"), "{kv-numop \"k\" \"<\" \"-3\"}");
+        assert_eq!(display(FilterType::Date, "{date after \"2024-01-02\"}"), "{date after \"2024-01-02\"}");
+    }
+
+    #[test]
+    fn test_minimal_parentheses()
+    {
+        let cases =
+            [
+                ("(({a}))", "{contains \"a\"}"),
+                ("({a} and {b}) or {c}", "{contains \"a\"} and {contains \"b\"} or {contains \"c\"}"),
+                ("{a} and ({b} or {c})", "{contains \"a\"} and ({contains \"b\"} or {contains \"c\"})"),
+                ("({a} or {b}) xor {c}", "{contains \"a\"} or {contains \"b\"} xor {contains \"c\"}"),
+                ("{a} or ({b} xor {c})", "{contains \"a\"} or ({contains \"b\"} xor {contains \"c\"})"),
+                ("(not {a}) and {b}", "not {contains \"a\"} and {contains \"b\"}"),
+                ("not ({a} and {b})", "not ({contains \"a\"} and {contains \"b\"})"),
+                ("not (not {a})", "not not {contains \"a\"}"),
+                ("!{a} && ({b} || {c})", "not {contains \"a\"} and ({contains \"b\"} or {contains \"c\"})"),
+            ];
+
+        for (input, expected) in cases
+        {
+            let input = input.replace("{a}", "{contains \"a\"}")
+                .replace("{b}", "{contains \"b\"}")
+                .replace("{c}", "{contains \"c\"}");
+            assert_eq!(display(FilterType::Key, &input), expected);
+        }
+    }
+
+    #[test]
+    fn test_round_trip()
+    {
+        let inputs =
+            [
+                "{contains \"x\"} nand {numop \">\" \"1\"} xnor not {contains \"y\"}",
+                "not ({contains \"a\"} nor {contains \"b\"}) and ({contains \"c\"} xor {contains \"d\"})",
+                "{contains \"a\"} or {contains \"b\"} and {contains \"c\"} or not (not {contains \"d\"})",
+                "(({contains \"a\"} or {contains \"b\"}) and ({contains \"c\"} or {contains \"d\"}))",
+            ];
+
+        for input in inputs
+        {
+            let expression = parse(&FilterType::Key, input).unwrap();
+            let reparsed = parse(&FilterType::Key, &expression.to_string()).unwrap();
+            assert_eq!(reparsed, expression, "{}", input);
+        }
+    }
+}
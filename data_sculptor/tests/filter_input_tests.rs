@@ -0,0 +1,86 @@
+mod filter_input_tests
+{
+    use data_sculptor::core::filters::expression_parser::{highlight, HighlightKind};
+    use data_sculptor::core::filters::filter::FilterType;
+    use data_sculptor::core::filters::filter_completion::complete;
+
+    fn kinds(input: &str) -> Vec<(&str, HighlightKind)>
+    {
+        highlight(input).into_iter().map(|span| (&input[span.range], span.kind)).collect()
+    }
+
+    #[test]
+    fn test_highlight()
+    {
+        assert_eq!(kinds("not ({contains \"a\"} xand {b})"),
+        [
+            ("not", HighlightKind::Operator),
+            ("(", HighlightKind::Parenthesis),
+            ("{contains ", HighlightKind::Command),
+            ("\"a\"", HighlightKind::QuotedString),
+            ("}", HighlightKind::Command),
+            ("xand", HighlightKind::Invalid),
+            ("{b}", HighlightKind::Command),
+            (")", HighlightKind::Parenthesis)
+        ]);
+    }
+
+    #[test]
+    fn test_highlight_mismatched_parentheses()
+    {
+        assert_eq!(kinds("(({a})"),
+        [
+            ("(", HighlightKind::MismatchedParenthesis),
+            ("(", HighlightKind::Parenthesis),
+            ("{a}", HighlightKind::Command),
+            (")", HighlightKind::Parenthesis)
+        ]);
+
+        assert_eq!(kinds("{a})")[1], (")", HighlightKind::MismatchedParenthesis));
+    }
+
+    #[test]
+    fn test_complete_keywords()
+    {
+        let labels = |filter_type: FilterType, input: &str| complete(&filter_type, input, &[])
+            .into_iter().map(|completion| completion.label).collect::<Vec<_>>();
+
+        assert_eq!(labels(FilterType::Key, "{"), ["contains", "equals", "numop"]);
+        assert_eq!(labels(FilterType::Date, "{d"), ["date"]);
+        assert_eq!(labels(FilterType::Value, "{a} and {kv"), ["kv-contains", "kv-numop"]);
+        assert!(labels(FilterType::Value, "{a}").is_empty());
+
+        let completion = &complete(&FilterType::Key, "nu", &[])[0];
+        assert_eq!(completion.input, "numop \"");
+    }
+
+    #[test]
+    fn test_complete_keys()
+    {
+        let keys = [String::from("Speed"), String::from("Sleep"), String::from("Note")];
+
+        let completions = complete(&FilterType::Value, "{kv-numop \"s", &keys);
+        assert_eq!(completions.len(), 2);
+        assert_eq!(completions[0].input, "{kv-numop \"Speed\" \"");
+
+        // Only the first argument is a key
+        assert!(complete(&FilterType::Value, "{kv-numop \"Speed\" \"", &keys).is_empty());
+        assert!(complete(&FilterType::Key, "{kv-numop \"s", &keys).is_empty());
+    }
+
+    #[test]
+    fn test_escapes()
+    {
+        assert_eq!(kinds("{contains \"a\\\"\\}\"}"),
+        [
+            ("{contains ", HighlightKind::Command),
+            ("\"a\\\"\\}\"", HighlightKind::QuotedString),
+            ("}", HighlightKind::Command)
+        ]);
+
+        let keys = [String::from("Say \"hi\"")];
+        let completions = complete(&FilterType::Value, "{kv-contains \"say \\\"", &keys);
+        assert_eq!(completions[0].input, "{kv-contains \"Say \\\"hi\\\"\" \"");
+    }
+}
+
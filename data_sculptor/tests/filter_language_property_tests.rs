@@ -0,0 +1,317 @@
+mod filter_language_property_tests
+{
+    use chrono::NaiveDate;
+    use indexmap::IndexMap;
+    use proptest::prelude::*;
+    use data_sculptor::core::data_containers::{DateKey, DayDataParsed, EntryKey, EntryValue};
+    use data_sculptor::core::filters::expression_parser::{highlight, parse, parse_with_macros, FilterMacros};
+    use data_sculptor::core::filters::expression_simplifier::simplify;
+    use data_sculptor::core::filters::filter::{filter_key, Filter, FilterID, FilterType, KeyFilterMode};
+    use data_sculptor::core::filters::filter_completion::complete;
+
+    /// Number of distinct commands random expressions are built from.
+    const ATOMS: usize = 4;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Operator
+    {
+        And, Or, Xor, Nor, Nand, Xnor
+    }
+
+    impl Operator
+    {
+        const ALL: [Operator; 6] =
+            [Operator::And, Operator::Or, Operator::Xor, Operator::Nor, Operator::Nand, Operator::Xnor];
+
+        fn keyword(self) -> &'static str
+        {
+            match self
+            {
+                Operator::And => "and",
+                Operator::Or => "or",
+                Operator::Xor => "xor",
+                Operator::Nor => "nor",
+                Operator::Nand => "nand",
+                Operator::Xnor => "xnor"
+            }
+        }
+
+        /// Results for the operands (false, false), (false, true), (true, false) and (true, true).
+        fn truth_table(self) -> [bool; 4]
+        {
+            match self
+            {
+                Operator::And => [false, false, false, true],
+                Operator::Or => [false, true, true, true],
+                Operator::Xor => [false, true, true, false],
+                Operator::Nor => [true, false, false, false],
+                Operator::Nand => [true, true, true, false],
+                Operator::Xnor => [true, false, false, true]
+            }
+        }
+
+        fn apply(self, a: bool, b: bool) -> bool
+        {
+            self.truth_table()[(a as usize) * 2 + b as usize]
+        }
+
+        /// Whether the operator binds as strong as 'and'.
+        fn binds_tightly(self) -> bool
+        {
+            matches!(self, Operator::And | Operator::Nand)
+        }
+    }
+
+    /// Reference model of the filter language.
+    #[derive(Clone, Debug)]
+    enum Expr
+    {
+        Atom(usize),
+        Literal(bool),
+        Not(Box<Expr>),
+        Binary(Operator, Box<Expr>, Box<Expr>)
+    }
+
+    impl Expr
+    {
+        fn evaluate(&self, present: &[bool]) -> bool
+        {
+            match self
+            {
+                Expr::Atom(index) => present[*index],
+                Expr::Literal(value) => *value,
+                Expr::Not(inner) => !inner.evaluate(present),
+                Expr::Binary(op, a, b) => op.apply(a.evaluate(present), b.evaluate(present))
+            }
+        }
+
+        /// Fully parenthesized input, optionally using '!', '&&' and '||' instead of the keywords.
+        fn render(&self, aliases: bool) -> String
+        {
+            match self
+            {
+                Expr::Atom(index) => atom(*index),
+                Expr::Literal(value) => value.to_string(),
+                Expr::Not(inner) =>
+                    {
+                        format!("{}({})", if aliases {"!"} else {"not "}, inner.render(aliases))
+                    }
+                Expr::Binary(op, a, b) =>
+                    {
+                        let keyword = match op
+                        {
+                            Operator::And if aliases => "&&",
+                            Operator::Or if aliases => "||",
+                            _ => op.keyword()
+                        };
+                        format!("({}) {} ({})", a.render(aliases), keyword, b.render(aliases))
+                    }
+            }
+        }
+    }
+
+    fn atom(index: usize) -> String
+    {
+        format!("{{contains \"a{}\"}}", index)
+    }
+
+    fn leaf() -> impl Strategy<Value = Expr>
+    {
+        prop_oneof![
+            3 => (0..ATOMS).prop_map(Expr::Atom),
+            1 => any::<bool>().prop_map(Expr::Literal)
+        ]
+    }
+
+    fn operator() -> impl Strategy<Value = Operator>
+    {
+        proptest::sample::select(Operator::ALL.to_vec())
+    }
+
+    fn expr() -> impl Strategy<Value = Expr>
+    {
+        leaf().prop_recursive(5, 48, 2, |inner|
+            prop_oneof![
+                1 => inner.clone().prop_map(|e| Expr::Not(Box::new(e))),
+                3 => (operator(), inner.clone(), inner)
+                    .prop_map(|(op, a, b)| Expr::Binary(op, Box::new(a), Box::new(b)))
+            ])
+    }
+
+    /// Which of the atoms are contained in the key title.
+    fn presence() -> impl Strategy<Value = Vec<bool>>
+    {
+        proptest::collection::vec(any::<bool>(), ATOMS)
+    }
+
+    /// Evaluates the expression as the only key filter on a key containing the present atoms.
+    fn evaluate(expression: &Filter, present: &[bool]) -> bool
+    {
+        let title: String = (0..ATOMS).filter(|i| present[*i]).map(|i| format!("a{} ", i)).collect();
+        let key = EntryKey{title};
+
+        let mut filters = IndexMap::new();
+        filters.insert(FilterID::from(&expression.expression), expression.clone());
+        filter_key(&day(&key), &key, &filters, KeyFilterMode::Any)
+    }
+
+    /// A day holding only the given key.
+    fn day(key: &EntryKey) -> DayDataParsed
+    {
+        let mut entries = IndexMap::new();
+        entries.insert(key.clone(), EntryValue{string_value: String::new()});
+        DayDataParsed
+        {
+            date: DateKey
+            {
+                naive_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                date_string: String::from("2024-01-01")
+            },
+            entries
+        }
+    }
+
+    fn key_filter(input: &str) -> Filter
+    {
+        let expression = parse(&FilterType::Key, input)
+            .unwrap_or_else(|| panic!("failed to parse: {}", input));
+        Filter{title: expression.to_string(), expression, filter_type: FilterType::Key}
+    }
+
+    #[test]
+    fn test_truth_tables()
+    {
+        for op in Operator::ALL
+        {
+            for (a, b) in [(false, false), (false, true), (true, false), (true, true)]
+            {
+                let input = format!("{} {} {}", a, op.keyword(), b);
+                assert_eq!(evaluate(&key_filter(&input), &[false; ATOMS]), op.apply(a, b), "{}", input);
+            }
+        }
+
+        assert!(evaluate(&key_filter("not false"), &[false; ATOMS]));
+        assert!(!evaluate(&key_filter("not true"), &[false; ATOMS]));
+    }
+
+    proptest!
+    {
+        #[test]
+        fn test_evaluation_matches_reference(expr in expr(), present in presence(), aliases in any::<bool>())
+        {
+            let filter = key_filter(&expr.render(aliases));
+            let expected = expr.evaluate(&present);
+            prop_assert_eq!(evaluate(&filter, &present), expected);
+
+            let simplified = Filter{expression: simplify(&filter.expression), ..filter};
+            prop_assert_eq!(evaluate(&simplified, &present), expected);
+        }
+
+        #[test]
+        fn test_display_round_trip(expr in expr())
+        {
+            let parsed = parse(&FilterType::Key, &expr.render(false)).unwrap();
+            let printed = parsed.to_string();
+
+            let reparsed = parse(&FilterType::Key, &printed);
+            prop_assert_eq!(reparsed.as_ref(), Some(&parsed), "{}", printed);
+            prop_assert_eq!(reparsed.unwrap().to_string(), printed);
+        }
+
+        #[test]
+        fn test_escaped_keyword_round_trip(keyword in any::<String>())
+        {
+            let escaped = keyword.replace('\\', "\\\\").replace('"', "\\\"").replace('}', "\\}");
+            let input = format!("{{contains \"{}\"}}", escaped);
+
+            let filter = key_filter(&input);
+            prop_assert_eq!(&filter.title, &input);
+
+            let key = EntryKey{title: keyword};
+            let filters = IndexMap::from([(FilterID::from(&filter.expression), filter)]);
+            prop_assert!(filter_key(&day(&key), &key, &filters, KeyFilterMode::Any));
+        }
+
+        /// Inputs that were valid before escapes existed keep their meaning: a backslash that is
+        /// not in front of '\\', '"' or '}' is part of the keyword.
+        #[test]
+        fn test_unescaped_backslash_keyword(keyword in "[a-z:. ]*(\\\\[a-z:. ][a-z:. ]*)*")
+        {
+            let filter = key_filter(&format!("{{contains \"{}\"}}", keyword));
+            let reparsed = parse(&FilterType::Key, &filter.title);
+            prop_assert_eq!(reparsed.as_ref(), Some(&filter.expression));
+
+            let key = EntryKey{title: keyword};
+            let filters = IndexMap::from([(FilterID::from(&filter.expression), filter)]);
+            prop_assert!(filter_key(&day(&key), &key, &filters, KeyFilterMode::Any));
+        }
+
+        #[test]
+        fn test_precedence(first in (any::<bool>(), leaf()),
+                           rest in proptest::collection::vec((operator(), any::<bool>(), leaf()), 1..6),
+                           present in presence())
+        {
+            let operand = |(negated, leaf): &(bool, Expr)|
+                {
+                    let value = leaf.evaluate(&present);
+                    let text = leaf.render(false);
+                    if *negated {(!value, format!("not {}", text))} else {(value, text)}
+                };
+
+            // Reference: reduce 'and'/'nand' first, then everything else, both left to right
+            let (value, mut input) = operand(&first);
+            let mut terms = vec![value];
+            let mut loose_ops = Vec::new();
+            for (op, negated, leaf) in rest
+            {
+                let (value, text) = operand(&(negated, leaf));
+                input += &format!(" {} {}", op.keyword(), text);
+
+                if op.binds_tightly()
+                {
+                    let last = terms.pop().unwrap();
+                    terms.push(op.apply(last, value));
+                }
+                else
+                {
+                    loose_ops.push(op);
+                    terms.push(value);
+                }
+            }
+
+            let expected = loose_ops.iter().zip(&terms[1..])
+                .fold(terms[0], |acc, (op, term)| op.apply(acc, *term));
+            prop_assert_eq!(evaluate(&key_filter(&input), &present), expected, "{}", input);
+        }
+
+        #[test]
+        fn test_no_panics_on_arbitrary_input(input in any::<String>())
+        {
+            check_no_panics(&input);
+        }
+
+        #[test]
+        fn test_no_panics_on_filter_like_input(input in "[{}()\"@!&| a-z0-9äß€\\\\-]{0,40}")
+        {
+            check_no_panics(&input);
+        }
+    }
+
+    /// Runs the input through everything that handles user typed filters.
+    fn check_no_panics(input: &str)
+    {
+        let mut macros = FilterMacros::new();
+        macros.insert(String::from("m"), input.to_string());
+        macros.insert(String::from("n"), String::from("@m and {contains \"a\"}"));
+
+        let keys = vec![String::from("steps"), String::from("ä€")];
+        for filter_type in [FilterType::Date, FilterType::Key, FilterType::Value]
+        {
+            let _ = parse_with_macros(&filter_type, input, &macros);
+            let _ = parse_with_macros(&filter_type, &format!("{{{}}}", input), &FilterMacros::new());
+            let _ = parse_with_macros(&filter_type, &format!("@n or {}", input), &macros);
+            let _ = complete(&filter_type, input, &keys);
+        }
+        let _ = highlight(input);
+    }
+}
@@ -0,0 +1,48 @@
+mod filter_macro_tests
+{
+    use data_sculptor::core::filters::expression_parser::{parse, parse_with_macros, ExpressionError, FilterMacros};
+    use data_sculptor::core::filters::filter::{FilterID, FilterType};
+
+    fn macros(definitions: &[(&str, &str)]) -> FilterMacros
+    {
+        definitions.iter().map(|(name, input)| (name.to_string(), input.to_string())).collect()
+    }
+
+    #[test]
+    fn test_resolve()
+    {
+        let macros = macros(&[
+            ("workout_day", "{kv-contains \"activity\" \"workout\"} or @long_walk"),
+            ("long_walk", "kv-numop \"steps\" \">\" \"15000\"")
+        ]);
+
+        let input = "@workout_day and not {kv-contains \"mood\" \"bad\"}";
+        let expression = parse_with_macros(&FilterType::Value, input, &macros).unwrap();
+        assert_eq!(expression.to_string(), input);
+
+        // Same id as the expression written out
+        let expanded = parse(&FilterType::Value, "({kv-contains \"activity\" \"workout\"} \
+            or {kv-numop \"steps\" \">\" \"15000\"}) and not {kv-contains \"mood\" \"bad\"}").unwrap();
+        assert_eq!(FilterID::from(&expression), FilterID::from(&expanded));
+    }
+
+    #[test]
+    fn test_errors()
+    {
+        let macros = macros(&[
+            ("a", "@b"),
+            ("b", "{contains \"x\"} and @c"),
+            ("c", "@a"),
+            ("own", "@own"),
+            ("broken", "{contains \"x\"} and")
+        ]);
+        let parse = |input: &str| parse_with_macros(&FilterType::Key, input, &macros);
+
+        assert_eq!(parse("@missing"), Err(ExpressionError::UnknownMacro(String::from("missing"))));
+        assert_eq!(parse("@own"), Err(ExpressionError::MacroCycle(vec![String::from("own")])));
+        assert_eq!(parse("{contains \"y\"} or @b"), Err(ExpressionError::MacroCycle(
+            vec![String::from("b"), String::from("c"), String::from("a")])));
+        assert_eq!(parse("@broken"), Err(ExpressionError::InvalidMacro(String::from("broken"))));
+        assert_eq!(parse("@"), Err(ExpressionError::InvalidSyntax));
+    }
+}
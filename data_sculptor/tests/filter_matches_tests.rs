@@ -0,0 +1,79 @@
+mod filter_matches_tests
+{
+    use indexmap::IndexMap;
+    use data_sculptor::core::data_containers::{DayDataParsed, DayDataUnparsed, EntryKey, parse_and_sort_by_date};
+    use data_sculptor::core::filters::expression_parser;
+    use data_sculptor::core::filters::filter::{Filter, FilterID, FilterType, KeyFilterMode};
+    use data_sculptor::core::filters::filter_matches::{find_matches, split_by_ranges, FilterMatches};
+
+    fn day() -> DayDataParsed
+    {
+        let mut entries: IndexMap<String, String> = IndexMap::new();
+        entries.insert(String::from("food"), String::from("tomato pasta with tomato sauce"));
+        entries.insert(String::from("mood"), String::from("good"));
+        let days = vec![DayDataUnparsed{date: String::from("2024-01-02"), entries}];
+        parse_and_sort_by_date(days).unwrap().remove(0)
+    }
+
+    fn filters(filter_type: FilterType, inputs: &[&str]) -> IndexMap<FilterID, Filter>
+    {
+        let mut filters: IndexMap<FilterID, Filter> = IndexMap::new();
+        for input in inputs
+        {
+            let expression = expression_parser::parse(&filter_type, input).unwrap();
+            filters.insert
+            (
+                FilterID::from(&expression),
+                Filter{title: input.to_string(), expression, filter_type: filter_type.clone()}
+            );
+        }
+        filters
+    }
+
+    fn find(date_filters: &[&str], key_filters: &[&str], value_filters: &[&str]) -> FilterMatches
+    {
+        find_matches(&day(), &filters(FilterType::Date, date_filters), &filters(FilterType::Key, key_filters),
+                     KeyFilterMode::Any, &filters(FilterType::Value, value_filters))
+    }
+
+    fn key(title: &str) -> EntryKey
+    {
+        EntryKey{title: String::from(title)}
+    }
+
+    #[test]
+    fn test_find_matches()
+    {
+        let matches = find(&["{contains \"01\"}"], &["{contains \"oo\"}"],
+                       &["{kv-contains \"food\" \"tomato\"} or {kv-contains \"mood\" \"bad\"}"]);
+
+        assert_eq!(matches.date_ranges(), vec![5..7]);
+        assert_eq!(matches.key_ranges(&key("food")), vec![1..3]);
+        assert_eq!(matches.key_ranges(&key("mood")), vec![1..3]);
+        assert_eq!(matches.value_ranges(&key("food")), [0..6, 18..24]);
+        assert!(matches.value_ranges(&key("mood")).is_empty());
+    }
+
+    #[test]
+    fn test_only_reasons_for_passing_match()
+    {
+        // The failing side of an 'and' does not count
+        let matches = find(&[], &[], &["({kv-contains \"food\" \"pasta\"} and {kv-contains \"mood\" \"bad\"}) \
+            or {kv-contains \"mood\" \"good\"}"]);
+        assert!(matches.value_ranges(&key("food")).is_empty());
+        assert_eq!(matches.value_ranges(&key("mood")), vec![0..4]);
+
+        // Nothing below a negation matches
+        let matches = find(&[], &["not not {contains \"food\"}"], &[]);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_split_by_ranges()
+    {
+        assert_eq!(split_by_ranges("tomato pasta", &[0..6, 7..9]),
+                   [("tomato", true), (" ", false), ("pa", true), ("sta", false)]);
+        assert_eq!(split_by_ranges("äb", std::slice::from_ref(&(1..2))), [("äb", false)]);
+        assert_eq!(split_by_ranges("", &[]), []);
+    }
+}
@@ -0,0 +1,109 @@
+mod filter_preview_tests
+{
+    use std::sync::Mutex;
+    use indexmap::IndexMap;
+    use data_sculptor::core::data_containers::{DayDataUnparsed, parse_and_sort_by_date};
+    use data_sculptor::core::data_manager::DataManager;
+    use data_sculptor::core::filters::expression_parser::{parse_with_macros, FilterMacros};
+    use data_sculptor::core::filters::filter::FilterType;
+    use data_sculptor::gui::gui_message::GUIMessage;
+    use data_sculptor::gui::views::filter::filter_view_control::FilterPreview;
+    use data_sculptor::gui::views::list::list_view_control::ListView;
+
+    fn data_manager() -> Mutex<DataManager>
+    {
+        let mut days: Vec<DayDataUnparsed> = Vec::new();
+        for (date, speed) in [("2024-01-01", "5"), ("2024-01-02", "15"), ("2024-01-03", "25")]
+        {
+            let mut entries: IndexMap<String, String> = IndexMap::new();
+            entries.insert(String::from("speed"), String::from(speed));
+            entries.insert(String::from("speed_max"), String::from("30"));
+            entries.insert(String::from("note"), String::from("something"));
+            days.push(DayDataUnparsed{date: String::from(date), entries});
+        }
+
+        let mut data_manager = DataManager::default();
+        data_manager.replace_data(parse_and_sort_by_date(days).unwrap(), "test.json");
+        Mutex::new(data_manager)
+    }
+
+    /// Sends the given messages to a new list view and returns the preview of the filter view
+    /// of the given type after typing the given input into it.
+    fn preview(messages: Vec<GUIMessage>, filter_type: FilterType, input: &str) -> FilterPreview
+    {
+        let dm = data_manager();
+        let mut list_view = ListView::default();
+        for message in messages
+        {
+            let _ = list_view.update(message, &dm);
+        }
+
+        let _ = list_view.update(GUIMessage::OpenFilterView(filter_type.clone()), &dm);
+        let _ = list_view.update(GUIMessage::FilterInputChanged(String::from(input)), &dm);
+        list_view.get_filter_view(&filter_type).preview.clone()
+    }
+
+    #[test]
+    fn test_empty_and_invalid()
+    {
+        assert_eq!(preview(vec![], FilterType::Date, "  "), FilterPreview::Empty);
+        let input = "{contains \"a\"} and";
+        let error = parse_with_macros(&FilterType::Date, input, &FilterMacros::new()).err().unwrap();
+        assert_eq!(preview(vec![], FilterType::Date, input), FilterPreview::Invalid(error.to_string()));
+
+        // Commands of other filter types are invalid
+        assert!(matches!(preview(vec![], FilterType::Value, "{contains \"a\"}"), FilterPreview::Invalid(_)));
+    }
+
+    #[test]
+    fn test_days_kept()
+    {
+        assert_eq!(preview(vec![], FilterType::Date, "{date after \"2024-01-01\"}"),
+                   FilterPreview::Valid {kept: 2, current: 3});
+        assert_eq!(preview(vec![], FilterType::Value, "{kv-numop \"speed\" \">\" \"10\"}"),
+                   FilterPreview::Valid {kept: 2, current: 3});
+
+        // Filters of the other types are already applied
+        let date_filter = vec![
+            GUIMessage::OpenFilterView(FilterType::Date),
+            GUIMessage::FilterInputChanged(String::from("{date after \"2024-01-02\"}")),
+            GUIMessage::AddFilter
+        ];
+        assert_eq!(preview(date_filter, FilterType::Value, "{kv-numop \"speed\" \"<\" \"20\"}"),
+                   FilterPreview::Valid {kept: 0, current: 1});
+    }
+
+    #[test]
+    fn test_keys_kept()
+    {
+        // Key filters count keys instead of days
+        assert_eq!(preview(vec![], FilterType::Key, "{contains \"speed\"}"),
+                   FilterPreview::Valid {kept: 2, current: 3});
+        assert_eq!(preview(vec![], FilterType::Key, "{equals \"speed\"}"),
+                   FilterPreview::Valid {kept: 1, current: 3});
+
+        // With the default mode, another key filter shows more keys
+        let key_filter = vec![
+            GUIMessage::OpenFilterView(FilterType::Key),
+            GUIMessage::FilterInputChanged(String::from("{equals \"note\"}")),
+            GUIMessage::AddFilter
+        ];
+        assert_eq!(preview(key_filter, FilterType::Key, "{equals \"speed\"}"),
+                   FilterPreview::Valid {kept: 2, current: 1});
+    }
+
+    #[test]
+    fn test_same_input_in_other_view()
+    {
+        let dm = data_manager();
+        let mut list_view = ListView::default();
+        for filter_type in [FilterType::Date, FilterType::Key]
+        {
+            let _ = list_view.update(GUIMessage::OpenFilterView(filter_type), &dm);
+            let _ = list_view.update(GUIMessage::FilterInputChanged(String::from("{contains \"2024\"}")), &dm);
+        }
+
+        assert_eq!(list_view.get_filter_view(&FilterType::Date).preview, FilterPreview::Valid {kept: 3, current: 3});
+        assert_eq!(list_view.get_filter_view(&FilterType::Key).preview, FilterPreview::Valid {kept: 0, current: 3});
+    }
+}
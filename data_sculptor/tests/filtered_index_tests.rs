@@ -0,0 +1,112 @@
+mod filtered_index_tests
+{
+    use indexmap::IndexMap;
+    use data_sculptor::core::data_containers::{DayDataParsed, DayDataUnparsed, EntryKey, parse_and_sort_by_date};
+    use data_sculptor::core::filters::expression_parser;
+    use data_sculptor::core::filters::filter::{Filter, FilterID, FilterType, KeyFilterMode, KeySelection};
+    use data_sculptor::core::filters::filtered_index::{FilteredDay, FilteredIndex};
+
+    fn data() -> Vec<DayDataParsed>
+    {
+        let mut days: Vec<DayDataUnparsed> = Vec::new();
+        for (date, speed) in [("2024-01-01", "5"), ("2024-01-02", "15"), ("2024-01-03", "25")]
+        {
+            let mut entries: IndexMap<String, String> = IndexMap::new();
+            entries.insert(String::from("speed"), String::from(speed));
+            entries.insert(String::from("note"), String::from("something"));
+            days.push(DayDataUnparsed{date: String::from(date), entries});
+        }
+        parse_and_sort_by_date(days).unwrap()
+    }
+
+    fn filters(filter_type: FilterType, inputs: &[&str]) -> IndexMap<FilterID, Filter>
+    {
+        let mut filters: IndexMap<FilterID, Filter> = IndexMap::new();
+        for input in inputs
+        {
+            let expression = expression_parser::parse(&filter_type, input).unwrap();
+            filters.insert
+            (
+                FilterID::from(&expression),
+                Filter{title: input.to_string(), expression, filter_type: filter_type.clone()}
+            );
+        }
+        filters
+    }
+
+    #[test]
+    fn test_no_filters()
+    {
+        let data = data();
+        let none = IndexMap::new();
+        let index = FilteredIndex::build(&data, &none, &none, KeyFilterMode::Any, &none);
+
+        assert_eq!(index.days.len(), 3);
+        assert_eq!(index.days[2], FilteredDay{day_index: 2, entry_indices: vec![0, 1]});
+        assert_eq!(index.keys, [EntryKey{title: String::from("speed")},
+            EntryKey{title: String::from("note")}]);
+    }
+
+    #[test]
+    fn test_day_and_key_filters()
+    {
+        let data = data();
+        let date_filters = filters(FilterType::Date, &["{date after \"2024-01-01\"}"]);
+        let key_filters = filters(FilterType::Key, &["{contains \"note\"}"]);
+        let value_filters = filters(FilterType::Value, &["{kv-numop \"speed\" \"<\" \"20\"}"]);
+
+        let index = FilteredIndex::build(&data, &date_filters, &key_filters, KeyFilterMode::Any, &value_filters);
+
+        assert_eq!(index.days, [FilteredDay{day_index: 1, entry_indices: vec![1]}]);
+        assert_eq!(index.keys, [EntryKey{title: String::from("note")}]);
+        assert_eq!(index.resolve(&data)[0].date.date_string, "2024-01-02");
+    }
+
+    #[test]
+    fn test_key_filter_modes()
+    {
+        let data = data();
+        let none = IndexMap::new();
+        let key_filters = filters(FilterType::Key, &["{contains \"e\"}", "not {contains \"note\"}"]);
+        let keys = |mode: KeyFilterMode| FilteredIndex::build(&data, &none, &key_filters, mode, &none).keys;
+
+        assert_eq!(keys(KeyFilterMode::Any).len(), 2);
+        assert_eq!(keys(KeyFilterMode::All), [EntryKey{title: String::from("speed")}]);
+    }
+
+    #[test]
+    fn test_filters_from_keys()
+    {
+        let keys = [String::from("speed"), String::from("note")];
+        let include = Filter::from_keys(&keys, KeySelection::Include).unwrap();
+        let exclude = Filter::from_keys(&keys[1..], KeySelection::Exclude).unwrap();
+
+        assert_eq!(include.title, "{equals \"speed\"} or {equals \"note\"}");
+        assert_eq!(exclude.title, "not {equals \"note\"}");
+        assert!(Filter::from_keys(&[], KeySelection::Include).is_none());
+    }
+
+    #[test]
+    fn test_filters_from_keys_match_exactly()
+    {
+        let mut entries: IndexMap<String, String> = IndexMap::new();
+        for key in ["speed", "speed_max", "note"]
+        {
+            entries.insert(String::from(key), String::from("1"));
+        }
+        let data = parse_and_sort_by_date(vec![DayDataUnparsed{date: String::from("2024-01-01"), entries}]).unwrap();
+        let none = IndexMap::new();
+
+        // 'speed' is a prefix of 'speed_max', which is not selected
+        let keys = |selection: KeySelection|
+        {
+            let filter = Filter::from_keys(&[String::from("speed")], selection).unwrap();
+            let key_filters = IndexMap::from([(FilterID::from(&filter.expression), filter)]);
+            FilteredIndex::build(&data, &none, &key_filters, KeyFilterMode::Any, &none).keys
+        };
+
+        assert_eq!(keys(KeySelection::Include), [EntryKey{title: String::from("speed")}]);
+        assert_eq!(keys(KeySelection::Exclude), [EntryKey{title: String::from("speed_max")},
+            EntryKey{title: String::from("note")}]);
+    }
+}
@@ -0,0 +1,121 @@
+mod history_tests
+{
+    use data_sculptor::core::filters::expression_parser::parse_with_macros;
+    use data_sculptor::core::filters::filter::{FilterID, FilterType};
+    use data_sculptor::gui::gui_message::GUIMessage;
+    use data_sculptor::gui::history::{History, HistoryCommand};
+    use data_sculptor::gui::views::filter::filter_view_control::FilterView;
+
+    #[test]
+    fn test_bounded_undo_redo()
+    {
+        let mut history: History<i32> = History::new(3);
+        for change in 1..=5
+        {
+            history.record(-change);
+        }
+
+        // Only the last three changes can be undone
+        assert_eq!(history.undo(), Some(-5));
+        history.undone(5);
+        assert_eq!(history.undo(), Some(-4));
+        history.undone(4);
+        assert_eq!(history.undo(), Some(-3));
+        history.undone(3);
+        assert_eq!(history.undo(), None);
+
+        assert_eq!(history.redo(), Some(3));
+        history.redone(-3);
+        assert!(history.can_undo());
+
+        // A new change clears everything that could be redone
+        history.record(-6);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_filter_changes()
+    {
+        let mut history: History<HistoryCommand> = History::new(10);
+        let mut filter_view = FilterView::from(FilterType::Key);
+
+        for input in ["{contains \"a\"}", "{contains \"b\"}"]
+        {
+            let _ = filter_view.update(GUIMessage::FilterInputChanged(input.to_string()), &mut history);
+            let _ = filter_view.update(GUIMessage::AddFilter, &mut history);
+        }
+
+        // Edit the first filter
+        let first_id = filter_view.filters.get_index(0).unwrap().0.clone();
+        let _ = filter_view.update(GUIMessage::ClickFilter(first_id), &mut history);
+        let _ = filter_view.update(GUIMessage::FilterInputChanged("{contains \"c\"}".to_string()), &mut history);
+        let _ = filter_view.update(GUIMessage::UpdateFilter, &mut history);
+
+        let titles = |view: &FilterView| view.filters.values().map(|f| f.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&filter_view), ["{contains \"c\"}", "{contains \"b\"}"]);
+
+        // Undo everything
+        while let Some(HistoryCommand::Filter(_, change)) = history.undo()
+        {
+            let redo = filter_view.apply_change(change).unwrap();
+            history.undone(HistoryCommand::Filter(FilterType::Key, redo));
+        }
+        assert!(filter_view.filters.is_empty());
+
+        // Redo everything
+        while let Some(HistoryCommand::Filter(_, change)) = history.redo()
+        {
+            let inverse = filter_view.apply_change(change).unwrap();
+            history.redone(HistoryCommand::Filter(FilterType::Key, inverse));
+        }
+        assert_eq!(titles(&filter_view), ["{contains \"c\"}", "{contains \"b\"}"]);
+    }
+
+    #[test]
+    fn test_macro_changes()
+    {
+        let mut history: History<HistoryCommand> = History::new(10);
+        let mut filter_view = FilterView::from(FilterType::Key);
+
+        let messages = |macro_input: &str| vec![
+            GUIMessage::MacroNameInputChanged("m".to_string()),
+            GUIMessage::MacroInputChanged(macro_input.to_string()),
+            GUIMessage::DefineMacro
+        ];
+
+        let mut all_messages = messages("{contains \"a\"}");
+        all_messages.push(GUIMessage::FilterInputChanged("@m".to_string()));
+        all_messages.push(GUIMessage::AddFilter);
+        for message in all_messages
+        {
+            let _ = filter_view.update(message, &mut history);
+        }
+
+        let id = filter_view.filters.get_index(0).unwrap().0.clone();
+        let _ = filter_view.update(GUIMessage::DeleteFilter(id.clone()), &mut history);
+
+        // Redefining the macro changes the id the deleted filter would have
+        for message in messages("{contains \"b\"}")
+        {
+            let _ = filter_view.update(message, &mut history);
+        }
+        assert_eq!(filter_view.macros["m"], "{contains \"b\"}");
+
+        // Undoing the redefinition and the deletion brings back the filter as it was
+        for _ in 0..2
+        {
+            let Some(HistoryCommand::Filter(_, change)) = history.undo() else {panic!("Nothing to undo")};
+            let redo = filter_view.apply_change(change).unwrap();
+            history.undone(HistoryCommand::Filter(FilterType::Key, redo));
+        }
+        assert_eq!(filter_view.macros["m"], "{contains \"a\"}");
+        let expression = parse_with_macros(&FilterType::Key, "@m", &filter_view.macros).unwrap();
+        assert_eq!(filter_view.filters.keys().collect::<Vec<_>>(), [&FilterID::from(&expression)]);
+        assert_eq!(filter_view.filters[&id].title, "@m");
+
+        // Undoing the addition of the filter still finds it
+        let Some(HistoryCommand::Filter(_, change)) = history.undo() else {panic!("Nothing to undo")};
+        assert!(filter_view.apply_change(change).is_some());
+        assert!(filter_view.filters.is_empty());
+    }
+}
@@ -232,3 +232,44 @@ mod load_data_str_tests
         assert!(result.is_err());
     }
 }
+
+mod load_data_file_with_progress_tests
+{
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::sync::atomic::AtomicBool;
+    use data_sculptor::file_io::data_loader::load_data_file_with_progress;
+
+    fn write_temp_file(name: &str, content: &str) -> String
+    {
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_reports_progress()
+    {
+        let path = write_temp_file("data_sculptor_progress_test.json",
+                                   "{\"2023-10-05\": {\"something\": \"1\"}}");
+
+        let mut progress: Vec<f32> = Vec::new();
+        let days = load_data_file_with_progress(&path, &mut |p| progress.push(p),
+                                                &AtomicBool::new(false)).unwrap();
+
+        assert_eq!(days.len(), 1);
+        assert_eq!(*progress.last().unwrap(), 1.0);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_cancelled()
+    {
+        let path = write_temp_file("data_sculptor_cancel_test.json", "{}");
+
+        let result = load_data_file_with_progress(&path, &mut |_| {}, &AtomicBool::new(true));
+
+        assert_eq!(result.err().unwrap().kind(), ErrorKind::Interrupted);
+        fs::remove_file(path).unwrap();
+    }
+}
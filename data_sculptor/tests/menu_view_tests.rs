@@ -0,0 +1,35 @@
+mod menu_view_tests
+{
+    use std::fs;
+    use data_sculptor::core::settings::Settings;
+    use data_sculptor::gui::gui_message::GUIMessage;
+    use data_sculptor::gui::views::menu::menu_view_control::MenuView;
+
+    #[test]
+    fn test_missing_recent_files()
+    {
+        let existing = std::env::temp_dir().join("data_sculptor_menu_test.json").to_string_lossy().into_owned();
+        let missing = std::env::temp_dir().join("data_sculptor_menu_test_missing.json").to_string_lossy().into_owned();
+        fs::write(&existing, "{}").unwrap();
+        let _ = fs::remove_file(&missing);
+
+        let mut settings = Settings {recent_files: vec![existing.clone(), missing.clone()], ..Default::default()};
+        let mut menu_view = MenuView::default();
+        menu_view.check_recent_files(&settings.recent_files);
+        assert_eq!(menu_view.missing_files.iter().collect::<Vec<_>>(), [&missing]);
+
+        // The flag is only updated when the files are checked again
+        fs::write(&missing, "{}").unwrap();
+        assert!(menu_view.missing_files.contains(&missing));
+        menu_view.check_recent_files(&settings.recent_files);
+        assert!(menu_view.missing_files.is_empty());
+
+        let _ = fs::remove_file(&missing);
+        menu_view.check_recent_files(&settings.recent_files);
+        let _ = menu_view.update(GUIMessage::RemoveRecentFile(missing.clone()), &mut settings);
+        let _ = fs::remove_file(&existing);
+
+        assert_eq!(settings.recent_files, [existing]);
+        assert!(menu_view.missing_files.is_empty());
+    }
+}
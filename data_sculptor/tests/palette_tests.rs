@@ -0,0 +1,54 @@
+mod palette_tests
+{
+    use std::fs;
+    use iced::Color;
+    use data_sculptor::file_io::config_handler::{read_palette, write_palette};
+    use data_sculptor::core::palette::Palette;
+
+    fn read(name: &str, content: &str) -> Result<Palette, String>
+    {
+        let path = std::env::temp_dir().join(format!("data_sculptor_palette_test_{}.json", name));
+        fs::write(&path, content).unwrap();
+        let palette = read_palette(&path);
+        let _ = fs::remove_file(&path);
+        palette
+    }
+
+    #[test]
+    fn test_write_read_round_trip()
+    {
+        let palette = Palette
+        {
+            name: String::from("Sepia"),
+            background: Color::from_rgb8(0xF4, 0xEC, 0xD8),
+            string: Color::from_rgba8(0xA6, 0x5E, 0x00, 0.5),
+            ..Palette::light()
+        };
+
+        let path = std::env::temp_dir().join("data_sculptor_palette_test").join("sepia.json");
+        write_palette(&path, &palette).unwrap();
+        let read = read_palette(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(read.name, palette.name);
+        assert_eq!(read.background.into_rgba8(), palette.background.into_rgba8());
+        assert_eq!(read.string.into_rgba8(), palette.string.into_rgba8());
+        assert_eq!(read.value.into_rgba8(), palette.value.into_rgba8());
+    }
+
+    #[test]
+    fn test_missing_colors_are_dark()
+    {
+        let palette = read("partial", "{\"name\": \"Ocean\", \"background\": \"#003050\"}").unwrap();
+        assert_eq!(palette.background.into_rgba8(), [0x00, 0x30, 0x50, 0xFF]);
+        assert_eq!(palette.date, Palette::dark().date);
+    }
+
+    #[test]
+    fn test_invalid_palettes()
+    {
+        assert!(read("invalid_color", "{\"name\": \"Ocean\", \"background\": \"#0030\u{e4}\"}").is_err());
+        assert!(read("no_name", "{\"background\": \"#003050\"}").is_err());
+        assert!(read("built_in_name", "{\"name\": \"Light\"}").is_err());
+    }
+}
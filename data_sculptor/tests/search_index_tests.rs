@@ -0,0 +1,137 @@
+mod search_index_tests
+{
+    use indexmap::IndexMap;
+    use data_sculptor::core::data_containers::{DayDataParsed, DayDataUnparsed, parse_and_sort_by_date};
+    use data_sculptor::core::data_edit::DataEdit;
+    use data_sculptor::core::data_manager::DataManager;
+    use data_sculptor::core::search_index::{tokenize, QueryPart, SearchIndex, SearchQuery};
+
+    fn data() -> Vec<DayDataParsed>
+    {
+        let days = [
+            ("2024-01-02", [("note", "Went for a long walk"), ("mood", "good")]),
+            ("2024-01-01", [("note", "Walked the dog, long day"), ("mood", "tired")]),
+            ("2024-01-03", [("note", "Nothing"), ("mood", "Good, long walk planned")])
+        ];
+
+        let days: Vec<DayDataUnparsed> = days.into_iter()
+            .map(|(date, entries)|
+                {
+                    let entries: IndexMap<String, String> = entries.into_iter()
+                        .map(|(key, value)| (String::from(key), String::from(value)))
+                        .collect();
+                    DayDataUnparsed{date: String::from(date), entries}
+                })
+            .collect();
+        parse_and_sort_by_date(days).unwrap()
+    }
+
+    /// The dates and keys of all values found for the given input.
+    fn search(input: &str) -> Vec<(String, String)>
+    {
+        let data = data();
+        let index = SearchIndex::build(&data);
+        index.search(&SearchQuery::parse(input), &data).into_iter()
+            .map(|hit|
+                {
+                    let day = &data[hit.day_index];
+                    let (key, _) = day.entries.get_index(hit.entry_index).unwrap();
+                    (day.date.date_string.clone(), key.title.clone())
+                })
+            .collect()
+    }
+
+    fn hits(expected: &[(&str, &str)]) -> Vec<(String, String)>
+    {
+        expected.iter().map(|(date, key)| (date.to_string(), key.to_string())).collect()
+    }
+
+    #[test]
+    fn test_tokenize()
+    {
+        let words = tokenize("Hello, wörld!  42x");
+        let expected = [("hello", 0..5), ("wörld", 7..13), ("42x", 16..19)];
+        assert_eq!(words, expected.map(|(word, range)| (String::from(word), range)));
+        assert!(tokenize(" ,. ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_query()
+    {
+        let query = SearchQuery::parse("walk* \"long  Walk\" dog-day \"single\" \"open quote");
+        assert_eq!(query.parts, [
+            QueryPart::Word {word: String::from("walk"), prefix: true},
+            QueryPart::Phrase(vec![String::from("long"), String::from("walk")]),
+            QueryPart::Word {word: String::from("dog"), prefix: false},
+            QueryPart::Word {word: String::from("day"), prefix: false},
+            QueryPart::Word {word: String::from("single"), prefix: false},
+            QueryPart::Phrase(vec![String::from("open"), String::from("quote")])
+        ]);
+        assert!(SearchQuery::parse("  \"\" * ").is_empty());
+    }
+
+    #[test]
+    fn test_search()
+    {
+        // Results are sorted by date
+        assert_eq!(search("LONG"), hits(&[("2024-01-01", "note"), ("2024-01-02", "note"), ("2024-01-03", "mood")]));
+        assert_eq!(search("walk"), hits(&[("2024-01-02", "note"), ("2024-01-03", "mood")]));
+        assert_eq!(search("walk*"), hits(&[("2024-01-01", "note"), ("2024-01-02", "note"), ("2024-01-03", "mood")]));
+
+        // Every part has to be found in the same value
+        assert_eq!(search("good long"), hits(&[("2024-01-03", "mood")]));
+        assert_eq!(search("dog good"), hits(&[]));
+
+        assert_eq!(search("\"long walk\""), hits(&[("2024-01-02", "note"), ("2024-01-03", "mood")]));
+        assert_eq!(search("\"walk long\""), hits(&[]));
+        assert_eq!(search(""), hits(&[]));
+        assert_eq!(search("missing"), hits(&[]));
+    }
+
+    #[test]
+    fn test_search_ranges()
+    {
+        let data = data();
+        let index = SearchIndex::build(&data);
+
+        let hits = index.search(&SearchQuery::parse("\"a long\" wal*"), &data);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].ranges, [9..10, 11..15, 16..20]);
+    }
+
+    #[test]
+    fn test_update_day()
+    {
+        let mut dm = DataManager::default();
+        dm.replace_data(data(), "test.json");
+        let mut index = SearchIndex::build(&dm.data);
+
+        let mut entries: IndexMap<String, String> = IndexMap::new();
+        entries.insert(String::from("note"), String::from("Short walk"));
+        let edits =
+            [
+                DataEdit::SetValue{date: "2024-01-02".into(), key: "note".into(), value: "No walk today".into()},
+                DataEdit::InsertKey{date: "2024-01-01".into(), key: "plan".into(), value: "long walk".into(), index: 0},
+                DataEdit::InsertDay{date: "2023-12-31".into(), entries},
+                DataEdit::RemoveKey{date: "2024-01-03".into(), key: "note".into()},
+                DataEdit::RemoveDay{date: "2024-01-01".into()}
+            ];
+
+        for edit in edits
+        {
+            let date = edit.date().to_string();
+            let old_day = dm.find_day(&date).map(|day_index| (day_index, dm.data[day_index].clone()));
+            dm.apply_edit(edit).unwrap();
+            let new_day = dm.find_day(&date).map(|day_index| (day_index, &dm.data[day_index]));
+            index.update_day(old_day.as_ref().map(|(day_index, day)| (*day_index, day)), new_day);
+
+            // The updated index finds the same as one built from the edited data
+            let rebuilt = SearchIndex::build(&dm.data);
+            for input in ["walk", "long", "\"long walk\"", "wal*", "good", "today", "dog"]
+            {
+                let query = SearchQuery::parse(input);
+                assert_eq!(index.search(&query, &dm.data), rebuilt.search(&query, &dm.data), "{}", input);
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+mod settings_tests
+{
+    use std::fs;
+    use data_sculptor::core::filters::expression_parser::FilterMacros;
+    use data_sculptor::core::filters::filter::KeyFilterMode;
+    use data_sculptor::core::settings::{is_valid_date_format, SavedFilterSet, Settings, MAX_FONT_SIZE, MAX_RECENT_FILES};
+    use data_sculptor::file_io::config_handler::{read_settings, write_settings};
+
+    #[test]
+    fn test_write_read_round_trip()
+    {
+        let mut macros = FilterMacros::new();
+        macros.insert(String::from("sport"), String::from("{contains \"run\"}"));
+
+        let mut settings = Settings
+        {
+            theme: String::from("Light"),
+            date_display_format: String::from("%d.%m.%Y"),
+            last_opened_file: Some(String::from("data.json")),
+            ..Default::default()
+        };
+        settings.filters.key = SavedFilterSet{filters: vec![String::from("@sport")], macros};
+        settings.filters.key_filter_mode = KeyFilterMode::All;
+
+        let path = std::env::temp_dir().join("data_sculptor_settings_test").join("config.json");
+        write_settings(&path, &settings).unwrap();
+        assert_eq!(read_settings(&path).unwrap(), settings);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_missing_and_unusable_values()
+    {
+        let path = std::env::temp_dir().join("data_sculptor_settings_test_partial.json");
+        fs::write(&path, "{\"font_size\": 1000, \"date_display_format\": \"%H:%M\"}").unwrap();
+        let settings = read_settings(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(settings.font_size, MAX_FONT_SIZE);
+        assert_eq!(settings.date_display_format, Settings::default().date_display_format);
+        assert_eq!(settings.theme, Settings::default().theme);
+        assert!(settings.restore_filters);
+    }
+
+    #[test]
+    fn test_date_formats()
+    {
+        assert!(is_valid_date_format("%Y-%m-%d"));
+        assert!(is_valid_date_format("%d. %B %Y"));
+        assert!(!is_valid_date_format("%H"));
+        assert!(!is_valid_date_format("%Q"));
+        assert!(!is_valid_date_format(" "));
+    }
+
+    #[test]
+    fn test_recent_files()
+    {
+        let mut settings = Settings::default();
+        settings.add_recent_file("a.json");
+        settings.add_recent_file("b.json");
+        settings.add_recent_file("a.json");
+        assert_eq!(settings.recent_files, ["a.json", "b.json"]);
+        assert_eq!(settings.last_opened_file.as_deref(), Some("a.json"));
+
+        for i in 0..2 * MAX_RECENT_FILES
+        {
+            settings.add_recent_file(&format!("{}.json", i));
+        }
+        assert_eq!(settings.recent_files.len(), MAX_RECENT_FILES);
+        assert_eq!(settings.recent_files[0], format!("{}.json", 2 * MAX_RECENT_FILES - 1));
+    }
+}
@@ -0,0 +1,57 @@
+mod text_formats_tests
+{
+    use indexmap::IndexMap;
+    use data_sculptor::file_io::text_formats::{all_keys, format_days, DayEntries, TextFormat};
+
+    fn days() -> DayEntries
+    {
+        let mut days: DayEntries = IndexMap::new();
+        for (date, entries) in [
+            ("2024-01-01", vec![("mood", "good"), ("note", "a, \"quoted\" note")]),
+            ("2024-01-02", vec![("steps", "9000"), ("mood", "tired | sleepy\nwent to bed")])
+        ]
+        {
+            let entries = entries.into_iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+            days.insert(date.to_string(), entries);
+        }
+        days
+    }
+
+    #[test]
+    fn test_all_keys()
+    {
+        assert_eq!(all_keys(&days()), ["mood", "note", "steps"]);
+        assert!(all_keys(&IndexMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_csv()
+    {
+        let csv = format_days(&days(), TextFormat::Csv).unwrap();
+        assert_eq!(csv, "date,mood,note,steps\r\n\
+            2024-01-01,good,\"a, \"\"quoted\"\" note\",\r\n\
+            2024-01-02,\"tired | sleepy\nwent to bed\",,9000");
+    }
+
+    #[test]
+    fn test_markdown()
+    {
+        let markdown = format_days(&days(), TextFormat::Markdown).unwrap();
+        assert_eq!(markdown, "| date | mood | note | steps |\n\
+            | --- | --- | --- | --- |\n\
+            | 2024-01-01 | good | a, \"quoted\" note |  |\n\
+            | 2024-01-02 | tired \\| sleepy<br>went to bed |  | 9000 |");
+    }
+
+    #[test]
+    fn test_plain_text_and_json()
+    {
+        let text = format_days(&days(), TextFormat::PlainText).unwrap();
+        assert_eq!(text, "2024-01-01\n  mood: good\n  note: a, \"quoted\" note\n\n\
+            2024-01-02\n  steps: 9000\n  mood: tired | sleepy\nwent to bed");
+
+        let json = format_days(&days(), TextFormat::Json).unwrap();
+        let parsed: DayEntries = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, days());
+    }
+}